@@ -0,0 +1,426 @@
+/* Generated by `custom_codes::c_header::header()`, do not edit. */
+#ifndef CUSTOM_CODES_H
+#define CUSTOM_CODES_H
+
+#include <stdbool.h>
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+#define CC_FAMILY_OUTCOME 1u
+
+typedef enum CcOutcome {
+    CC_OUTCOME_SUCCESS = 1001,
+    CC_OUTCOME_FAILURE = 1002,
+    CC_OUTCOME_FORWARD = 1003,
+} CcOutcome;
+
+#define CC_FAMILY_ACCESS_STATUS 2u
+
+typedef enum CcAccessStatus {
+    CC_ACCESS_STATUS_GRANTED = 2001,
+    CC_ACCESS_STATUS_REVOKED = 2002,
+    CC_ACCESS_STATUS_EXPIRED = 2003,
+    CC_ACCESS_STATUS_DENIED = 2004,
+    CC_ACCESS_STATUS_REJECTED = 2005,
+    CC_ACCESS_STATUS_IN_PROGRESS = 2006,
+    CC_ACCESS_STATUS_ACCEPTED_RAC = 2007,
+    CC_ACCESS_STATUS_REJECTED_RAC = 2008,
+    CC_ACCESS_STATUS_UNSPECIFIED = 2009,
+} CcAccessStatus;
+
+#define CC_FAMILY_CUSTOM_BOOL 3u
+
+typedef enum CcCustomBool {
+    CC_CUSTOM_BOOL_TRUE = 3001,
+    CC_CUSTOM_BOOL_FALSE = 3002,
+    CC_CUSTOM_BOOL_UNSPECIFIED = 3003,
+} CcCustomBool;
+
+#define CC_FAMILY_DB_OPS 4u
+
+typedef enum CcDbOps {
+    CC_DB_OPS_REPO_CREATED = 4001,
+    CC_DB_OPS_REPO_INITIALIZED = 4002,
+    CC_DB_OPS_REPO_ALREADY_EXISTS = 4003,
+    CC_DB_OPS_REPO_EMPTY = 4004,
+    CC_DB_OPS_REPO_NOT_FOUND = 4005,
+    CC_DB_OPS_REPO_DROPPED = 4006,
+    CC_DB_OPS_DB_INTEGRITY_CONSISTENT = 4007,
+    CC_DB_OPS_DB_INTEGRITY_CORRUPTED = 4008,
+    CC_DB_OPS_DOCUMENT_INTEGRITY_CONSISTENT = 4009,
+    CC_DB_OPS_DOCUMENT_INTEGRITY_CORRUPTED = 4010,
+    CC_DB_OPS_DB_CREATED = 4011,
+    CC_DB_OPS_DB_FOUND = 4012,
+    CC_DB_OPS_DB_ALREADY_EXISTS = 4013,
+    CC_DB_OPS_DB_NOT_FOUND = 4014,
+    CC_DB_OPS_DB_EMPTY = 4015,
+    CC_DB_OPS_DB_LIST = 4016,
+    CC_DB_OPS_DB_MODIFIED = 4017,
+    CC_DB_OPS_DB_DROPPED = 4018,
+    CC_DB_OPS_DOCUMENT_CREATED = 4019,
+    CC_DB_OPS_DOCUMENT_INSERTED = 4020,
+    CC_DB_OPS_DOCUMENT_FOUND = 4021,
+    CC_DB_OPS_DOCUMENT_EMPTY = 4022,
+    CC_DB_OPS_DOCUMENT_LIST = 4023,
+    CC_DB_OPS_DOCUMENT_ALREADY_EXISTS = 4024,
+    CC_DB_OPS_DOCUMENT_NOT_FOUND = 4025,
+    CC_DB_OPS_DOCUMENT_MODIFIED = 4026,
+    CC_DB_OPS_DOCUMENT_DROPPED = 4027,
+    CC_DB_OPS_FIELD_CREATED = 4028,
+    CC_DB_OPS_FIELD_INSERTED = 4029,
+    CC_DB_OPS_FIELD_FOUND = 4030,
+    CC_DB_OPS_FIELD_ALREADY_EXISTS = 4031,
+    CC_DB_OPS_FIELD_NOT_FOUND = 4032,
+    CC_DB_OPS_FIELD_CONTENTS = 4033,
+    CC_DB_OPS_FIELD_LIST = 4034,
+    CC_DB_OPS_FIELD_MODIFIED = 4035,
+    CC_DB_OPS_FIELD_DROPPED = 4036,
+    CC_DB_OPS_CREATED = 4037,
+    CC_DB_OPS_COMMITTED = 4038,
+    CC_DB_OPS_UN_COMMITTED = 4039,
+    CC_DB_OPS_INSERTED = 4040,
+    CC_DB_OPS_CHANGED = 4041,
+    CC_DB_OPS_UNCHANGED = 4042,
+    CC_DB_OPS_SKIPPED = 4043,
+    CC_DB_OPS_ALREADY_EXISTS = 4044,
+    CC_DB_OPS_MODIFIED = 4045,
+    CC_DB_OPS_SWAPPED = 4046,
+    CC_DB_OPS_DELETED = 4047,
+    CC_DB_OPS_KEY_FOUND = 4048,
+    CC_DB_OPS_KEY_NOT_FOUND = 4049,
+    CC_DB_OPS_NOT_EXECUTED = 4050,
+    CC_DB_OPS_QUERY_CONFLICT_ERROR = 4051,
+    CC_DB_OPS_EMPTY = 4052,
+    CC_DB_OPS_QUEUED = 4053,
+    CC_DB_OPS_DE_QUEUED = 4054,
+    CC_DB_OPS_COMPACTION_TRUE = 4055,
+    CC_DB_OPS_COMPACTION_FALSE = 4056,
+    CC_DB_OPS_CONN_REFUSED = 4057,
+    CC_DB_OPS_PORT_ADDR_IN_USE = 4058,
+    CC_DB_OPS_DRIVER_ERROR = 4059,
+    CC_DB_OPS_RUNTIME_ERROR = 4060,
+    CC_DB_OPS_ENCOUNTERED_ERRORS = 4061,
+    CC_DB_OPS_PERMISSION_DENIED = 4062,
+    CC_DB_OPS_CLUSTER_ONLINE = 4063,
+    CC_DB_OPS_CLUSTER_OFFLINE = 4064,
+    CC_DB_OPS_CLUSTER_SYNCED = 4065,
+    CC_DB_OPS_CLUSTER_NODE_ADDED = 4066,
+    CC_DB_OPS_CONFIG_UPDATED = 4067,
+    CC_DB_OPS_CLUSTER_NODE_DROPPED = 4068,
+    CC_DB_OPS_LOGGED = 4069,
+    CC_DB_OPS_NOT_LOGGED = 4070,
+    CC_DB_OPS_INTERRUPTED = 4071,
+    CC_DB_OPS_UNSPECIFIED = 4072,
+} CcDbOps;
+
+#define CC_FAMILY_EXEC_COMMAND 5u
+
+typedef enum CcExecCommand {
+    CC_EXEC_COMMAND_QUEUED = 5001,
+    CC_EXEC_COMMAND_DE_QUEUED = 5002,
+    CC_EXEC_COMMAND_IN_PROGRESS = 5003,
+    CC_EXEC_COMMAND_PAUSED = 5004,
+    CC_EXEC_COMMAND_FROZEN = 5005,
+    CC_EXEC_COMMAND_KILLED = 5006,
+    CC_EXEC_COMMAND_EXECUTED = 5007,
+    CC_EXEC_COMMAND_INTERRUPTED = 5008,
+    CC_EXEC_COMMAND_UNSPECIFIED = 5009,
+} CcExecCommand;
+
+#define CC_FAMILY_FILE_OPS 6u
+
+typedef enum CcFileOps {
+    CC_FILE_OPS_DIR_CREATED = 6001,
+    CC_FILE_OPS_DIR_ACCESS_DENIED = 6002,
+    CC_FILE_OPS_DIR_IS_READ_ONLY = 6003,
+    CC_FILE_OPS_DIRECTORY_APPEND_ONLY = 6004,
+    CC_FILE_OPS_FILE_IS_READ_ONLY = 6005,
+    CC_FILE_OPS_FILE_IS_APPEND_ONLY = 6006,
+    CC_FILE_OPS_DELETION_DENIED = 6007,
+    CC_FILE_OPS_DELETION_TO_BE_DONE = 6008,
+    CC_FILE_OPS_READ_DONE = 6009,
+    CC_FILE_OPS_WRITE_DONE = 6010,
+    CC_FILE_OPS_ALREADY_EXISTS = 6011,
+    CC_FILE_OPS_UPDATE_DONE = 6012,
+    CC_FILE_OPS_DELETION_DONE = 6013,
+    CC_FILE_OPS_READ_IN_PROGRESS = 6014,
+    CC_FILE_OPS_WRITE_IN_PROGRESS = 6015,
+    CC_FILE_OPS_UPDATE_IN_PROGRESS = 6016,
+    CC_FILE_OPS_DELETION_IN_PROGRESS = 6017,
+    CC_FILE_OPS_DIR_OPENED = 6018,
+    CC_FILE_OPS_DIR_CLOSED = 6019,
+    CC_FILE_OPS_DIR_MODIFIED = 6020,
+    CC_FILE_OPS_OPENED_FILE = 6021,
+    CC_FILE_OPS_CLOSED_FILE = 6022,
+    CC_FILE_OPS_OPEN_ERROR = 6023,
+    CC_FILE_OPS_ENCOUNTERED_ERRORS = 6024,
+    CC_FILE_OPS_CREATE_DENIED = 6025,
+    CC_FILE_OPS_READ_DENIED = 6026,
+    CC_FILE_OPS_UPDATE_DENIED = 6027,
+    CC_FILE_OPS_WRITE_DENIED = 6028,
+    CC_FILE_OPS_APPEND_DENIED = 6029,
+    CC_FILE_OPS_METADATA_ADDED = 6030,
+    CC_FILE_OPS_METADATA_NOT_AVAILABLE = 6031,
+    CC_FILE_OPS_METADATA_CHANGED = 6032,
+    CC_FILE_OPS_METADATA_DELETED = 6033,
+    CC_FILE_OPS_OPEN_TRUE = 6034,
+    CC_FILE_OPS_OPEN_FALSE = 6035,
+    CC_FILE_OPS_CREATE_TRUE = 6036,
+    CC_FILE_OPS_CREATE_FALSE = 6037,
+    CC_FILE_OPS_WRITE_TRUE = 6038,
+    CC_FILE_OPS_WRITE_FALSE = 6039,
+    CC_FILE_OPS_APPEND_TRUE = 6040,
+    CC_FILE_OPS_INTERRUPTED = 6041,
+    CC_FILE_OPS_DELETE_TRUE = 6042,
+} CcFileOps;
+
+#define CC_FAMILY_COMPRESSION 7u
+
+typedef enum CcCompression {
+    CC_COMPRESSION_DONE = 7001,
+    CC_COMPRESSION_IN_PROGRESS = 7002,
+    CC_COMPRESSION_ALREADY_COMPRESSED = 7003,
+    CC_COMPRESSION_IMPOSSIBLE = 7004,
+    CC_COMPRESSION_TO_BE_DONE = 7005,
+    CC_COMPRESSION_STREAM_COMPRESS = 7006,
+    CC_COMPRESSION_INTERRUPTED = 7007,
+    CC_COMPRESSION_UNSPECIFIED = 7008,
+} CcCompression;
+
+#define CC_FAMILY_SUBSCRIPTION 8u
+
+typedef enum CcSubscription {
+    CC_SUBSCRIPTION_SUBSCRIBED = 8001,
+    CC_SUBSCRIPTION_UNSUBSCRIBED = 8002,
+    CC_SUBSCRIPTION_NOT_ACTIVATED = 8003,
+    CC_SUBSCRIPTION_UNSPECIFIED = 8004,
+} CcSubscription;
+
+#define CC_FAMILY_ACTIVITY_STATUS 9u
+
+typedef enum CcActivityStatus {
+    CC_ACTIVITY_STATUS_ACTIVATED = 9001,
+    CC_ACTIVITY_STATUS_DEACTIVATED = 9002,
+    CC_ACTIVITY_STATUS_UNSPECIFIED = 9003,
+} CcActivityStatus;
+
+#define CC_FAMILY_ACTIVITY_TOGGLE 10u
+
+typedef enum CcActivityToggle {
+    CC_ACTIVITY_TOGGLE_ACTIVATE = 10001,
+    CC_ACTIVITY_TOGGLE_DEACTIVATE = 10002,
+    CC_ACTIVITY_TOGGLE_UNSPECIFIED = 10003,
+} CcActivityToggle;
+
+#define CC_FAMILY_CLI 11u
+
+typedef enum CcCli {
+    CC_CLI_PARSE_IN_PROGRESS = 11001,
+    CC_CLI_PARSE_ARGS = 11002,
+    CC_CLI_ARG_FIELD_PARSE_IN_PROGRESS = 11003,
+    CC_CLI_PARSED_ARG_FIELD = 11004,
+    CC_CLI_PARSE_ASYNC = 11005,
+    CC_CLI_ARG_FIELD_ASYNC_PARSE_IN_PROGRESS = 11006,
+    CC_CLI_NOT_ENOUGH_ARGS = 11007,
+    CC_CLI_ENCOUNTERED_ERRORS = 11008,
+} CcCli;
+
+#define CC_FAMILY_DATE_TIME_OP 12u
+
+typedef enum CcDateTimeOp {
+    CC_DATE_TIME_OP_DATE_CREATED = 12001,
+    CC_DATE_TIME_OP_DATE_DELETED = 12002,
+    CC_DATE_TIME_OP_DATE_MODIFIED = 12003,
+    CC_DATE_TIME_OP_DATE_UNSPECIFIED = 12004,
+    CC_DATE_TIME_OP_TIME_CREATED = 12005,
+    CC_DATE_TIME_OP_TIME_DELETED = 12006,
+    CC_DATE_TIME_OP_TIME_MODIFIED = 12007,
+    CC_DATE_TIME_OP_TIME_UNSPECIFIED = 12008,
+    CC_DATE_TIME_OP_DATE_TIME_UNSPECIFIED = 12009,
+} CcDateTimeOp;
+
+#define CC_FAMILY_SEC_OPS 13u
+
+typedef enum CcSecOps {
+    CC_SEC_OPS_KEY_CORRUPTED = 13001,
+    CC_SEC_OPS_KEY_AUTHENTIC = 13002,
+    CC_SEC_OPS_KEY_INVALID = 13003,
+    CC_SEC_OPS_TIMED_OUT = 13004,
+    CC_SEC_OPS_POSSIBLE_ID_THEFT = 13005,
+    CC_SEC_OPS_DATA_CORRUPTED = 13006,
+    CC_SEC_OPS_DATA_INVALID = 13007,
+    CC_SEC_OPS_ENCRYPTED = 13008,
+    CC_SEC_OPS_KEY_GEN_SUCCEEDED = 13009,
+    CC_SEC_OPS_KEY_GEN_FAILED = 13010,
+    CC_SEC_OPS_KEY_DELETED = 13011,
+    CC_SEC_OPS_CRYPTO_RANDOM_GENERATED = 13012,
+    CC_SEC_OPS_CRYPTO_RANDOM_GEN_FAILURE = 13013,
+    CC_SEC_OPS_NON_CRYPTO_RANDOM_GENERATED = 13014,
+    CC_SEC_OPS_NON_CRYPTO_RANDOM_GEN_FAILURE = 13015,
+    CC_SEC_OPS_AUTHENTIC_MAC = 13016,
+    CC_SEC_OPS_INVALID_MAC = 13017,
+    CC_SEC_OPS_VALID_RAC = 13018,
+    CC_SEC_OPS_INVALID_RAC = 13019,
+    CC_SEC_OPS_KEY_LENGTH_SANE = 13020,
+    CC_SEC_OPS_KEY_TOO_SHORT = 13021,
+    CC_SEC_OPS_KEY_TOO_LONG = 13022,
+} CcSecOps;
+
+#define CC_FAMILY_KEY_LENGTH 14u
+
+typedef enum CcKeyLength {
+    CC_KEY_LENGTH_BYTES8 = 14001,
+    CC_KEY_LENGTH_BYTES16 = 14002,
+    CC_KEY_LENGTH_BYTES24 = 14003,
+    CC_KEY_LENGTH_BYTES32 = 14004,
+    CC_KEY_LENGTH_BYTES64 = 14005,
+    CC_KEY_LENGTH_BYTES128 = 14006,
+    CC_KEY_LENGTH_BYTES256 = 14007,
+    CC_KEY_LENGTH_BYTES512 = 14008,
+    CC_KEY_LENGTH_BYTES1024 = 14009,
+    CC_KEY_LENGTH_BYTES2048 = 14010,
+    CC_KEY_LENGTH_BYTES4096 = 14011,
+} CcKeyLength;
+
+#define CC_FAMILY_HARDWARE_RESOURCES 15u
+
+typedef enum CcHardwareResources {
+    CC_HARDWARE_RESOURCES_CPU_UNDER_HEAVY_LOAD = 15001,
+    CC_HARDWARE_RESOURCES_RAM_UNDER_HEAVY_LOAD = 15002,
+    CC_HARDWARE_RESOURCES_CPU_MAXED = 15003,
+    CC_HARDWARE_RESOURCES_CPU_OVERCLOCKED = 15004,
+    CC_HARDWARE_RESOURCES_CPU_UNDERCLOCKED = 15005,
+    CC_HARDWARE_RESOURCES_CPU_IDLE = 15006,
+    CC_HARDWARE_RESOURCES_CPU_TEMP_OK = 15007,
+    CC_HARDWARE_RESOURCES_CPU_THROTTLING = 15008,
+    CC_HARDWARE_RESOURCES_ETHERNET_DEV_UP = 15009,
+    CC_HARDWARE_RESOURCES_ETHERNET_DEV_DOWN = 15010,
+    CC_HARDWARE_RESOURCES_ETHERNET_DEV_ACCESSED = 15011,
+    CC_HARDWARE_RESOURCES_CARRIER_DEV_UP = 15012,
+    CC_HARDWARE_RESOURCES_CARRIER_DEV_DOWN = 15013,
+    CC_HARDWARE_RESOURCES_CARRIER_DEV_CHIP_MISSING = 15014,
+    CC_HARDWARE_RESOURCES_DRIVER_MISSING = 15015,
+    CC_HARDWARE_RESOURCES_OPTIC_DEV_UP = 15016,
+    CC_HARDWARE_RESOURCES_OPTIC_DEV_DOWN = 15017,
+    CC_HARDWARE_RESOURCES_OPTICAL_DEV_ACCESSED = 15018,
+    CC_HARDWARE_RESOURCES_OPTICAL_DEV_ACCESS_DENIED = 15019,
+    CC_HARDWARE_RESOURCES_OPTICAL_DEV_POWERED_OFF = 15020,
+    CC_HARDWARE_RESOURCES_OPTICAL_DEV_CHIP_MISSING = 15021,
+    CC_HARDWARE_RESOURCES_NFC_DEV_UP = 15022,
+    CC_HARDWARE_RESOURCES_NFC_DEV_DOWN = 15023,
+    CC_HARDWARE_RESOURCES_NFC_DEV_ACTIVE = 15024,
+    CC_HARDWARE_RESOURCES_NFC_DEV_PAIRED = 15025,
+    CC_HARDWARE_RESOURCES_NFC_DEV_ACCESSED = 15026,
+    CC_HARDWARE_RESOURCES_NFC_DEV_DENIED = 15027,
+    CC_HARDWARE_RESOURCES_NFC_DEV_CLOSED = 15028,
+    CC_HARDWARE_RESOURCES_NFC_DEV_POWERED_OFF = 15029,
+    CC_HARDWARE_RESOURCES_WI_FI_DEV_UP = 15030,
+    CC_HARDWARE_RESOURCES_WI_FI_DEV_DOWN = 15031,
+    CC_HARDWARE_RESOURCES_WI_FI_DEV_ACCESS_DENIED = 15032,
+    CC_HARDWARE_RESOURCES_WI_FI_DEV_POWERED_OFF = 15033,
+    CC_HARDWARE_RESOURCES_WI_FI_DEV_ACCESSED = 15034,
+    CC_HARDWARE_RESOURCES_WI_FI_DEV_HOT_SPOT_MODE = 15035,
+    CC_HARDWARE_RESOURCES_BLUETOOTH_DEV_UP = 15036,
+    CC_HARDWARE_RESOURCES_BLUETOOTH_DEV_PAIRED = 15037,
+    CC_HARDWARE_RESOURCES_BLUETOOTH_DEV_DEV_TRUSTED = 15038,
+    CC_HARDWARE_RESOURCES_BLUETOOTH_DEV_DEV_UNTRUSTED = 15039,
+    CC_HARDWARE_RESOURCES_BLUETOOTH_DEV_ACCESS_DENIED = 15040,
+    CC_HARDWARE_RESOURCES_BLUETOOTH_DEV_UNPAIRED = 15041,
+    CC_HARDWARE_RESOURCES_BLUETOOTH_DEV_SHARE_MODE = 15042,
+    CC_HARDWARE_RESOURCES_BLUETOOTH_DEV_SINK_MODE = 15043,
+    CC_HARDWARE_RESOURCES_BLUETOOTH_DEV_TX_MODE = 15044,
+    CC_HARDWARE_RESOURCES_BLUETOOTH_DEV_RX_MODE = 15045,
+    CC_HARDWARE_RESOURCES_BLUETOOTH_DEV_CLOSED = 15046,
+    CC_HARDWARE_RESOURCES_BLUETOOTH_DEV_POWER_OFF = 15047,
+    CC_HARDWARE_RESOURCES_BLUETOOTH_DEV_DOWN = 15048,
+    CC_HARDWARE_RESOURCES_USB_DEV_UP = 15049,
+    CC_HARDWARE_RESOURCES_USB_DEV_DOWN = 15050,
+    CC_HARDWARE_RESOURCES_USB_DEV_POWERED_OFF = 15051,
+    CC_HARDWARE_RESOURCES_USB_DEV_ADDED = 15052,
+    CC_HARDWARE_RESOURCES_USB_DEV_TX_MODE = 15053,
+    CC_HARDWARE_RESOURCES_USB_DEV_RX_MODE = 15054,
+    CC_HARDWARE_RESOURCES_USB_DEV_REMOVED = 15055,
+    CC_HARDWARE_RESOURCES_USB_DEV_ACCESS_DENIED = 15056,
+    CC_HARDWARE_RESOURCES_GPS_DEV_UP = 15057,
+    CC_HARDWARE_RESOURCES_GPS_DEV_DOWN = 15058,
+    CC_HARDWARE_RESOURCES_GPS_DEV_TX_MODE = 15059,
+    CC_HARDWARE_RESOURCES_GPS_DEV_RX_MODE = 15060,
+    CC_HARDWARE_RESOURCES_GPS_DEV_ACCESS_DENIED = 15061,
+    CC_HARDWARE_RESOURCES_AIRPLANE_MODE = 15062,
+    CC_HARDWARE_RESOURCES_UNSPECIFIED = 15063,
+} CcHardwareResources;
+
+#define CC_FAMILY_NETWORKING 16u
+
+typedef enum CcNetworking {
+    CC_NETWORKING_NETWORK_ACCESS_DENIED = 16001,
+    CC_NETWORKING_HOST_UNREACHABLE = 16002,
+    CC_NETWORKING_CONNECTION_RESET = 16003,
+    CC_NETWORKING_CONNECTION_ABORTED = 16004,
+    CC_NETWORKING_NOT_CONNECTED = 16005,
+    CC_NETWORKING_HEADERS_CORRUPTED = 16006,
+    CC_NETWORKING_SERVER_NOT_RESPONDING = 16007,
+    CC_NETWORKING_IP_IN_USE = 16008,
+    CC_NETWORKING_PORT_IN_USE = 16009,
+    CC_NETWORKING_ADDR_IN_USE = 16010,
+    CC_NETWORKING_IP_AVAILABLE = 16011,
+    CC_NETWORKING_BROKEN_PIPE = 16012,
+    CC_NETWORKING_NET_CONN_EXISTS = 16013,
+    CC_NETWORKING_INVALID_NET_INPUT = 16014,
+    CC_NETWORKING_INVALID_NET_DATA = 16015,
+    CC_NETWORKING_NET_TIMED_OUT = 16016,
+    CC_NETWORKING_INTERRUPTED = 16017,
+    CC_NETWORKING_UNEXPECTED_NET_EOF = 16018,
+    CC_NETWORKING_NET_DRIVER_BUGGY = 16019,
+    CC_NETWORKING_UNSPECIFIED = 16020,
+} CcNetworking;
+
+#define CC_FAMILY_SEC_HARDWARE 17u
+
+typedef enum CcSecHardware {
+    CC_SEC_HARDWARE_USB_KEY_UP = 17001,
+    CC_SEC_HARDWARE_USB_KEY_DOWN = 17002,
+    CC_SEC_HARDWARE_USB_KEY_DEV_TX_MODE = 17003,
+    CC_SEC_HARDWARE_USB_KEY_DEV_RX_MODE = 17004,
+    CC_SEC_HARDWARE_USB_KEY_DEV_ACCESS_DENIED = 17005,
+    CC_SEC_HARDWARE_FINGER_PRINT_DEV_UP = 17006,
+    CC_SEC_HARDWARE_FINGER_PRINT_DEV_DOWN = 17007,
+    CC_SEC_HARDWARE_FINGER_PRINT_DEV_POWERED_OFF = 17008,
+    CC_SEC_HARDWARE_FINGER_PRINT_DEV_TX_MODE = 17009,
+    CC_SEC_HARDWARE_FINGER_PRINT_DEV_RX_MODE = 17010,
+    CC_SEC_HARDWARE_FINGER_PRINT_DEV_ACCESS_DENIED = 17011,
+    CC_SEC_HARDWARE_IRIS_DEV_UP = 17012,
+    CC_SEC_HARDWARE_IRIS_DEV_DOWN = 17013,
+    CC_SEC_HARDWARE_IRIS_DEV_POWERED_OFF = 17014,
+    CC_SEC_HARDWARE_IRIS_DEV_TX_MODE = 17015,
+    CC_SEC_HARDWARE_IRIS_DEV_RX_MODE = 17016,
+    CC_SEC_HARDWARE_IRIS_DEV_ACCESS_DENIED = 17017,
+    CC_SEC_HARDWARE_IR_FLOOD_DEV_UP = 17018,
+    CC_SEC_HARDWARE_IR_FLOOD_DEV_DOWN = 17019,
+    CC_SEC_HARDWARE_IR_FLOOD_DEV_POWERED_OFF = 17020,
+    CC_SEC_HARDWARE_IR_DEV_TX_MODE = 17021,
+    CC_SEC_HARDWARE_IR_DEV_RX_MODE = 17022,
+    CC_SEC_HARDWARE_IR_DEV_ACCESS_DENIED = 17023,
+    CC_SEC_HARDWARE_UNSPECIFIED = 17024,
+} CcSecHardware;
+
+/* The name of the variant of a code, eg. "DocumentNotFound", NULL if the code is unknown */
+const char *cc_code_name(uint32_t code);
+
+/* A message describing a code in English, NULL if the code is unknown */
+const char *cc_code_message(uint32_t code);
+
+/* The name of the family of a code, eg. "DbOps", NULL if the code is unknown */
+const char *cc_code_family(uint32_t code);
+
+/* Whether a code reports a successful operation or a healthy state, false if the code is unknown */
+bool cc_code_is_success(uint32_t code);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* CUSTOM_CODES_H */
@@ -0,0 +1,92 @@
+#![deny(missing_docs)]
+//! The C ABI of `custom_codes` for hosts written in C, C++ or any language able to call C functions.
+//!
+//! A code crosses the boundary as the number returned by `Code::code()`, eg. `4025` for `DbOps::DocumentNotFound`,
+//! whose constants are declared by `include/custom_codes.h`. The returned strings are static,
+//! they must not be freed by the caller and stay valid for the lifetime of the process.
+//! ### Examples
+//! ```
+//! use custom_codes::{Code, DbOps};
+//! use custom_codes_ffi::{cc_code_family, cc_code_is_success, cc_code_name};
+//! use std::ffi::CStr;
+//!
+//! let code = DbOps::DocumentNotFound.code();
+//! let name = unsafe { CStr::from_ptr(cc_code_name(code)) };
+//! let family = unsafe { CStr::from_ptr(cc_code_family(code)) };
+//! assert_eq!(name.to_str(), Ok("DocumentNotFound"));
+//! assert_eq!(family.to_str(), Ok("DbOps"));
+//! assert!(!cc_code_is_success(code));
+//! assert!(cc_code_name(999_999).is_null());
+//! ```
+
+use custom_codes::FAMILIES;
+use std::collections::HashMap;
+use std::ffi::CString;
+use std::os::raw::c_char;
+use std::ptr;
+use std::sync::OnceLock;
+
+/// What the C functions return for a code
+struct Entry {
+    name: CString,
+    message: CString,
+    family: CString,
+    success: bool,
+}
+
+/// Every known code, built on the first call
+fn entries() -> &'static HashMap<u32, Entry> {
+    static ENTRIES: OnceLock<HashMap<u32, Entry>> = OnceLock::new();
+
+    ENTRIES.get_or_init(|| {
+        FAMILIES
+            .iter()
+            .flat_map(|family| {
+                family.variants.iter().map(move |variant| {
+                    let entry = Entry {
+                        name: c_string(variant.name),
+                        message: c_string(&variant.description()),
+                        family: c_string(family.name),
+                        success: variant.success,
+                    };
+
+                    (family.code(variant), entry)
+                })
+            })
+            .collect()
+    })
+}
+
+fn c_string(value: &str) -> CString {
+    CString::new(value).expect("names and doc comments do not contain NUL bytes")
+}
+
+fn lookup(code: u32, field: impl Fn(&Entry) -> &CString) -> *const c_char {
+    entries()
+        .get(&code)
+        .map_or(ptr::null(), |entry| field(entry).as_ptr())
+}
+
+/// The name of the variant of a code, eg. `"DocumentNotFound"`, NULL if the code is unknown
+#[no_mangle]
+pub extern "C" fn cc_code_name(code: u32) -> *const c_char {
+    lookup(code, |entry| &entry.name)
+}
+
+/// A message describing a code in English, NULL if the code is unknown
+#[no_mangle]
+pub extern "C" fn cc_code_message(code: u32) -> *const c_char {
+    lookup(code, |entry| &entry.message)
+}
+
+/// The name of the family of a code, eg. `"DbOps"`, NULL if the code is unknown
+#[no_mangle]
+pub extern "C" fn cc_code_family(code: u32) -> *const c_char {
+    lookup(code, |entry| &entry.family)
+}
+
+/// Whether a code reports a successful operation or a healthy state, false if the code is unknown
+#[no_mangle]
+pub extern "C" fn cc_code_is_success(code: u32) -> bool {
+    entries().get(&code).is_some_and(|entry| entry.success)
+}
@@ -0,0 +1,32 @@
+#include <stdio.h>
+#include <string.h>
+
+#include "custom_codes.h"
+
+static int failures = 0;
+
+static void check(int condition, const char *description) {
+    if (!condition) {
+        fprintf(stderr, "failed: %s\n", description);
+        failures++;
+    }
+}
+
+int main(void) {
+    CcDbOps code = CC_DB_OPS_DOCUMENT_NOT_FOUND;
+
+    check(code == 4025, "the constant is the numeric code");
+    check(strcmp(cc_code_name(code), "DocumentNotFound") == 0, "cc_code_name");
+    check(strcmp(cc_code_family(code), "DbOps") == 0, "cc_code_family");
+    check(strcmp(cc_code_message(code), "Document does not exist") == 0, "cc_code_message");
+    check(!cc_code_is_success(code), "a missing document is a failure");
+    check(cc_code_is_success(CC_OUTCOME_SUCCESS), "a success is a success");
+    check(code / 1000 == CC_FAMILY_DB_OPS, "the family is the thousands of the code");
+
+    check(cc_code_name(999999) == NULL, "unknown codes have no name");
+    check(cc_code_message(999999) == NULL, "unknown codes have no message");
+    check(cc_code_family(999999) == NULL, "unknown codes have no family");
+    check(!cc_code_is_success(999999), "unknown codes are not successes");
+
+    return failures == 0 ? 0 : 1;
+}
@@ -0,0 +1,56 @@
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Run with `UPDATE_HEADER=1` to regenerate the shipped header after changing a code family
+#[test]
+fn shipped_header_is_up_to_date() {
+    let path = concat!(env!("CARGO_MANIFEST_DIR"), "/include/custom_codes.h");
+    let header = custom_codes::c_header::header();
+    if std::env::var_os("UPDATE_HEADER").is_some() {
+        std::fs::write(path, &header).unwrap();
+    }
+
+    let shipped = std::fs::read_to_string(path).unwrap();
+    assert!(
+        shipped == header,
+        "ffi/include/custom_codes.h is out of date, regenerate it with `UPDATE_HEADER=1 cargo test -p custom_codes_ffi`"
+    );
+}
+
+/// The `deps` directory holding both this test and `libcustom_codes_ffi.a`
+fn artifacts_dir() -> PathBuf {
+    let exe = std::env::current_exe().unwrap();
+    exe.parent().unwrap().to_path_buf()
+}
+
+/// Compiles `tests/c/test.c` against the shipped header and the static library then runs it.
+/// Skipped when no C compiler is installed
+#[test]
+fn c_program_uses_the_abi() {
+    if !cfg!(unix) || Command::new("cc").arg("--version").output().is_err() {
+        eprintln!("skipping, `cc` is not available");
+        return;
+    }
+
+    let manifest_dir = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let artifacts = artifacts_dir();
+    let program = artifacts.join("custom_codes_c_test");
+
+    let status = Command::new("cc")
+        .arg(manifest_dir.join("tests/c/test.c"))
+        .arg("-I")
+        .arg(manifest_dir.join("include"))
+        .arg(artifacts.join("libcustom_codes_ffi.a"))
+        .args(["-lpthread", "-ldl", "-lm", "-o"])
+        .arg(&program)
+        .status()
+        .unwrap();
+    assert!(status.success(), "compiling tests/c/test.c failed");
+
+    let output = Command::new(&program).output().unwrap();
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
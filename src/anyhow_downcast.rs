@@ -1,6 +1,15 @@
+use std::array::TryFromSliceError;
+use std::env::VarError;
 use std::error::Error;
+use std::ffi::NulError;
 use std::fmt;
 use std::io::ErrorKind;
+use std::net::AddrParseError;
+use std::num::{ParseFloatError, ParseIntError};
+use std::str::Utf8Error;
+use std::string::FromUtf8Error;
+use std::sync::PoisonError;
+use std::time::SystemTimeError;
 
 /// Eenable downcasting to a borrowed string
 #[derive(Debug)]
@@ -18,13 +27,13 @@ impl<'se> Error for BorrowedStr<'se> {}
 #[derive(Debug)]
 pub struct StringifyError(pub String);
 
-impl<'se> fmt::Display for StringifyError {
+impl fmt::Display for StringifyError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "{}", self.0)
     }
 }
 
-impl<'se> Error for StringifyError {}
+impl Error for StringifyError {}
 
 /// All common errors for use in crates. They mirror std::io::ErrorKind;
 #[derive(Debug)]
@@ -79,6 +88,45 @@ pub enum DownCastErrors<'se> {
     Unspecified,
     /// Any `StripPrefix`  error
     StripPrefixError(std::path::StripPrefixError),
+    /// An integer could not be parsed, carrying the reason for the failure
+    ParseIntError(std::num::IntErrorKind),
+    /// A float could not be parsed
+    ParseFloatError(std::num::ParseFloatError),
+    /// A byte slice is not valid UTF-8
+    Utf8Error {
+        /// Number of leading bytes that were valid UTF-8
+        valid_up_to: usize,
+        /// Length of the invalid byte sequence, `None` if the input ended unexpectedly
+        error_len: Option<usize>,
+    },
+    /// A `Vec<u8>` could not be converted into a `String` since it is not valid UTF-8
+    FromUtf8Error {
+        /// Number of leading bytes that were valid UTF-8
+        valid_up_to: usize,
+        /// Length of the invalid byte sequence, `None` if the input ended unexpectedly
+        error_len: Option<usize>,
+    },
+    /// An IP or socket address could not be parsed
+    AddrParseError(std::net::AddrParseError),
+    /// A `SystemTime` is earlier than the time it was compared to,
+    /// carrying how far back in time it is
+    SystemTimeError(std::time::Duration),
+    /// An environment variable is not present or is not valid unicode
+    VarError(std::env::VarError),
+    /// A string passed to `CString` contains an interior nul byte
+    NulError {
+        /// Position of the nul byte
+        position: usize,
+        /// The bytes that were being converted
+        bytes: Vec<u8>,
+    },
+    /// A lock is poisoned since a thread panicked while holding it.
+    ///
+    /// Since a `PoisonError` is generic over the guard it carries, map it into a `PoisonError<()>` before
+    /// converting it into an `anyhow::Error`, eg. `mutex.lock().map_err(|_| PoisonError::new(()))`
+    PoisonError,
+    /// A slice could not be converted into an array since the lengths do not match
+    TryFromSliceError,
     /// An `OsString` Error
     Stringify(String),
     /// Returns an error as a borrowed string
@@ -91,15 +139,211 @@ pub enum DownCastErrors<'se> {
     InvalidFolder,
     /// Path is not valid
     InvalidPath,
+    /// `serde_json` input is not syntactically valid JSON
+    #[cfg(feature = "serde_json")]
+    JsonSyntax {
+        /// Line at which the error occurred, starting at 1
+        line: usize,
+        /// Column at which the error occurred, starting at 1
+        column: usize,
+    },
+    /// `serde_json` input is valid JSON but does not match the type being deserialized
+    #[cfg(feature = "serde_json")]
+    JsonData {
+        /// Line at which the error occurred, starting at 1
+        line: usize,
+        /// Column at which the error occurred, starting at 1
+        column: usize,
+    },
+    /// `serde_json` input ended before a complete JSON value was read
+    #[cfg(feature = "serde_json")]
+    JsonEof {
+        /// Line at which the error occurred, starting at 1
+        line: usize,
+        /// Column at which the error occurred, starting at 1
+        column: usize,
+    },
+    /// A `toml` document could not be deserialized
+    #[cfg(feature = "toml")]
+    TomlDe {
+        /// The reason the document could not be deserialized
+        message: String,
+        /// Byte range of the document where the error occurred
+        span: Option<std::ops::Range<usize>>,
+    },
+    /// A `tokio` timeout elapsed before the future completed
+    #[cfg(feature = "tokio")]
+    TokioElapsed,
+    /// A `tokio` task did not run to completion
+    #[cfg(feature = "tokio")]
+    TokioJoinError {
+        /// The task was cancelled, otherwise it panicked
+        cancelled: bool,
+    },
+    /// A `reqwest` request failed
+    #[cfg(feature = "reqwest")]
+    Reqwest {
+        /// The stage of the request that failed
+        kind: crate::HttpErrorKind,
+        /// The HTTP status code if the error was caused by the response status
+        status: Option<u16>,
+        /// The URL of the request
+        url: Option<String>,
+    },
+    /// A `hyper` connection failed
+    #[cfg(feature = "hyper")]
+    Hyper(crate::HttpErrorKind),
+    /// The outermost code attached to the error with `coded::WithCode` or `coded::CodedError`,
+    /// returned before the root cause is inspected
+    Coded(&'se crate::coded::CodedError),
     /// No matches were found when downcasting the error to `std::io::Error` so it is not an `I/O` error
+    #[cfg(feature = "anyhow")]
     Unmatched(&'se anyhow::Error),
+    /// No matches were found when downcasting the `std::error::Error` passed to `try_downcast_dyn`
+    UnmatchedDyn(&'se (dyn Error + 'static)),
+}
+
+/// This method tries to downcast an `anyhow::Error` to return a `DownCastErrors` enum for common error handling.
+///
+/// A code attached to the error with `coded::WithCode` is returned as `DownCastErrors::Coded` before the
+/// root cause is inspected
+/// ### Examples
+/// ```
+/// # use custom_codes::{try_downcast, DownCastErrors};
+/// use std::num::IntErrorKind;
+///
+/// let error = anyhow::Error::new("foo".parse::<u8>().unwrap_err());
+/// match try_downcast(&error) {
+///     DownCastErrors::ParseIntError(kind) => assert_eq!(kind, IntErrorKind::InvalidDigit),
+///     _ => panic!("expected a `ParseIntError`"),
+/// }
+///
+/// let error = anyhow::Error::new(String::from_utf8(vec![b'a', 0xff]).unwrap_err());
+/// match try_downcast(&error) {
+///     DownCastErrors::FromUtf8Error { valid_up_to, error_len } => {
+///         assert_eq!(valid_up_to, 1);
+///         assert_eq!(error_len, Some(1));
+///     }
+///     _ => panic!("expected a `FromUtf8Error`"),
+/// }
+/// ```
+#[cfg(feature = "anyhow")]
+pub fn try_downcast(error: &anyhow::Error) -> DownCastErrors<'_> {
+    if let Some(coded) = crate::coded::outermost_code(error.as_ref()) {
+        DownCastErrors::Coded(coded)
+    } else if let Some(found) = classify(error.root_cause()) {
+        found
+    } else if let Some(ecosystem_error) = crate::ecosystem::downcast(error.as_ref()) {
+        ecosystem_error
+    } else {
+        DownCastErrors::Unmatched(error)
+    }
+}
+
+/// This method tries to downcast any `std::error::Error` to return a `DownCastErrors` enum for common error handling.
+///
+/// The `source()` chain of the error is walked to its root cause, which is classified exactly like `try_downcast`
+/// classifies an `anyhow::Error`, so it can be used by crates that do not depend on `anyhow`.
+/// Like `try_downcast`, the outermost `coded::CodedError` of the chain is returned as `DownCastErrors::Coded` first
+/// ### Examples
+/// ```
+/// # use custom_codes::{try_downcast_dyn, DownCastErrors};
+/// use std::error::Error;
+/// use std::fmt;
+///
+/// #[derive(Debug)]
+/// struct ConfigError(std::io::Error);
+///
+/// impl fmt::Display for ConfigError {
+///     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+///         write!(f, "unable to read the config file")
+///     }
+/// }
+///
+/// impl Error for ConfigError {
+///     fn source(&self) -> Option<&(dyn Error + 'static)> {
+///         Some(&self.0)
+///     }
+/// }
+///
+/// let error: Box<dyn Error> = Box::new(ConfigError(std::io::ErrorKind::NotFound.into()));
+/// assert!(matches!(try_downcast_dyn(error.as_ref()), DownCastErrors::NotFound));
+/// ```
+pub fn try_downcast_dyn<'se>(error: &'se (dyn Error + 'static)) -> DownCastErrors<'se> {
+    if let Some(coded) = crate::coded::outermost_code(error) {
+        return DownCastErrors::Coded(coded);
+    }
+
+    let mut root = error;
+    while let Some(source) = root.source() {
+        root = source;
+    }
+
+    if let Some(found) = classify(root) {
+        found
+    } else if let Some(ecosystem_error) = crate::ecosystem::downcast(error) {
+        ecosystem_error
+    } else {
+        DownCastErrors::UnmatchedDyn(error)
+    }
 }
 
-/// This method tries to downcast an `anyhow::Error` to return a `DownCastErrors` enum for common error handling
-pub fn try_downcast(error: &anyhow::Error) -> DownCastErrors {
-    if let Some(ioerror) = error.root_cause().downcast_ref::<std::io::Error>() {
-        let kind = ioerror.kind();
+/// Classifies the root cause of an error into one of the `DownCastErrors` it mirrors
+fn classify(root: &(dyn Error + 'static)) -> Option<DownCastErrors<'static>> {
+    let found = if let Some(ioerror) = root.downcast_ref::<std::io::Error>() {
+        DownCastErrors::from(ioerror.kind())
+    } else if let Some(strip_prefix_error) = root.downcast_ref::<std::path::StripPrefixError>() {
+        DownCastErrors::StripPrefixError(strip_prefix_error.clone())
+    } else if let Some(parse_int_error) = root.downcast_ref::<ParseIntError>() {
+        DownCastErrors::ParseIntError(*parse_int_error.kind())
+    } else if let Some(parse_float_error) = root.downcast_ref::<ParseFloatError>() {
+        DownCastErrors::ParseFloatError(parse_float_error.clone())
+    } else if let Some(utf8_error) = root.downcast_ref::<Utf8Error>() {
+        DownCastErrors::Utf8Error {
+            valid_up_to: utf8_error.valid_up_to(),
+            error_len: utf8_error.error_len(),
+        }
+    } else if let Some(from_utf8_error) = root.downcast_ref::<FromUtf8Error>() {
+        DownCastErrors::FromUtf8Error {
+            valid_up_to: from_utf8_error.utf8_error().valid_up_to(),
+            error_len: from_utf8_error.utf8_error().error_len(),
+        }
+    } else if let Some(addr_parse_error) = root.downcast_ref::<AddrParseError>() {
+        DownCastErrors::AddrParseError(addr_parse_error.clone())
+    } else if let Some(system_time_error) = root.downcast_ref::<SystemTimeError>() {
+        DownCastErrors::SystemTimeError(system_time_error.duration())
+    } else if let Some(var_error) = root.downcast_ref::<VarError>() {
+        DownCastErrors::VarError(var_error.clone())
+    } else if let Some(nul_error) = root.downcast_ref::<NulError>() {
+        DownCastErrors::NulError {
+            position: nul_error.nul_position(),
+            bytes: nul_error.clone().into_vec(),
+        }
+    } else if root.downcast_ref::<PoisonError<()>>().is_some() {
+        DownCastErrors::PoisonError
+    } else if root.downcast_ref::<TryFromSliceError>().is_some() {
+        DownCastErrors::TryFromSliceError
+    } else if let Some(os_string_error) = root.downcast_ref::<StringifyError>() {
+        DownCastErrors::Stringify(os_string_error.0.clone())
+    } else if let Some(borrowed_str) = root.downcast_ref::<BorrowedStr>() {
+        DownCastErrors::BorrowedStr(borrowed_str.0)
+    } else if root.downcast_ref::<InvalidFile>().is_some() {
+        DownCastErrors::InvalidFile
+    } else if root.downcast_ref::<InvalidFileName>().is_some() {
+        DownCastErrors::InvalidFileName
+    } else if root.downcast_ref::<InvalidFolder>().is_some() {
+        DownCastErrors::InvalidFolder
+    } else if root.downcast_ref::<InvalidPath>().is_some() {
+        DownCastErrors::InvalidPath
+    } else {
+        return None;
+    };
+
+    Some(found)
+}
 
+impl<'se> From<ErrorKind> for DownCastErrors<'se> {
+    fn from(kind: ErrorKind) -> Self {
         match kind {
             ErrorKind::NotFound => DownCastErrors::NotFound,
             ErrorKind::PermissionDenied => DownCastErrors::PermissionDenied,
@@ -121,29 +365,6 @@ pub fn try_downcast(error: &anyhow::Error) -> DownCastErrors {
             ErrorKind::UnexpectedEof => DownCastErrors::UnexpectedEof,
             _ => DownCastErrors::Unspecified,
         }
-    } else if let Some(strip_prefix_error) = error
-        .root_cause()
-        .downcast_ref::<std::path::StripPrefixError>()
-    {
-        DownCastErrors::StripPrefixError(strip_prefix_error.clone())
-    } else if let Some(os_string_error) = error.root_cause().downcast_ref::<StringifyError>() {
-        DownCastErrors::Stringify(os_string_error.0.clone())
-    } else if let Some(borrowed_str) = error.root_cause().downcast_ref::<BorrowedStr>() {
-        DownCastErrors::BorrowedStr(borrowed_str.0)
-    } else if error.root_cause().downcast_ref::<InvalidFile>().is_some() {
-        DownCastErrors::InvalidFile
-    } else if error
-        .root_cause()
-        .downcast_ref::<InvalidFileName>()
-        .is_some()
-    {
-        DownCastErrors::InvalidFileName
-    } else if error.root_cause().downcast_ref::<InvalidFolder>().is_some() {
-        DownCastErrors::InvalidFolder
-    } else if error.root_cause().downcast_ref::<InvalidPath>().is_some() {
-        DownCastErrors::InvalidPath
-    } else {
-        DownCastErrors::Unmatched(error)
     }
 }
 
@@ -153,7 +374,7 @@ pub struct InvalidFile;
 
 impl fmt::Display for InvalidFile {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{}", self)
+        write!(f, "The file is invalid")
     }
 }
 
@@ -165,7 +386,7 @@ pub struct InvalidFileName;
 
 impl fmt::Display for InvalidFileName {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{}", self)
+        write!(f, "Name of a file is invalid")
     }
 }
 
@@ -177,7 +398,7 @@ pub struct InvalidFolder;
 
 impl fmt::Display for InvalidFolder {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{}", self)
+        write!(f, "Path is not a directory")
     }
 }
 
@@ -188,7 +409,7 @@ pub struct InvalidPath;
 
 impl fmt::Display for InvalidPath {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{}", self)
+        write!(f, "Path is not valid")
     }
 }
 
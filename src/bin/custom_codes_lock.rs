@@ -0,0 +1,64 @@
+//! Checks the code families of this build against a lockfile, `custom_codes.lock` by default, or updates it.
+//! `check` exits with a failure when a variant was removed, renumbered or retyped and lists the additions.
+//!
+//! ```sh
+//! cargo run --bin custom_codes_lock -- check
+//! cargo run --bin custom_codes_lock -- update path/to/custom_codes.lock
+//! ```
+
+use custom_codes::lock::{Lockfile, LOCKFILE};
+use std::path::PathBuf;
+use std::process::exit;
+
+fn main() {
+    let mut args = std::env::args().skip(1);
+    let command = args.next();
+    let path = args
+        .next()
+        .map_or_else(|| PathBuf::from(LOCKFILE), PathBuf::from);
+    if args.next().is_some() {
+        usage();
+    }
+
+    let current = Lockfile::current();
+    match command.as_deref() {
+        Some("check") => {
+            let locked = match std::fs::read_to_string(&path) {
+                Ok(source) => Lockfile::parse(&source).unwrap_or_else(|error| {
+                    eprintln!("failed to read `{}`: {}", path.display(), error);
+                    exit(1);
+                }),
+                Err(error) => {
+                    eprintln!("failed to read `{}`: {}", path.display(), error);
+                    exit(1);
+                }
+            };
+
+            let diff = locked.diff(&current);
+            print!("{}", diff);
+            if diff.is_breaking() {
+                eprintln!(
+                    "the code families are not compatible with `{}`",
+                    path.display()
+                );
+                exit(1);
+            } else if !diff.is_empty() {
+                println!(
+                    "the code families are compatible, run `custom_codes_lock update` to lock the additions"
+                );
+            }
+        }
+        Some("update") => {
+            if let Err(error) = std::fs::write(&path, current.to_string()) {
+                eprintln!("failed to write `{}`: {}", path.display(), error);
+                exit(1);
+            }
+        }
+        _ => usage(),
+    }
+}
+
+fn usage() -> ! {
+    eprintln!("usage: custom_codes_lock <check|update> [LOCKFILE]");
+    exit(2);
+}
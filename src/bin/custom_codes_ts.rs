@@ -0,0 +1,29 @@
+//! Writes the TypeScript definitions of the codes to a directory, the current one by default.
+//!
+//! ```sh
+//! cargo run --bin custom_codes_ts -- frontend/src/generated
+//! ```
+
+use custom_codes::typescript::{constants, definitions, CONSTANTS_FILE, DEFINITIONS_FILE};
+use std::path::PathBuf;
+use std::process::exit;
+
+fn main() {
+    let mut args = std::env::args_os().skip(1);
+    let directory = args.next().map(PathBuf::from).unwrap_or_default();
+    if args.next().is_some() {
+        eprintln!("usage: custom_codes_ts [DIRECTORY]");
+        exit(2);
+    }
+
+    for (file, contents) in [
+        (DEFINITIONS_FILE, definitions()),
+        (CONSTANTS_FILE, constants()),
+    ] {
+        let path = directory.join(file);
+        if let Err(error) = std::fs::write(&path, contents) {
+            eprintln!("failed to write `{}`: {}", path.display(), error);
+            exit(1);
+        }
+    }
+}
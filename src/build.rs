@@ -0,0 +1,608 @@
+//! Generates code families from a TOML file in a build script.
+//!
+//! The file lists families as an array of `families` tables. A family has a `name`, a stable `id` from `100`
+//! to `255`, as the numbers below `100` are reserved for the families of this crate, an optional `message`
+//! documenting it and an array of `variants` tables. A variant has a `name`, a stable `number` from `1`
+//! to `999` and optionally:
+//! - `message`, the message written by `Display`
+//! - `failure`, `true` when the variant is not successful, see `Code::is_success`
+//! - `severity`, a `Severity` such as `"Warning"`
+//! - `retry`, a `RetryHint` such as `"WithBackoff"`
+//! - `http`, the HTTP status of a response reporting the variant, a failure without one is `500`
+//! - `grpc`, a `GrpcCode` such as `"FailedPrecondition"`
+//!
+//! ```toml
+//! [[families]]
+//! name = "Billing"
+//! id = 120
+//! message = "Outcome of charging a customer"
+//!
+//! [[families.variants]]
+//! name = "Charged"
+//! number = 1
+//! message = "The customer has been charged"
+//!
+//! [[families.variants]]
+//! name = "CardDeclined"
+//! number = 2
+//! message = "The card of the customer has been declined"
+//! failure = true
+//! severity = "Warning"
+//! http = 402
+//! ```
+//!
+//! Every family is written as an invocation of `define_codes!`, so the generated enums implement the same
+//! traits as the families of this crate. The names and numbers are checked while generating,
+//! a duplicate fails the build script with the family and variant at fault.
+//! ### Examples
+//! The `main` function of `build.rs`, with `custom_codes` as a build dependency with the `build` feature:
+//! ```no_run
+//! custom_codes::build::generate("codes.toml").unwrap();
+//! ```
+//! `src/lib.rs`:
+//! ```ignore
+//! include!(concat!(env!("OUT_DIR"), "/codes.rs"));
+//! ```
+
+use crate::{GrpcCode, RetryHint, Severity};
+use std::collections::HashSet;
+use std::fmt::{self, Write};
+use std::path::{Path, PathBuf};
+use std::{env, fs, io};
+use toml::{Table, Value};
+
+/// The lowest family number available to applications, the numbers below it are reserved for this crate
+pub const FIRST_FAMILY_ID: u16 = 100;
+
+const SEVERITIES: &[Severity] = &[
+    Severity::Trace,
+    Severity::Debug,
+    Severity::Info,
+    Severity::Notice,
+    Severity::Warning,
+    Severity::Error,
+    Severity::Critical,
+    Severity::Alert,
+];
+
+const RETRY_HINTS: &[RetryHint] = &[
+    RetryHint::Never,
+    RetryHint::Immediately,
+    RetryHint::WithBackoff,
+    RetryHint::AfterReconnect,
+];
+
+const GRPC_CODES: &[GrpcCode] = &[
+    GrpcCode::Ok,
+    GrpcCode::Cancelled,
+    GrpcCode::Unknown,
+    GrpcCode::InvalidArgument,
+    GrpcCode::DeadlineExceeded,
+    GrpcCode::NotFound,
+    GrpcCode::AlreadyExists,
+    GrpcCode::PermissionDenied,
+    GrpcCode::ResourceExhausted,
+    GrpcCode::FailedPrecondition,
+    GrpcCode::Aborted,
+    GrpcCode::OutOfRange,
+    GrpcCode::Unimplemented,
+    GrpcCode::Internal,
+    GrpcCode::Unavailable,
+    GrpcCode::DataLoss,
+    GrpcCode::Unauthenticated,
+];
+
+/// Generates the families of the TOML file at `path` into `$OUT_DIR/<file stem>.rs` with the default derives
+/// and tells Cargo to run the build script again when the file changes. Returns the path of the generated file
+pub fn generate(path: impl AsRef<Path>) -> Result<PathBuf, BuildError> {
+    Generator::new().generate(path)
+}
+
+/// Generates the source of the families of a TOML file with the default derives
+/// ### Examples
+/// ```
+/// let source = custom_codes::build::render(
+///     "[[families]]\n\
+///      name = \"Billing\"\n\
+///      id = 120\n\
+///      variants = [{ name = \"Charged\", number = 1 }]\n",
+/// )
+/// .unwrap();
+/// assert!(source.contains("pub enum Billing = 120 {"));
+/// ```
+pub fn render(source: &str) -> Result<String, BuildError> {
+    Generator::new().render(source)
+}
+
+/// Generates code families, deriving `Debug`, `PartialEq`, `Eq`, `Clone`, `Copy` and `Hash`
+/// for every enum along with the derives added by `with_derive`
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct Generator {
+    derives: Vec<String>,
+}
+
+impl Generator {
+    /// A generator with the default derives
+    pub fn new() -> Self {
+        Generator::default()
+    }
+
+    /// Also derives the trait at `path` for every enum, eg. `serde::Serialize`
+    pub fn with_derive(mut self, path: &str) -> Self {
+        self.derives.push(path.into());
+        self
+    }
+
+    /// Generates the families of the TOML file at `path` into `$OUT_DIR/<file stem>.rs`
+    /// and tells Cargo to run the build script again when the file changes.
+    /// Returns the path of the generated file
+    pub fn generate(&self, path: impl AsRef<Path>) -> Result<PathBuf, BuildError> {
+        let path = path.as_ref();
+        let out_dir = env::var_os("OUT_DIR").ok_or_else(|| {
+            BuildError::Io(io::Error::new(
+                io::ErrorKind::NotFound,
+                "OUT_DIR is not set, code families are generated by a build script",
+            ))
+        })?;
+        let stem = path.file_stem().unwrap_or_else(|| "codes".as_ref());
+        let out = Path::new(&out_dir).join(stem).with_extension("rs");
+
+        println!("cargo:rerun-if-changed={}", path.display());
+        self.generate_to(path, &out)?;
+
+        Ok(out)
+    }
+
+    /// Generates the families of the TOML file at `path` into the file at `out`,
+    /// which is left untouched when it is up to date
+    pub fn generate_to(
+        &self,
+        path: impl AsRef<Path>,
+        out: impl AsRef<Path>,
+    ) -> Result<(), BuildError> {
+        let source = self.render(&fs::read_to_string(path)?)?;
+        if fs::read_to_string(&out).ok().as_deref() != Some(source.as_str()) {
+            fs::write(out, source)?;
+        }
+
+        Ok(())
+    }
+
+    /// Generates the source of the families of a TOML file
+    pub fn render(&self, source: &str) -> Result<String, BuildError> {
+        let families = parse(&source.parse::<Table>()?)?;
+
+        let mut out = String::from("// Generated by `custom_codes::build`, do not edit\n");
+        for family in &families {
+            out.push('\n');
+            self.write_family(&mut out, family)
+                .expect("writing to a String cannot fail");
+        }
+
+        Ok(out)
+    }
+
+    fn write_family(&self, out: &mut String, family: &FamilySpec) -> fmt::Result {
+        writeln!(out, "::custom_codes::define_codes! {{")?;
+        write_docs(out, "    ", family.message.as_deref())?;
+        write!(out, "    #[derive(Debug, PartialEq, Eq, Clone, Copy, Hash")?;
+        for derive in &self.derives {
+            write!(out, ", {}", derive)?;
+        }
+        writeln!(out, ")]")?;
+        writeln!(out, "    pub enum {} = {} {{", family.name, family.id)?;
+        for variant in &family.variants {
+            write_docs(out, "        ", variant.message.as_deref())?;
+            writeln!(out, "        {} = {},", variant.name, variant.number)?;
+        }
+        writeln!(out, "    }}")?;
+
+        let variants = &family.variants;
+        write_section(out, "failures", variants, |variant| {
+            variant.failure.then_some(None)
+        })?;
+        write_section(out, "severities", variants, |variant| {
+            variant
+                .severity
+                .map(|severity| Some(severity.as_str().into()))
+        })?;
+        write_section(out, "retries", variants, |variant| {
+            variant.retry.map(|hint| Some(format!("{:?}", hint)))
+        })?;
+        write_section(out, "http", variants, |variant| {
+            variant.http.map(|status| Some(status.to_string()))
+        })?;
+        write_section(out, "grpc", variants, |variant| {
+            variant.grpc.map(|code| Some(format!("{:?}", code)))
+        })?;
+
+        writeln!(out, "}}")
+    }
+}
+
+/// Writes a message as doc attributes, one for each of its lines
+fn write_docs(out: &mut String, indent: &str, message: Option<&str>) -> fmt::Result {
+    for line in message.into_iter().flat_map(str::lines) {
+        writeln!(out, "{}#[doc = {:?}]", indent, format!(" {}", line))?;
+    }
+
+    Ok(())
+}
+
+/// Writes a section of `define_codes!` listing the variants for which `entry` returns `Some`,
+/// followed by `=>` and the value it holds if any
+fn write_section(
+    out: &mut String,
+    section: &str,
+    variants: &[VariantSpec],
+    entry: impl Fn(&VariantSpec) -> Option<Option<String>>,
+) -> fmt::Result {
+    let entries: Vec<_> = variants
+        .iter()
+        .filter_map(|variant| match entry(variant)? {
+            Some(value) => Some(format!("{} => {}", variant.name, value)),
+            None => Some(variant.name.clone()),
+        })
+        .collect();
+
+    if entries.is_empty() {
+        Ok(())
+    } else {
+        writeln!(out, "    {} [{}]", section, entries.join(", "))
+    }
+}
+
+struct FamilySpec {
+    name: String,
+    id: u16,
+    message: Option<String>,
+    variants: Vec<VariantSpec>,
+}
+
+struct VariantSpec {
+    name: String,
+    number: u16,
+    message: Option<String>,
+    failure: bool,
+    severity: Option<Severity>,
+    retry: Option<RetryHint>,
+    http: Option<u16>,
+    grpc: Option<GrpcCode>,
+}
+
+/// Reads and validates the families of a TOML document
+fn parse(document: &Table) -> Result<Vec<FamilySpec>, ValidationError> {
+    let root = Entry {
+        table: document,
+        family: None,
+        variant: None,
+    };
+    root.check_fields(&["families"])?;
+
+    let mut families = Vec::new();
+    let mut names = HashSet::new();
+    let mut ids = HashSet::new();
+    for table in root.tables("families")? {
+        let mut entry = Entry {
+            table,
+            family: None,
+            variant: None,
+        };
+        let name = entry.name()?;
+        entry.family = Some(name.clone());
+        entry.check_fields(&["name", "id", "message", "variants"])?;
+
+        let id = entry.integer("id", FIRST_FAMILY_ID.into(), 255)?;
+        if !names.insert(name.clone()) {
+            return Err(entry.error(ValidationErrorReason::DuplicateFamily(name)));
+        }
+        if !ids.insert(id) {
+            return Err(entry.error(ValidationErrorReason::DuplicateFamilyId(id)));
+        }
+
+        let mut variants = Vec::new();
+        let mut variant_names = HashSet::new();
+        let mut numbers = HashSet::new();
+        for table in entry.tables("variants")? {
+            let mut variant = Entry {
+                table,
+                family: entry.family.clone(),
+                variant: None,
+            };
+            let name = variant.name()?;
+            variant.variant = Some(name.clone());
+            variant.check_fields(&[
+                "name", "number", "message", "failure", "severity", "retry", "http", "grpc",
+            ])?;
+
+            let number = variant.integer("number", 1, 999)?;
+            if !variant_names.insert(name.clone()) {
+                return Err(variant.error(ValidationErrorReason::DuplicateVariant(name)));
+            }
+            if !numbers.insert(number) {
+                return Err(variant.error(ValidationErrorReason::DuplicateNumber(number)));
+            }
+
+            variants.push(VariantSpec {
+                name,
+                number,
+                message: variant.optional_string("message")?,
+                failure: variant.optional_bool("failure")?.unwrap_or(false),
+                severity: variant.named("severity", SEVERITIES)?,
+                retry: variant.named("retry", RETRY_HINTS)?,
+                http: if variant.table.contains_key("http") {
+                    Some(variant.integer("http", 100, 599)?)
+                } else {
+                    None
+                },
+                grpc: variant.named("grpc", GRPC_CODES)?,
+            });
+        }
+        if variants.is_empty() {
+            return Err(entry.error(ValidationErrorReason::NoVariants));
+        }
+
+        families.push(FamilySpec {
+            name,
+            id,
+            message: entry.optional_string("message")?,
+            variants,
+        });
+    }
+
+    Ok(families)
+}
+
+/// A table of the document along with the family and variant it describes, for errors
+struct Entry<'t> {
+    table: &'t Table,
+    family: Option<String>,
+    variant: Option<String>,
+}
+
+impl<'t> Entry<'t> {
+    fn error(&self, reason: ValidationErrorReason) -> ValidationError {
+        ValidationError {
+            family: self.family.clone(),
+            variant: self.variant.clone(),
+            reason,
+        }
+    }
+
+    fn check_fields(&self, fields: &[&str]) -> Result<(), ValidationError> {
+        match self
+            .table
+            .keys()
+            .find(|key| !fields.contains(&key.as_str()))
+        {
+            Some(key) => Err(self.error(ValidationErrorReason::UnknownField(key.clone()))),
+            None => Ok(()),
+        }
+    }
+
+    fn field(&self, field: &'static str) -> Result<&'t Value, ValidationError> {
+        self.table
+            .get(field)
+            .ok_or_else(|| self.error(ValidationErrorReason::MissingField(field)))
+    }
+
+    fn tables(&self, field: &'static str) -> Result<Vec<&'t Table>, ValidationError> {
+        let wrong_type = || self.error(ValidationErrorReason::WrongType(field));
+
+        match self.table.get(field) {
+            None => Ok(Vec::new()),
+            Some(Value::Array(values)) => values
+                .iter()
+                .map(|value| value.as_table().ok_or_else(wrong_type))
+                .collect(),
+            Some(_) => Err(wrong_type()),
+        }
+    }
+
+    /// The `name` field, a Rust identifier in upper camel case
+    fn name(&self) -> Result<String, ValidationError> {
+        let name = self
+            .field("name")?
+            .as_str()
+            .ok_or_else(|| self.error(ValidationErrorReason::WrongType("name")))?;
+        let mut chars = name.chars();
+        let valid = chars.next().is_some_and(|first| first.is_ascii_uppercase())
+            && chars.all(|char| char.is_ascii_alphanumeric())
+            && name != "Self";
+
+        if valid {
+            Ok(name.into())
+        } else {
+            Err(self.error(ValidationErrorReason::InvalidName(name.into())))
+        }
+    }
+
+    fn integer(&self, field: &'static str, min: i64, max: i64) -> Result<u16, ValidationError> {
+        let value = self
+            .field(field)?
+            .as_integer()
+            .ok_or_else(|| self.error(ValidationErrorReason::WrongType(field)))?;
+
+        if value < min || value > max {
+            return Err(self.error(ValidationErrorReason::OutOfRange { field, value }));
+        }
+
+        Ok(value as u16)
+    }
+
+    fn optional_string(&self, field: &'static str) -> Result<Option<String>, ValidationError> {
+        self.table
+            .get(field)
+            .map(|value| {
+                value
+                    .as_str()
+                    .map(Into::into)
+                    .ok_or_else(|| self.error(ValidationErrorReason::WrongType(field)))
+            })
+            .transpose()
+    }
+
+    fn optional_bool(&self, field: &'static str) -> Result<Option<bool>, ValidationError> {
+        self.table
+            .get(field)
+            .map(|value| {
+                value
+                    .as_bool()
+                    .ok_or_else(|| self.error(ValidationErrorReason::WrongType(field)))
+            })
+            .transpose()
+    }
+
+    /// The value among `values` whose variant is named by `field`
+    fn named<T: fmt::Debug + Copy>(
+        &self,
+        field: &'static str,
+        values: &[T],
+    ) -> Result<Option<T>, ValidationError> {
+        self.optional_string(field)?
+            .map(|name| {
+                values
+                    .iter()
+                    .copied()
+                    .find(|value| format!("{:?}", value) == name)
+                    .ok_or_else(|| self.error(ValidationErrorReason::UnknownValue { field, name }))
+            })
+            .transpose()
+    }
+}
+
+/// The code families could not be generated
+#[derive(Debug)]
+pub enum BuildError {
+    /// The TOML file could not be read or the generated file could not be written
+    Io(io::Error),
+    /// The file is not a valid TOML document
+    Toml(toml::de::Error),
+    /// The document does not describe valid code families
+    Invalid(ValidationError),
+}
+
+impl From<io::Error> for BuildError {
+    fn from(error: io::Error) -> Self {
+        BuildError::Io(error)
+    }
+}
+
+impl From<toml::de::Error> for BuildError {
+    fn from(error: toml::de::Error) -> Self {
+        BuildError::Toml(error)
+    }
+}
+
+impl From<ValidationError> for BuildError {
+    fn from(error: ValidationError) -> Self {
+        BuildError::Invalid(error)
+    }
+}
+
+impl fmt::Display for BuildError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BuildError::Io(error) => write!(f, "Unable to generate the code families: {}", error),
+            BuildError::Toml(error) => write!(f, "Invalid TOML: {}", error),
+            BuildError::Invalid(error) => write!(f, "Invalid code families: {}", error),
+        }
+    }
+}
+
+impl std::error::Error for BuildError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            BuildError::Io(error) => Some(error),
+            BuildError::Toml(error) => Some(error),
+            BuildError::Invalid(error) => Some(error),
+        }
+    }
+}
+
+/// A family or variant is invalid
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct ValidationError {
+    /// The name of the family at fault, if it could be read
+    pub family: Option<String>,
+    /// The name of the variant at fault, if the error is about a variant and its name could be read
+    pub variant: Option<String>,
+    /// Why the family or variant is invalid
+    pub reason: ValidationErrorReason,
+}
+
+/// Why a family or variant is invalid
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum ValidationErrorReason {
+    /// A required field is missing
+    MissingField(&'static str),
+    /// A field does not have the expected TOML type
+    WrongType(&'static str),
+    /// A field is not one of the fields of a family or a variant
+    UnknownField(String),
+    /// A name is not a Rust identifier in upper camel case
+    InvalidName(String),
+    /// A family number, variant number or HTTP status is out of its range
+    OutOfRange {
+        /// The field holding the number
+        field: &'static str,
+        /// The number found
+        value: i64,
+    },
+    /// A severity, retry hint or gRPC code is not one of the variants of its type
+    UnknownValue {
+        /// The field holding the name
+        field: &'static str,
+        /// The name found
+        name: String,
+    },
+    /// Two families have the same name
+    DuplicateFamily(String),
+    /// Two families have the same number
+    DuplicateFamilyId(u16),
+    /// Two variants of a family have the same name
+    DuplicateVariant(String),
+    /// Two variants of a family have the same number
+    DuplicateNumber(u16),
+    /// A family has no variants
+    NoVariants,
+}
+
+impl fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (&self.family, &self.variant) {
+            (Some(family), Some(variant)) => write!(f, "{}::{}: ", family, variant)?,
+            (Some(family), None) => write!(f, "{}: ", family)?,
+            (None, _) => {}
+        }
+        match &self.reason {
+            ValidationErrorReason::MissingField(field) => write!(f, "missing field `{}`", field),
+            ValidationErrorReason::WrongType(field) => write!(f, "`{}` has the wrong type", field),
+            ValidationErrorReason::UnknownField(field) => write!(f, "unknown field `{}`", field),
+            ValidationErrorReason::InvalidName(name) => {
+                write!(f, "`{}` is not a name in upper camel case", name)
+            }
+            ValidationErrorReason::OutOfRange { field, value } => {
+                write!(f, "`{}` is out of range: {}", field, value)
+            }
+            ValidationErrorReason::UnknownValue { field, name } => {
+                write!(f, "`{}` is not a valid `{}`", name, field)
+            }
+            ValidationErrorReason::DuplicateFamily(name) => {
+                write!(f, "the family `{}` is declared twice", name)
+            }
+            ValidationErrorReason::DuplicateFamilyId(id) => {
+                write!(f, "the family number {} is already used", id)
+            }
+            ValidationErrorReason::DuplicateVariant(name) => {
+                write!(f, "the variant `{}` is declared twice", name)
+            }
+            ValidationErrorReason::DuplicateNumber(number) => {
+                write!(f, "the variant number {} is already used", number)
+            }
+            ValidationErrorReason::NoVariants => write!(f, "the family has no variants"),
+        }
+    }
+}
+
+impl std::error::Error for ValidationError {}
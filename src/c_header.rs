@@ -0,0 +1,90 @@
+//! The C header of the `custom_codes_ffi` crate which exposes the codes to C and C++ hosts.
+//!
+//! Every family is declared as a C enum whose constants are the numeric codes returned by `Code::code()`,
+//! eg. `CC_DB_OPS_DOCUMENT_NOT_FOUND = 4025`, followed by the functions interpreting a numeric code.
+//! `header()` generates `ffi/include/custom_codes.h` shipped with the repository.
+//! ### Examples
+//! ```
+//! let header = custom_codes::c_header::header();
+//! assert!(header.contains("    CC_DB_OPS_DOCUMENT_NOT_FOUND = 4025,\n"));
+//! assert!(header.contains("const char *cc_code_name(uint32_t code);\n"));
+//! ```
+
+use crate::code::screaming_snake_case;
+use crate::FAMILIES;
+use alloc::string::String;
+use core::fmt::{self, Write};
+
+/// Generates the C header declaring the numeric codes and the functions of `custom_codes_ffi`
+pub fn header() -> String {
+    let mut header = String::new();
+    write_header(&mut header).expect("writing to a `String` does not fail");
+
+    header
+}
+
+fn write_header(out: &mut String) -> fmt::Result {
+    writeln!(
+        out,
+        "/* Generated by `custom_codes::c_header::header()`, do not edit. */"
+    )?;
+    writeln!(out, "#ifndef CUSTOM_CODES_H")?;
+    writeln!(out, "#define CUSTOM_CODES_H")?;
+    writeln!(out)?;
+    writeln!(out, "#include <stdbool.h>")?;
+    writeln!(out, "#include <stdint.h>")?;
+    writeln!(out)?;
+    writeln!(out, "#ifdef __cplusplus")?;
+    writeln!(out, "extern \"C\" {{")?;
+    writeln!(out, "#endif")?;
+
+    for family in FAMILIES {
+        let prefix = screaming_snake_case(family.name);
+
+        writeln!(out)?;
+        writeln!(out, "#define CC_FAMILY_{} {}u", prefix, family.id)?;
+        writeln!(out)?;
+        writeln!(out, "typedef enum Cc{} {{", family.name)?;
+        for variant in family.variants {
+            writeln!(
+                out,
+                "    CC_{}_{} = {},",
+                prefix,
+                screaming_snake_case(variant.name),
+                family.code(variant)
+            )?;
+        }
+        writeln!(out, "}} Cc{};", family.name)?;
+    }
+
+    writeln!(out)?;
+    writeln!(
+        out,
+        "/* The name of the variant of a code, eg. \"DocumentNotFound\", NULL if the code is unknown */"
+    )?;
+    writeln!(out, "const char *cc_code_name(uint32_t code);")?;
+    writeln!(out)?;
+    writeln!(
+        out,
+        "/* A message describing a code in English, NULL if the code is unknown */"
+    )?;
+    writeln!(out, "const char *cc_code_message(uint32_t code);")?;
+    writeln!(out)?;
+    writeln!(
+        out,
+        "/* The name of the family of a code, eg. \"DbOps\", NULL if the code is unknown */"
+    )?;
+    writeln!(out, "const char *cc_code_family(uint32_t code);")?;
+    writeln!(out)?;
+    writeln!(
+        out,
+        "/* Whether a code reports a successful operation or a healthy state, false if the code is unknown */"
+    )?;
+    writeln!(out, "bool cc_code_is_success(uint32_t code);")?;
+    writeln!(out)?;
+    writeln!(out, "#ifdef __cplusplus")?;
+    writeln!(out, "}}")?;
+    writeln!(out, "#endif")?;
+    writeln!(out)?;
+    writeln!(out, "#endif /* CUSTOM_CODES_H */")
+}
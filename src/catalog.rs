@@ -0,0 +1,517 @@
+//! Localized messages of codes loaded from Fluent or TOML catalogs.
+//!
+//! Every variant has a message key made of its family and name in kebab case, eg. `db-ops-document-not-found`
+//! for `DbOps::DocumentNotFound`. A `Catalog` holds the messages of any number of locales and falls back to
+//! bundled English defaults, the doc comments of the variants, which `english_template()` writes as a Fluent
+//! file for translators.
+//!
+//! Messages are read from the subset of Fluent made of `key = pattern` messages, spanning several lines when the
+//! following lines are indented, and `#` comments. Attributes and terms are ignored. A pattern interpolates
+//! arguments with `{ $name }` and literals with `{ "text" }`, the payload of a variant is the argument `$payload`.
+//! With the `toml` feature a catalog is also read from a TOML table of keys and patterns.
+//!
+//! A message is looked up in the locale asked for, then in the fallbacks configured for it, then in the locales
+//! obtained by removing its subtags one at a time, eg. `sw-KE` then `sw`, then in `en` and the English defaults.
+//! ### Examples
+//! ```
+//! use custom_codes::catalog::Catalog;
+//! use custom_codes::{DbOps, SecOps};
+//!
+//! let mut catalog = Catalog::new();
+//! catalog
+//!     .add_ftl(
+//!         "sw",
+//!         "# Swahili\n\
+//!          db-ops-document-not-found = Hati haipo\n\
+//!          db-ops-encountered-errors = Hitilafu: { $payload }\n",
+//!     )
+//!     .unwrap();
+//!
+//! assert_eq!(catalog.message("sw-KE", &DbOps::DocumentNotFound), "Hati haipo");
+//! assert_eq!(
+//!     catalog.message("sw", &DbOps::EncounteredErrors("disk full".into())),
+//!     "Hitilafu: disk full"
+//! );
+//! // Falls back to the bundled English message
+//! assert_eq!(catalog.message("sw", &SecOps::KeyInvalid), "Key has been tampered with");
+//! ```
+
+use crate::code::{kebab_case, variant_of};
+use crate::codec::{Binary, RawCode};
+use crate::{Code, KeyLength, FAMILIES};
+use alloc::borrow::ToOwned;
+use alloc::collections::BTreeMap;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt::{self, Write};
+
+/// The locale of the bundled messages, always the last of a fallback chain
+pub const DEFAULT_LOCALE: &str = "en";
+
+/// The message key of the variant `name` of the family `family`
+fn key(family: &'static str, name: &'static str) -> String {
+    let mut key = kebab_case(family);
+    key.push('-');
+    key.push_str(&kebab_case(name));
+
+    key
+}
+
+/// The message key of `code`, eg. `db-ops-document-not-found`
+/// ### Examples
+/// ```
+/// use custom_codes::catalog::message_key;
+/// use custom_codes::{AccessStatus, DbOps};
+///
+/// assert_eq!(message_key(&DbOps::DocumentNotFound), "db-ops-document-not-found");
+/// assert_eq!(message_key(&AccessStatus::AcceptedRAC), "access-status-accepted-rac");
+/// ```
+pub fn message_key<T: Code>(code: &T) -> String {
+    key(T::FAMILY, code.name())
+}
+
+/// Writes the bundled English message of every variant of every family as a Fluent file,
+/// a template for translators
+pub fn english_template() -> String {
+    let mut template = String::new();
+    for family in FAMILIES {
+        let _ = writeln!(template, "\n## {}", family.name);
+        for variant in family.variants {
+            let _ = writeln!(
+                template,
+                "{} = {}",
+                key(family.name, variant.name),
+                english(variant)
+            );
+        }
+    }
+
+    template.split_off(1)
+}
+
+/// The bundled English pattern of a variant, its description followed by its payload if it carries one
+fn english(variant: &crate::CodeVariant) -> String {
+    let mut pattern = variant.description();
+    if variant.payload.is_some() {
+        pattern.push_str(": { $payload }");
+    }
+
+    pattern
+}
+
+/// Messages of several locales with fallbacks, see the module documentation
+#[derive(Debug, Clone, Default)]
+pub struct Catalog {
+    /// The patterns of every locale by message key
+    locales: BTreeMap<String, BTreeMap<String, String>>,
+    fallbacks: BTreeMap<String, Vec<String>>,
+}
+
+impl Catalog {
+    /// A catalog holding only the bundled English messages
+    pub fn new() -> Self {
+        Catalog::default()
+    }
+
+    /// Adds or replaces the pattern of the message `key` in `locale`
+    pub fn insert(&mut self, locale: &str, key: impl Into<String>, pattern: impl Into<String>) {
+        self.locales
+            .entry(locale.to_owned())
+            .or_default()
+            .insert(key.into(), pattern.into());
+    }
+
+    /// Adds the messages of a Fluent file to `locale`, replacing those with the same key
+    pub fn add_ftl(&mut self, locale: &str, source: &str) -> Result<(), ParseError> {
+        for (key, pattern) in parse_ftl(source)? {
+            self.insert(locale, key, pattern);
+        }
+
+        Ok(())
+    }
+
+    /// Adds the messages of a TOML table of keys and patterns to `locale`, replacing those with the same key
+    #[cfg(feature = "toml")]
+    pub fn add_toml(&mut self, locale: &str, source: &str) -> Result<(), ParseError> {
+        let messages: BTreeMap<String, String> =
+            toml::from_str(source).map_err(|error| ParseError {
+                line: error
+                    .span()
+                    .map_or(0, |span| source[..span.start].lines().count().max(1)),
+                reason: ParseErrorReason::Toml(error.message().into()),
+            })?;
+
+        for (key, pattern) in messages {
+            check_pattern(&pattern).map_err(|reason| ParseError {
+                line: source
+                    .lines()
+                    .position(|line| line.trim_start().starts_with(key.as_str()))
+                    .map_or(0, |index| index + 1),
+                reason,
+            })?;
+            self.insert(locale, key, pattern);
+        }
+
+        Ok(())
+    }
+
+    /// Adds the messages of the file at `path` to `locale`, read as TOML when its extension is `toml`
+    /// and as Fluent otherwise
+    #[cfg(feature = "std")]
+    pub fn load(
+        &mut self,
+        locale: &str,
+        path: impl AsRef<std::path::Path>,
+    ) -> Result<(), LoadError> {
+        let path = path.as_ref();
+        let source = std::fs::read_to_string(path).map_err(LoadError::Io)?;
+
+        match path.extension().and_then(|extension| extension.to_str()) {
+            #[cfg(feature = "toml")]
+            Some("toml") => self.add_toml(locale, &source),
+            _ => self.add_ftl(locale, &source),
+        }
+        .map_err(LoadError::Parse)
+    }
+
+    /// Looks up messages missing from `locale` in `fallbacks`, in order, before the default chain
+    pub fn with_fallbacks(mut self, locale: &str, fallbacks: &[&str]) -> Self {
+        self.fallbacks.insert(
+            locale.to_owned(),
+            fallbacks
+                .iter()
+                .map(|fallback| (*fallback).to_owned())
+                .collect(),
+        );
+        self
+    }
+
+    /// The locales a message is looked up in for `locale`, in order, see the module documentation
+    /// ### Examples
+    /// ```
+    /// use custom_codes::catalog::Catalog;
+    ///
+    /// let catalog = Catalog::new().with_fallbacks("fr-CA", &["fr-FR"]);
+    /// assert_eq!(catalog.chain("fr-CA"), ["fr-CA", "fr-FR", "fr", "en"]);
+    /// ```
+    pub fn chain(&self, locale: &str) -> Vec<String> {
+        let mut chain: Vec<String> = Vec::new();
+        let mut push = |locale: &str| {
+            if !chain.iter().any(|known| known == locale) {
+                chain.push(locale.to_owned());
+            }
+        };
+
+        push(locale);
+        for fallback in self.fallbacks.get(locale).into_iter().flatten() {
+            push(fallback);
+        }
+        let mut truncated = locale;
+        while let Some((language, _)) = truncated.rsplit_once('-') {
+            push(language);
+            truncated = language;
+        }
+        push(DEFAULT_LOCALE);
+
+        chain
+    }
+
+    /// The pattern of the message `key` for `locale` following its fallback chain,
+    /// without the bundled English messages
+    pub fn pattern(&self, locale: &str, key: &str) -> Option<&str> {
+        self.chain(locale).iter().find_map(|locale| {
+            self.locales
+                .get(locale)
+                .and_then(|messages| messages.get(key))
+                .map(String::as_str)
+        })
+    }
+
+    /// The message of `code` in `locale`
+    pub fn message<T: Binary>(&self, locale: &str, code: &T) -> String {
+        self.message_with(locale, code, &[])
+    }
+
+    /// The message of `code` in `locale` interpolating `arguments` besides `$payload`
+    /// ### Examples
+    /// ```
+    /// use custom_codes::catalog::Catalog;
+    /// use custom_codes::DbOps;
+    ///
+    /// let mut catalog = Catalog::new();
+    /// catalog.insert("fr", "db-ops-document-not-found", "Le document { $id } n'existe pas");
+    ///
+    /// assert_eq!(
+    ///     catalog.message_with("fr", &DbOps::DocumentNotFound, &[("id", "users/1")]),
+    ///     "Le document users/1 n'existe pas"
+    /// );
+    /// ```
+    pub fn message_with<T: Binary>(
+        &self,
+        locale: &str,
+        code: &T,
+        arguments: &[(&str, &str)],
+    ) -> String {
+        let key = message_key(code);
+        let bundled;
+        let pattern = match self.pattern(locale, &key) {
+            Some(pattern) => pattern,
+            None => match variant_of(code) {
+                Some(variant) => {
+                    bundled = english(variant);
+                    &bundled
+                }
+                None => return key,
+            },
+        };
+
+        let mut message = String::new();
+        let _ = interpolate(pattern, &mut message, |name| {
+            if name == "payload" {
+                self.payload(locale, code)
+            } else {
+                arguments
+                    .iter()
+                    .find(|(argument, _)| *argument == name)
+                    .map(|(_, value)| (*value).to_owned())
+            }
+        });
+
+        message
+    }
+
+    /// The payload of `code` as text, a nested code is written as its message
+    fn payload<T: Binary>(&self, locale: &str, code: &T) -> Option<String> {
+        let payload_type = variant_of(code)?.payload?;
+        let mut buffer = Vec::new();
+        code.encode_into(&mut buffer);
+        let (raw, _) = RawCode::decode(&buffer).ok()?;
+
+        let text = match payload_type {
+            "String" => raw.str().ok()?.to_owned(),
+            "Vec<String>" => raw
+                .str_list()
+                .ok()?
+                .collect::<Result<Vec<_>, _>>()
+                .ok()?
+                .join(", "),
+            "Vec<u8>" => hex(raw.bytes().ok()?),
+            "Vec<Vec<u8>>" => raw
+                .list()
+                .ok()?
+                .map(|item| item.map(hex))
+                .collect::<Result<Vec<_>, _>>()
+                .ok()?
+                .join(", "),
+            "KeyLength" => self.message(
+                locale,
+                &<KeyLength as crate::codec::WirePayload>::read(&raw).ok()?,
+            ),
+            _ => return None,
+        };
+
+        Some(text)
+    }
+}
+
+fn hex(bytes: &[u8]) -> String {
+    let mut text = String::with_capacity(bytes.len() * 2);
+    for byte in bytes {
+        let _ = write!(text, "{:02x}", byte);
+    }
+
+    text
+}
+
+/// Writes `pattern` replacing its placeables, an unknown argument is written as `{$name}` like Fluent does
+fn interpolate(
+    pattern: &str,
+    out: &mut String,
+    argument: impl Fn(&str) -> Option<String>,
+) -> Result<(), ParseErrorReason> {
+    let mut rest = pattern;
+    while let Some(start) = rest.find('{') {
+        out.push_str(&rest[..start]);
+        let end = rest[start..]
+            .find('}')
+            .ok_or(ParseErrorReason::UnterminatedPlaceable)?
+            + start;
+        let placeable = rest[start + 1..end].trim();
+
+        if let Some(name) = placeable.strip_prefix('$') {
+            if !is_identifier(name) {
+                return Err(ParseErrorReason::InvalidPlaceable(placeable.into()));
+            }
+            match argument(name) {
+                Some(value) => out.push_str(&value),
+                None => {
+                    let _ = write!(out, "{{${}}}", name);
+                }
+            }
+        } else if let Some(literal) = placeable
+            .strip_prefix('"')
+            .and_then(|literal| literal.strip_suffix('"'))
+        {
+            out.push_str(literal);
+        } else {
+            return Err(ParseErrorReason::InvalidPlaceable(placeable.into()));
+        }
+        rest = &rest[end + 1..];
+    }
+    if rest.contains('}') {
+        return Err(ParseErrorReason::UnterminatedPlaceable);
+    }
+    out.push_str(rest);
+
+    Ok(())
+}
+
+fn check_pattern(pattern: &str) -> Result<(), ParseErrorReason> {
+    interpolate(pattern, &mut String::new(), |_| None)
+}
+
+fn is_identifier(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .is_some_and(|first| first.is_ascii_alphabetic())
+        && chars.all(|current| current.is_ascii_alphanumeric() || current == '-' || current == '_')
+}
+
+/// Reads the messages of a Fluent file as pairs of keys and patterns
+fn parse_ftl(source: &str) -> Result<Vec<(String, String)>, ParseError> {
+    let mut messages: Vec<(String, String, usize)> = Vec::new();
+    // Whether indented lines continue the last message rather than an ignored term or attribute
+    let mut continues = false;
+
+    for (index, line) in source.lines().enumerate() {
+        let number = index + 1;
+        let error = |reason| ParseError {
+            line: number,
+            reason,
+        };
+
+        if line.trim().is_empty() || line.starts_with('#') {
+            continue;
+        }
+        if line.starts_with(' ') {
+            let text = line.trim();
+            if text.starts_with('.') {
+                // An attribute, its own continuation lines are indented too
+                continues = false;
+            } else if continues {
+                let (_, pattern, _) = messages.last_mut().expect("a message continues");
+                if !pattern.is_empty() {
+                    pattern.push('\n');
+                }
+                pattern.push_str(text);
+            }
+            continue;
+        }
+
+        let (key, pattern) = line
+            .split_once('=')
+            .ok_or_else(|| error(ParseErrorReason::ExpectedMessage))?;
+        let key = key.trim_end();
+        if key.starts_with('-') {
+            // A term
+            continues = false;
+            continue;
+        }
+        if !is_identifier(key) {
+            return Err(error(ParseErrorReason::InvalidKey(key.into())));
+        }
+        messages.push((key.into(), pattern.trim().into(), number));
+        continues = true;
+    }
+
+    messages
+        .into_iter()
+        .map(|(key, pattern, line)| {
+            if pattern.is_empty() {
+                return Err(ParseError {
+                    line,
+                    reason: ParseErrorReason::MissingPattern,
+                });
+            }
+            check_pattern(&pattern).map_err(|reason| ParseError { line, reason })?;
+
+            Ok((key, pattern))
+        })
+        .collect()
+}
+
+/// A catalog could not be read
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct ParseError {
+    /// The line of the message which could not be read, starting at `1`, or `0` when it is unknown
+    pub line: usize,
+    /// Why the message could not be read
+    pub reason: ParseErrorReason,
+}
+
+/// Why a message of a catalog could not be read
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum ParseErrorReason {
+    /// The line is neither a comment, a message nor the continuation of a message
+    ExpectedMessage,
+    /// The key of a message is not a Fluent identifier
+    InvalidKey(String),
+    /// A message has no pattern
+    MissingPattern,
+    /// A `{` is not closed by a `}` or a `}` was not opened
+    UnterminatedPlaceable,
+    /// A placeable is neither `{ $argument }` nor `{ "literal" }`
+    InvalidPlaceable(String),
+    /// The TOML document is invalid or is not a table of strings
+    Toml(String),
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Line {}: ", self.line)?;
+        match &self.reason {
+            ParseErrorReason::ExpectedMessage => write!(f, "expected a message"),
+            ParseErrorReason::InvalidKey(key) => write!(f, "`{}` is not a valid key", key),
+            ParseErrorReason::MissingPattern => write!(f, "the message has no pattern"),
+            ParseErrorReason::UnterminatedPlaceable => write!(f, "unterminated placeable"),
+            ParseErrorReason::InvalidPlaceable(placeable) => {
+                write!(f, "`{{{}}}` is not a supported placeable", placeable)
+            }
+            ParseErrorReason::Toml(message) => f.write_str(message),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ParseError {}
+
+/// A catalog file could not be loaded
+#[cfg(feature = "std")]
+#[derive(Debug)]
+pub enum LoadError {
+    /// The file could not be read
+    Io(std::io::Error),
+    /// The file is not a valid catalog
+    Parse(ParseError),
+}
+
+#[cfg(feature = "std")]
+impl fmt::Display for LoadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LoadError::Io(error) => write!(f, "Unable to read the catalog: {}", error),
+            LoadError::Parse(error) => write!(f, "Invalid catalog: {}", error),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for LoadError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            LoadError::Io(error) => Some(error),
+            LoadError::Parse(error) => Some(error),
+        }
+    }
+}
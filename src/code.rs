@@ -0,0 +1,948 @@
+use crate::{GrpcCode, RetryHint, Severity};
+use core::fmt;
+
+/// A family of codes with stable names and numbers, implemented by every code enum
+/// ### Examples
+/// ```
+/// # use custom_codes::{Code, DbOps};
+/// assert_eq!(DbOps::FAMILY, "DbOps");
+/// assert_eq!(DbOps::DocumentNotFound.name(), "DocumentNotFound");
+/// assert_eq!(DbOps::from_name("DocumentNotFound"), Some(DbOps::DocumentNotFound));
+/// assert_eq!(DbOps::from_number(DbOps::DocumentNotFound.number()), Some(DbOps::DocumentNotFound));
+/// assert_eq!(DbOps::DocumentNotFound.code(), 4025);
+/// ```
+pub trait Code: Sized {
+    /// The name of the family, eg. `DbOps`
+    const FAMILY: &'static str;
+    /// The stable number of the family, it is never reused by another family
+    const FAMILY_ID: u16;
+    /// The lines of the doc comment of the family
+    const DOCS: &'static [&'static str];
+    /// Every variant of the family in the order it is declared
+    const VARIANTS: &'static [CodeVariant];
+    /// The deprecated names still accepted for some variants
+    const ALIASES: &'static [CodeAlias];
+
+    /// The name of the variant as declared, eg. `DocumentNotFound`
+    fn name(&self) -> &'static str;
+
+    /// The stable number of the variant within its family, numbers start at `1`
+    fn number(&self) -> u16;
+
+    /// Get a variant from its name. Returns `None` if the name is unknown
+    /// or the variant carries a payload which cannot be recovered from its name
+    fn from_name(name: &str) -> Option<Self>;
+
+    /// Get a variant from its number. Returns `None` if the number is unknown
+    /// or the variant carries a payload which cannot be recovered from its number
+    fn from_number(number: u16) -> Option<Self>;
+
+    /// The numeric code of the variant which is unique across all families,
+    /// the family number multiplied by `1000` added to the variant number
+    fn code(&self) -> u32 {
+        numeric_code(Self::FAMILY_ID, self.number())
+    }
+
+    /// Whether the code reports a successful operation or a healthy state. Codes reporting an error,
+    /// a denial or a state which is not initialized are listed as failures of their family
+    /// ### Examples
+    /// ```
+    /// # use custom_codes::{Code, DbOps};
+    /// assert!(DbOps::DocumentFound.is_success());
+    /// assert!(!DbOps::DocumentNotFound.is_success());
+    /// ```
+    fn is_success(&self) -> bool {
+        variant_of(self).is_some_and(|variant| variant.success)
+    }
+
+    /// The severity the code is logged at as declared by its family,
+    /// `severity::Overrides` replaces it for an application
+    /// ### Examples
+    /// ```
+    /// # use custom_codes::{Code, DbOps, FileOps, SecOps, Severity};
+    /// assert_eq!(SecOps::PossibleIdTheft.severity(), Severity::Alert);
+    /// assert_eq!(DbOps::DbIntegrityCorrupted.severity(), Severity::Critical);
+    /// assert_eq!(FileOps::ReadDone.severity(), Severity::Debug);
+    /// ```
+    fn severity(&self) -> Severity {
+        variant_of(self).map_or(Severity::Error, |variant| variant.severity)
+    }
+
+    /// The HTTP status of a response reporting the code as declared by its family.
+    /// A success is otherwise `200 OK` and a failure `500 Internal Server Error`
+    /// ### Examples
+    /// ```
+    /// # use custom_codes::{Code, DbOps, HardwareResources, Networking};
+    /// assert_eq!(DbOps::DocumentFound.http_status(), 200);
+    /// assert_eq!(DbOps::DocumentNotFound.http_status(), 404);
+    /// assert_eq!(Networking::NetTimedOut.http_status(), 504);
+    /// assert_eq!(Networking::PortInUse.http_status(), 500);
+    /// assert_eq!(HardwareResources::DriverMissing.http_status(), 500);
+    /// ```
+    fn http_status(&self) -> u16 {
+        variant_of(self).map_or(500, |variant| variant.http_status)
+    }
+
+    /// The gRPC status code of a response reporting the code as declared by its family,
+    /// otherwise the code matching `http_status()`
+    /// ### Examples
+    /// ```
+    /// # use custom_codes::{Code, DbOps, GrpcCode};
+    /// assert_eq!(DbOps::DocumentNotFound.grpc_code(), GrpcCode::NotFound);
+    /// assert_eq!(DbOps::DbIntegrityCorrupted.grpc_code(), GrpcCode::DataLoss);
+    /// ```
+    fn grpc_code(&self) -> GrpcCode {
+        variant_of(self).map_or(GrpcCode::Internal, |variant| variant.grpc)
+    }
+
+    /// Get a variant from its numeric code as returned by `code()`
+    fn from_code(code: u32) -> Option<Self> {
+        if code / 1000 == Self::FAMILY_ID as u32 {
+            Self::from_number((code % 1000) as u16)
+        } else {
+            None
+        }
+    }
+}
+
+/// Describes a variant of a code family
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct CodeVariant {
+    /// The name of the variant as declared
+    pub name: &'static str,
+    /// The stable number of the variant within its family
+    pub number: u16,
+    /// The type of the payload carried by the variant, if any, eg. `Vec<String>`
+    pub payload: Option<&'static str>,
+    /// The lines of the doc comment of the variant
+    pub docs: &'static [&'static str],
+    /// Whether the variant reports a successful operation or a healthy state, see `Code::is_success`
+    pub success: bool,
+    /// The severity the variant is logged at, see `Code::severity`
+    pub severity: Severity,
+    /// Whether and how the operation reporting the variant can be retried, see `Retryable`
+    pub retry: RetryHint,
+    /// The HTTP status of a response reporting the variant, see `Code::http_status`
+    pub http_status: u16,
+    /// The gRPC status code of a response reporting the variant, see `Code::grpc_code`
+    pub grpc: GrpcCode,
+}
+
+#[cfg(feature = "alloc")]
+impl CodeVariant {
+    /// The doc comment of the variant on a single line
+    /// ### Examples
+    /// ```
+    /// # use custom_codes::{Code, DbOps};
+    /// let variant = DbOps::VARIANTS.iter().find(|variant| variant.name == "DocumentNotFound").unwrap();
+    /// assert_eq!(variant.description(), "Document does not exist");
+    /// ```
+    pub fn description(&self) -> alloc::string::String {
+        summary(self.docs)
+    }
+}
+
+/// A deprecated name of a variant, eg. the misspelled `AccpetedRAC` of `AccessStatus::AcceptedRAC`
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct CodeAlias {
+    /// The deprecated name
+    pub name: &'static str,
+    /// The name of the variant replacing it
+    pub canonical: &'static str,
+}
+
+/// Describes a code family, see `FAMILIES`
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Family {
+    /// The name of the family, eg. `DbOps`
+    pub name: &'static str,
+    /// The stable number of the family
+    pub id: u16,
+    /// The lines of the doc comment of the family
+    pub docs: &'static [&'static str],
+    /// Every variant of the family in the order it is declared
+    pub variants: &'static [CodeVariant],
+    /// The deprecated names still accepted for some variants
+    pub aliases: &'static [CodeAlias],
+}
+
+impl Family {
+    /// Describes the family of `T`
+    pub const fn of<T: Code>() -> Self {
+        Family {
+            name: T::FAMILY,
+            id: T::FAMILY_ID,
+            docs: T::DOCS,
+            variants: T::VARIANTS,
+            aliases: T::ALIASES,
+        }
+    }
+
+    /// The numeric code of `variant` of the family, see `Code::code`
+    /// ### Examples
+    /// ```
+    /// # use custom_codes::{Code, DbOps, Family};
+    /// let variant = DbOps::VARIANTS.iter().find(|variant| variant.name == "DocumentNotFound").unwrap();
+    /// assert_eq!(Family::of::<DbOps>().code(variant), 4025);
+    /// ```
+    pub const fn code(&self, variant: &CodeVariant) -> u32 {
+        numeric_code(self.id, variant.number)
+    }
+}
+
+/// The family number multiplied by `1000` added to the variant number
+const fn numeric_code(family: u16, number: u16) -> u32 {
+    family as u32 * 1000 + number as u32
+}
+
+#[cfg(feature = "alloc")]
+impl Family {
+    /// The doc comment of the family up to its first heading or example, on a single line
+    pub fn description(&self) -> alloc::string::String {
+        summary(self.docs)
+    }
+
+    /// The name serde gives a variant, its misspelled alias when `legacy-names` is enabled
+    pub(crate) fn serialized_name(&self, variant: &'static str) -> &'static str {
+        match self.alias_of(variant) {
+            Some(alias) if cfg!(feature = "legacy-names") => alias.name,
+            _ => variant,
+        }
+    }
+
+    /// The other spelling of a variant which serde accepts when deserializing
+    pub(crate) fn accepted_name(&self, variant: &'static str) -> Option<&'static str> {
+        let alias = self.alias_of(variant)?;
+
+        if cfg!(feature = "legacy-names") {
+            Some(alias.canonical)
+        } else {
+            Some(alias.name)
+        }
+    }
+
+    fn alias_of(&self, variant: &str) -> Option<&'static CodeAlias> {
+        self.aliases.iter().find(|alias| alias.canonical == variant)
+    }
+}
+
+/// Joins the lines of a doc comment up to its first heading or code block
+#[cfg(feature = "alloc")]
+fn summary(docs: &[&str]) -> alloc::string::String {
+    docs.iter()
+        .map(|line| line.trim())
+        .take_while(|line| !line.starts_with('#') && !line.starts_with("```"))
+        .filter(|line| !line.is_empty())
+        .collect::<alloc::vec::Vec<_>>()
+        .join(" ")
+}
+
+/// Writes a name in upper snake case, eg. `DbOps` is written as `DB_OPS`
+#[cfg(feature = "alloc")]
+pub(crate) fn screaming_snake_case(name: &'static str) -> alloc::string::String {
+    use alloc::string::ToString;
+
+    SnakeCaseName(name).to_string().to_ascii_uppercase()
+}
+
+/// Writes a name in kebab case, eg. `DbOps` is written as `db-ops`
+#[cfg(feature = "alloc")]
+pub(crate) fn kebab_case(name: &'static str) -> alloc::string::String {
+    use alloc::string::ToString;
+
+    SnakeCaseName(name).to_string().replace('_', "-")
+}
+
+/// Every code family of the crate ordered by family number
+/// ### Examples
+/// ```
+/// # use custom_codes::FAMILIES;
+/// let db_ops = FAMILIES.iter().find(|family| family.name == "DbOps").unwrap();
+/// assert_eq!(db_ops.id, 4);
+/// ```
+pub const FAMILIES: &[Family] = &[
+    Family::of::<crate::Outcome>(),
+    Family::of::<crate::AccessStatus>(),
+    Family::of::<crate::CustomBool>(),
+    Family::of::<crate::DbOps>(),
+    Family::of::<crate::ExecCommand>(),
+    Family::of::<crate::FileOps>(),
+    Family::of::<crate::Compression>(),
+    Family::of::<crate::Subscription>(),
+    Family::of::<crate::ActivityStatus>(),
+    Family::of::<crate::ActivityToggle>(),
+    Family::of::<crate::Cli>(),
+    Family::of::<crate::DateTimeOp>(),
+    Family::of::<crate::SecOps>(),
+    Family::of::<crate::KeyLength>(),
+    Family::of::<crate::HardwareResources>(),
+    Family::of::<crate::Networking>(),
+    Family::of::<crate::SecHardware>(),
+];
+
+/// Writes the snake case form of a variant name, eg. `AuthenticMAC` is written as `authentic_mac`
+#[cfg(any(feature = "serde", feature = "alloc"))]
+pub(crate) struct SnakeCaseName(pub(crate) &'static str);
+
+#[cfg(any(feature = "serde", feature = "alloc"))]
+impl SnakeCaseName {
+    fn for_each(&self, mut f: impl FnMut(char)) {
+        let chars = self.0.as_bytes();
+
+        for (index, &current) in chars.iter().enumerate() {
+            let current = current as char;
+
+            if current.is_ascii_uppercase() && index > 0 {
+                let previous = chars[index - 1] as char;
+                let next_is_lower = chars
+                    .get(index + 1)
+                    .map(|next| next.is_ascii_lowercase())
+                    .unwrap_or(false);
+
+                if previous.is_ascii_lowercase()
+                    || previous.is_ascii_digit()
+                    || (previous.is_ascii_uppercase() && next_is_lower)
+                {
+                    f('_');
+                }
+            }
+
+            f(current.to_ascii_lowercase());
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    pub(crate) fn matches(&self, input: &str) -> bool {
+        let mut input = input.chars();
+        let mut matches = true;
+        self.for_each(|expected| matches &= input.next() == Some(expected));
+
+        matches && input.next().is_none()
+    }
+}
+
+#[cfg(any(feature = "serde", feature = "alloc"))]
+impl fmt::Display for SnakeCaseName {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut result = Ok(());
+        self.for_each(|current| {
+            if result.is_ok() {
+                result = fmt::Write::write_char(f, current);
+            }
+        });
+
+        result
+    }
+}
+
+/// The variant of `code` as described by `Code::VARIANTS`
+pub(crate) fn variant_of<T: Code>(code: &T) -> Option<&'static CodeVariant> {
+    numbered::<T>(code.number())
+}
+
+/// The variant of the family `T` numbered `number`
+pub(crate) fn numbered<T: Code>(number: u16) -> Option<&'static CodeVariant> {
+    T::VARIANTS.iter().find(|variant| variant.number == number)
+}
+
+/// The variant of the family `T` named `name`
+#[cfg(feature = "problem-details")]
+pub(crate) fn named<T: Code>(name: &str) -> Option<&'static CodeVariant> {
+    T::VARIANTS.iter().find(|variant| variant.name == name)
+}
+
+/// The index of the variant of `code` in `Code::VARIANTS`
+#[cfg(feature = "alloc")]
+pub(crate) fn index_of<T: Code>(code: &T) -> Option<usize> {
+    let number = code.number();
+
+    T::VARIANTS
+        .iter()
+        .position(|variant| variant.number == number)
+}
+
+/// Whether `name` is one of `names`, usable in constants
+pub const fn contains(names: &[&str], name: &str) -> bool {
+    let mut index = 0;
+    while index < names.len() {
+        if equals(names[index], name) {
+            return true;
+        }
+        index += 1;
+    }
+
+    false
+}
+
+/// The severity given to `name` under `severities`, otherwise the default of a success or a failure
+pub const fn severity_of(severities: &[(&str, Severity)], name: &str, success: bool) -> Severity {
+    let mut index = 0;
+    while index < severities.len() {
+        if equals(severities[index].0, name) {
+            return severities[index].1;
+        }
+        index += 1;
+    }
+
+    if success {
+        Severity::Debug
+    } else {
+        Severity::Error
+    }
+}
+
+/// The hint given to `name` under `retries`, otherwise `RetryHint::Never`
+pub const fn retry_of(retries: &[(&str, RetryHint)], name: &str) -> RetryHint {
+    let mut index = 0;
+    while index < retries.len() {
+        if equals(retries[index].0, name) {
+            return retries[index].1;
+        }
+        index += 1;
+    }
+
+    RetryHint::Never
+}
+
+/// Whether two strings are equal, usable in constants
+pub(crate) const fn equals(left: &str, right: &str) -> bool {
+    let (left, right) = (left.as_bytes(), right.as_bytes());
+    if left.len() != right.len() {
+        return false;
+    }
+
+    let mut byte = 0;
+    while byte < left.len() && left[byte] == right[byte] {
+        byte += 1;
+    }
+
+    byte == left.len()
+}
+
+/// The HTTP status given to `name` under `statuses`, otherwise `200` for a success and `500` for a failure
+pub const fn http_of(statuses: &[(&str, u16)], name: &str, success: bool) -> u16 {
+    let mut index = 0;
+    while index < statuses.len() {
+        if equals(statuses[index].0, name) {
+            return statuses[index].1;
+        }
+        index += 1;
+    }
+
+    if success {
+        200
+    } else {
+        500
+    }
+}
+
+/// The gRPC code given to `name` under `codes`, otherwise the code matching its HTTP `status`
+pub const fn grpc_of(codes: &[(&str, GrpcCode)], name: &str, status: u16) -> GrpcCode {
+    let mut index = 0;
+    while index < codes.len() {
+        if equals(codes[index].0, name) {
+            return codes[index].1;
+        }
+        index += 1;
+    }
+
+    GrpcCode::from_http(status)
+}
+
+/// Writes the doc comment of the variant of `code` up to its first heading or code block
+/// on a single line, its name when it is not documented
+pub fn write_message<T: Code>(code: &T, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    let docs = variant_of(code).map_or(&[][..], |variant| variant.docs);
+    let mut lines = docs
+        .iter()
+        .map(|line| line.trim())
+        .take_while(|line| !line.starts_with('#') && !line.starts_with("```"))
+        .filter(|line| !line.is_empty());
+
+    match lines.next() {
+        None => f.write_str(code.name()),
+        Some(first) => {
+            f.write_str(first)?;
+            lines.try_for_each(|line| {
+                f.write_str(" ")?;
+                f.write_str(line)
+            })
+        }
+    }
+}
+
+/// The error returned by the `FromStr` implementation of a code family
+/// when the name is not one of its variants without a payload
+/// ### Examples
+/// ```
+/// # use custom_codes::{DbOps, ParseCodeError};
+/// assert_eq!("DocumentNotFound".parse(), Ok(DbOps::DocumentNotFound));
+/// assert_eq!(
+///     "EncounteredErrors".parse::<DbOps>(),
+///     Err(ParseCodeError { family: "DbOps" })
+/// );
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct ParseCodeError {
+    /// The name of the family the code was parsed for
+    pub family: &'static str,
+}
+
+impl fmt::Display for ParseCodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "not the name of a {} code without a payload",
+            self.family
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ParseCodeError {}
+
+/// Expands items when the crate is built with `alloc`, whatever the features of the crate
+/// invoking `define_codes!`
+#[cfg(feature = "alloc")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __alloc_items {
+    ($($item:item)*) => { $($item)* };
+}
+
+/// Expands items when the crate is built with `alloc`, whatever the features of the crate
+/// invoking `define_codes!`
+#[cfg(not(feature = "alloc"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __alloc_items {
+    ($($item:item)*) => {};
+}
+
+/// Expands items when the crate is built with `std`, whatever the features of the crate
+/// invoking `define_codes!`
+#[cfg(feature = "std")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __std_items {
+    ($($item:item)*) => { $($item)* };
+}
+
+/// Expands items when the crate is built with `std`, whatever the features of the crate
+/// invoking `define_codes!`
+#[cfg(not(feature = "std"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __std_items {
+    ($($item:item)*) => {};
+}
+
+/// Declares a code family, its enum and the `Code` implementation, from a single table.
+/// Every variant is followed by its stable number. A variant carrying a payload declares its type
+/// like a tuple variant, a type implementing `codec::WirePayload` with the `alloc` feature.
+/// A deprecated name kept for compatibility points to the variant replacing it with `=>`
+/// instead of a number, it is declared as an associated constant equal to that variant
+/// so it is not a variant of its own.
+/// The doc comments of the family and its variants are kept in `Code::DOCS` and `CodeVariant::docs`,
+/// the summary of the doc comment of a variant is its message.
+///
+/// The table is followed by optional sections, in this order:
+/// - `failures` lists the variants which are not successful, see `Code::is_success`
+/// - `severities` gives a `Severity` to some variants, a successful variant is otherwise logged at
+///   `Severity::Debug` and a failure at `Severity::Error`
+/// - `retries` gives a `RetryHint` to the variants worth retrying, the other variants are never retried
+/// - `http` gives an HTTP status to some failures, the other failures are `500` and the successes `200`
+/// - `grpc` gives a `GrpcCode` to some variants, the others follow their HTTP status
+/// - `custom_impls` lists the traits among `Display`, `Error` and `FromStr` which the family implements
+///   itself instead of the generated implementations
+///
+/// Besides `Code`, the family implements `Display` writing the message of a variant,
+/// `FromStr` parsing the name of a variant without a payload, `std::error::Error` with the `std` feature
+/// and `codec::Binary` with the `alloc` feature. Serde and other traits are derived by the enum itself.
+///
+/// Family numbers go from `1` to `255` and those below `100` are reserved for the families of this crate.
+/// Variant numbers go from `1` to `999` and are unique within their family, a `CodeSet` of the family
+/// needs them below `CodeSet::CAPACITY`.
+/// ### Examples
+/// ```
+/// use custom_codes::{define_codes, Code, GrpcCode, Severity};
+///
+/// define_codes! {
+///     /// Outcome of charging a customer
+///     #[derive(Debug, PartialEq, Eq, Clone)]
+///     pub enum Billing = 120 {
+///         /// The customer has been charged
+///         Charged = 1,
+///         /// The card of the customer has been declined
+///         CardDeclined = 2,
+///         /// The payment provider did not answer in time
+///         ProviderTimedOut = 3,
+///     }
+///     failures [CardDeclined, ProviderTimedOut]
+///     severities [CardDeclined => Warning]
+///     retries [ProviderTimedOut => WithBackoff]
+///     http [CardDeclined => 402, ProviderTimedOut => 504]
+///     grpc [CardDeclined => FailedPrecondition]
+/// }
+///
+/// assert_eq!(Billing::CardDeclined.code(), 120_002);
+/// assert_eq!(Billing::CardDeclined.severity(), Severity::Warning);
+/// assert_eq!(Billing::CardDeclined.http_status(), 402);
+/// assert_eq!(Billing::ProviderTimedOut.grpc_code(), GrpcCode::DeadlineExceeded);
+/// assert_eq!(Billing::CardDeclined.to_string(), "The card of the customer has been declined");
+/// assert_eq!("Charged".parse(), Ok(Billing::Charged));
+/// ```
+#[macro_export]
+macro_rules! define_codes {
+    (
+        $(#[doc = $family_doc:literal])*
+        #[derive($($derive:path),*)]
+        $(#[$family_attr:meta])*
+        $vis:vis enum $family:ident = $family_id:literal {
+            $($entries:tt)*
+        }
+        $( failures [$($failure:ident),* $(,)?] )?
+        $( severities [$($severe:ident => $severity:ident),* $(,)?] )?
+        $( retries [$($retried:ident => $hint:ident),* $(,)?] )?
+        $( http [$($with_status:ident => $status:literal),* $(,)?] )?
+        $( grpc [$($with_grpc:ident => $grpc:ident),* $(,)?] )?
+        $( custom_impls [$($custom:ident),* $(,)?] )?
+    ) => {
+        $crate::define_codes! {
+            @munch
+            [
+                $(#[doc = $family_doc])*
+                #[derive($($derive),*)]
+                $(#[$family_attr])*
+                $vis enum $family = $family_id
+                failures [$($($failure)*)?]
+                severities [$($($severe => $severity,)*)?]
+                retries [$($($retried => $hint,)*)?]
+                http [$($($with_status => $status,)*)?]
+                grpc [$($($with_grpc => $grpc,)*)?]
+                custom_impls [$($($custom)*)?]
+            ]
+            [] [] []
+            $($entries)*
+        }
+    };
+    // Sorts the entries into variants and aliases while keeping the declaration order of the enum
+    (
+        @munch [$($header:tt)*] [$($variants:tt)*] [$($aliases:tt)*] [$($body:tt)*]
+        $(#[doc = $doc:literal])*
+        #[deprecated(note = $note:literal)]
+        $alias:ident => $canonical:ident,
+        $($rest:tt)*
+    ) => {
+        $crate::define_codes! {
+            @munch
+            [$($header)*]
+            [$($variants)*]
+            [$($aliases)* [$($doc)*] $note $alias => $canonical,]
+            [$($body)*]
+            $($rest)*
+        }
+    };
+    (
+        @munch [$($header:tt)*] [$($variants:tt)*] [$($aliases:tt)*] [$($body:tt)*]
+        $(#[doc = $doc:literal])*
+        $(#[cfg($cfg:meta)])*
+        $(#[cfg_attr($($attr:tt)*)])*
+        $variant:ident $(($payload:ty))? = $number:literal,
+        $($rest:tt)*
+    ) => {
+        $crate::define_codes! {
+            @munch
+            [$($header)*]
+            [$($variants)* [$($doc)*] $(#[cfg($cfg)])* $variant $(($payload))? = $number,]
+            [$($aliases)*]
+            [
+                $($body)*
+                $(#[doc = $doc])*
+                $(#[cfg($cfg)])*
+                $(#[cfg_attr($($attr)*)])*
+                $variant $(($payload))?,
+            ]
+            $($rest)*
+        }
+    };
+    (
+        @munch
+        [
+            $(#[doc = $family_doc:literal])*
+            #[derive($($derive:path),*)]
+            $(#[$family_attr:meta])*
+            $vis:vis enum $family:ident = $family_id:literal
+            failures [$($failure:ident)*]
+            severities [$($severe:ident => $severity:ident,)*]
+            retries [$($retried:ident => $hint:ident,)*]
+            http [$($with_status:ident => $status:literal,)*]
+            grpc [$($with_grpc:ident => $grpc:ident,)*]
+            custom_impls [$($custom:ident)*]
+        ]
+        [$( [$($doc:literal)*] $(#[cfg($cfg:meta)])* $variant:ident $(($payload:ty))? = $number:literal, )*]
+        [$( [$($alias_doc:literal)*] $note:literal $alias:ident => $canonical:ident, )*]
+        [$($body:tt)*]
+    ) => {
+        $(#[doc = $family_doc])*
+        #[derive($($derive),*)]
+        $(#[$family_attr])*
+        $vis enum $family {
+            $($body)*
+        }
+
+        #[allow(non_upper_case_globals)]
+        impl $family {
+            $(
+                $(#[doc = $alias_doc])*
+                #[deprecated(note = $note)]
+                pub const $alias: Self = $family::$canonical;
+            )*
+
+            const FAILURES: &'static [&'static str] = &[$(stringify!($failure)),*];
+            const SEVERITIES: &'static [(&'static str, $crate::Severity)] =
+                &[$((stringify!($severe), $crate::Severity::$severity)),*];
+            const RETRIES: &'static [(&'static str, $crate::RetryHint)] =
+                &[$((stringify!($retried), $crate::RetryHint::$hint)),*];
+            const HTTP_STATUSES: &'static [(&'static str, u16)] =
+                &[$((stringify!($with_status), $status)),*];
+            const GRPC_CODES: &'static [(&'static str, $crate::GrpcCode)] =
+                &[$((stringify!($with_grpc), $crate::GrpcCode::$grpc)),*];
+        }
+
+        // Every failure, severity, retry, status and gRPC code must name a variant of the family
+        // whose numbers are unique and fit in a code
+        const _: () = {
+            let variants = &[$(stringify!($variant)),*];
+            let numbers: &[u16] = &[$($number),*];
+            let family: u16 = $family_id;
+            assert!(family != 0 && family < 256, "a family number goes from 1 to 255");
+            let mut index = 0;
+            while index < numbers.len() {
+                assert!(
+                    numbers[index] != 0 && numbers[index] < 1000,
+                    "a variant number goes from 1 to 999"
+                );
+                let mut other = index + 1;
+                while other < numbers.len() {
+                    assert!(
+                        numbers[index] != numbers[other],
+                        "two variants of the family share a number"
+                    );
+                    other += 1;
+                }
+                index += 1;
+            }
+            let mut index = 0;
+            while index < $family::FAILURES.len() {
+                assert!(
+                    $crate::__private::contains(variants, $family::FAILURES[index]),
+                    "a failure does not name a variant of the family"
+                );
+                index += 1;
+            }
+            let mut index = 0;
+            while index < $family::SEVERITIES.len() {
+                assert!(
+                    $crate::__private::contains(variants, $family::SEVERITIES[index].0),
+                    "a severity does not name a variant of the family"
+                );
+                index += 1;
+            }
+            let mut index = 0;
+            while index < $family::RETRIES.len() {
+                assert!(
+                    $crate::__private::contains(variants, $family::RETRIES[index].0),
+                    "a retry does not name a variant of the family"
+                );
+                index += 1;
+            }
+            let mut index = 0;
+            while index < $family::HTTP_STATUSES.len() {
+                assert!(
+                    $crate::__private::contains(variants, $family::HTTP_STATUSES[index].0),
+                    "an HTTP status does not name a variant of the family"
+                );
+                index += 1;
+            }
+            let mut index = 0;
+            while index < $family::GRPC_CODES.len() {
+                assert!(
+                    $crate::__private::contains(variants, $family::GRPC_CODES[index].0),
+                    "a gRPC code does not name a variant of the family"
+                );
+                index += 1;
+            }
+        };
+
+        #[allow(deprecated)]
+        impl $crate::Code for $family {
+            const FAMILY: &'static str = stringify!($family);
+            const FAMILY_ID: u16 = $family_id;
+            const DOCS: &'static [&'static str] = &[$($family_doc),*];
+            const VARIANTS: &'static [$crate::CodeVariant] = &[
+                $(
+                    $(#[cfg($cfg)])*
+                    $crate::CodeVariant {
+                        name: stringify!($variant),
+                        number: $number,
+                        payload: $crate::define_codes!(@payload $($payload)?),
+                        docs: &[$($doc),*],
+                        success: !$crate::__private::contains($family::FAILURES, stringify!($variant)),
+                        severity: $crate::__private::severity_of(
+                            $family::SEVERITIES,
+                            stringify!($variant),
+                            !$crate::__private::contains($family::FAILURES, stringify!($variant)),
+                        ),
+                        retry: $crate::__private::retry_of($family::RETRIES, stringify!($variant)),
+                        http_status: $crate::__private::http_of(
+                            $family::HTTP_STATUSES,
+                            stringify!($variant),
+                            !$crate::__private::contains($family::FAILURES, stringify!($variant)),
+                        ),
+                        grpc: $crate::__private::grpc_of(
+                            $family::GRPC_CODES,
+                            stringify!($variant),
+                            $crate::__private::http_of(
+                                $family::HTTP_STATUSES,
+                                stringify!($variant),
+                                !$crate::__private::contains($family::FAILURES, stringify!($variant)),
+                            ),
+                        ),
+                    },
+                )*
+            ];
+            const ALIASES: &'static [$crate::CodeAlias] = &[
+                $( $crate::CodeAlias { name: stringify!($alias), canonical: stringify!($canonical) }, )*
+            ];
+
+            fn name(&self) -> &'static str {
+                match self {
+                    $( $(#[cfg($cfg)])* $crate::define_codes!(@pattern $family $variant $($payload)?) => stringify!($variant), )*
+                }
+            }
+
+            fn number(&self) -> u16 {
+                match self {
+                    $( $(#[cfg($cfg)])* $crate::define_codes!(@pattern $family $variant $($payload)?) => $number, )*
+                }
+            }
+
+            fn from_name(name: &str) -> Option<Self> {
+                match name {
+                    $( $(#[cfg($cfg)])* stringify!($variant) => $crate::define_codes!(@unit $family $variant $($payload)?), )*
+                    $( stringify!($alias) => Some($family::$canonical), )*
+                    _ => None,
+                }
+            }
+
+            fn from_number(number: u16) -> Option<Self> {
+                match number {
+                    $( $(#[cfg($cfg)])* $number => $crate::define_codes!(@unit $family $variant $($payload)?), )*
+                    _ => None,
+                }
+            }
+        }
+
+        $( $crate::define_codes!(@custom $custom); )*
+        $crate::define_codes!(@display $family [$($custom)*]);
+        $crate::define_codes!(@error $family [$($custom)*]);
+        $crate::define_codes!(@from_str $family [$($custom)*]);
+
+        $crate::__alloc_items! {
+            #[allow(deprecated)]
+            impl $crate::codec::Binary for $family {
+                fn encode_into(&self, buffer: &mut $crate::__private::Vec<u8>) {
+                    use $crate::Code;
+
+                    match self {
+                        $(
+                            $(#[cfg($cfg)])*
+                            $crate::define_codes!(@bind payload $family $variant $($payload)?) => {
+                                $crate::define_codes!(@encode buffer payload $number $($payload)?)
+                            }
+                        )*
+                    }
+                }
+
+                fn decode(
+                    bytes: &[u8],
+                ) -> ::core::result::Result<(Self, usize), $crate::codec::DecodeError> {
+                    let (raw, read) = $crate::codec::RawCode::decode(bytes)?;
+                    raw.expect_family::<Self>()?;
+
+                    let code = match raw.number() {
+                        $( $(#[cfg($cfg)])* $number => $crate::define_codes!(@decode raw $family $variant $($payload)?), )*
+                        number => {
+                            return Err($crate::codec::DecodeError::UnknownVariant {
+                                family: u16::from(raw.family()),
+                                number,
+                            })
+                        }
+                    };
+
+                    Ok((code, read))
+                }
+            }
+        }
+    };
+    // Only the generated implementations can be replaced by the family
+    (@custom Display) => {};
+    (@custom Error) => {};
+    (@custom FromStr) => {};
+    (@display $family:ident [Display $($custom:ident)*]) => {};
+    (@display $family:ident [$other:ident $($custom:ident)*]) => {
+        $crate::define_codes!(@display $family [$($custom)*]);
+    };
+    (@display $family:ident []) => {
+        impl ::core::fmt::Display for $family {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                $crate::__private::write_message(self, f)
+            }
+        }
+    };
+    (@error $family:ident [Error $($custom:ident)*]) => {};
+    (@error $family:ident [$other:ident $($custom:ident)*]) => {
+        $crate::define_codes!(@error $family [$($custom)*]);
+    };
+    (@error $family:ident []) => {
+        $crate::__std_items! {
+            impl $crate::__private::Error for $family {}
+        }
+    };
+    (@from_str $family:ident [FromStr $($custom:ident)*]) => {};
+    (@from_str $family:ident [$other:ident $($custom:ident)*]) => {
+        $crate::define_codes!(@from_str $family [$($custom)*]);
+    };
+    (@from_str $family:ident []) => {
+        impl ::core::str::FromStr for $family {
+            type Err = $crate::ParseCodeError;
+
+            fn from_str(name: &str) -> ::core::result::Result<Self, Self::Err> {
+                <Self as $crate::Code>::from_name(name).ok_or($crate::ParseCodeError {
+                    family: stringify!($family),
+                })
+            }
+        }
+    };
+    (@bind $binding:ident $family:ident $variant:ident $payload:ty) => { $family::$variant($binding) };
+    (@bind $binding:ident $family:ident $variant:ident) => { $family::$variant };
+    (@encode $buffer:ident $binding:ident $number:literal $payload:ty) => {
+        $crate::__private::write_with_payload($buffer, Self::FAMILY_ID, $number, $binding)
+    };
+    (@encode $buffer:ident $binding:ident $number:literal) => {
+        $crate::__private::write_header($buffer, Self::FAMILY_ID, $number, false)
+    };
+    (@decode $raw:ident $family:ident $variant:ident $payload:ty) => {
+        $family::$variant(<$payload as $crate::codec::WirePayload>::read(&$raw)?)
+    };
+    (@decode $raw:ident $family:ident $variant:ident) => {
+        $crate::__private::unit(&$raw, $family::$variant)?
+    };
+    (@payload $payload:ty) => { Some(stringify!($payload)) };
+    (@payload) => { None };
+    (@pattern $family:ident $variant:ident $payload:ty) => { $family::$variant(_) };
+    (@pattern $family:ident $variant:ident) => { $family::$variant };
+    (@unit $family:ident $variant:ident $payload:ty) => { None };
+    (@unit $family:ident $variant:ident) => { Some($family::$variant) };
+}
@@ -0,0 +1,408 @@
+//! A compact binary encoding of codes for high volume RPC.
+//!
+//! A code is encoded as its family number in a single byte followed by a varint (LEB128) holding
+//! the variant number shifted left by one, the lowest bit is set when a payload follows.
+//! A payload is prefixed by its length in bytes as a varint. Inside the payload
+//! - a `String` or `Vec<u8>` is written as is
+//! - a `Vec<String>` or `Vec<Vec<u8>>` is written as a sequence of items each prefixed by its length as a varint
+//! - a nested code such as `KeyLength` is written as its variant number as a varint
+//!
+//! Codes without a payload therefore take 2 bytes on the wire, eg. `DbOps::DocumentNotFound` is `[4, 50]`.
+//!
+//! `Binary::decode` returns an owned code while `RawCode::decode` borrows the payload from the input.
+//! ### Examples
+//! ```
+//! use custom_codes::codec::{Binary, RawCode};
+//! use custom_codes::DbOps;
+//!
+//! let mut buffer = Vec::new();
+//! DbOps::DocumentNotFound.encode_into(&mut buffer);
+//! assert_eq!(buffer, [4, 50]);
+//!
+//! buffer.clear();
+//! DbOps::DbList(vec!["users".into(), "orders".into()]).encode_into(&mut buffer);
+//! let (decoded, read) = DbOps::decode(&buffer).unwrap();
+//! assert_eq!(decoded, DbOps::DbList(vec!["users".into(), "orders".into()]));
+//! assert_eq!(read, buffer.len());
+//!
+//! // Borrow the payload without allocating
+//! let (raw, _) = RawCode::decode(&buffer).unwrap();
+//! let names: Vec<&str> = raw.str_list().unwrap().collect::<Result<_, _>>().unwrap();
+//! assert_eq!(names, ["users", "orders"]);
+//! ```
+
+use crate::{Code, KeyLength};
+use alloc::{string::String, vec::Vec};
+use core::convert::TryFrom;
+use core::fmt;
+
+/// Encodes and decodes a code in the compact binary format described in the module documentation
+pub trait Binary: Code {
+    /// Appends the encoded code to the buffer
+    fn encode_into(&self, buffer: &mut Vec<u8>);
+
+    /// Decodes a code from the start of the bytes, returning it with the number of bytes read
+    fn decode(bytes: &[u8]) -> Result<(Self, usize), DecodeError>;
+
+    /// Encodes the code into a new buffer
+    fn encode(&self) -> Vec<u8> {
+        let mut buffer = Vec::new();
+        self.encode_into(&mut buffer);
+
+        buffer
+    }
+}
+
+/// Errors returned when decoding a code
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum DecodeError {
+    /// The input ended before the code was complete
+    UnexpectedEof,
+    /// A varint is longer than the number it holds allows
+    VarintOverflow,
+    /// The code belongs to another family than the one being decoded
+    WrongFamily {
+        /// The family number being decoded
+        expected: u16,
+        /// The family number found in the input
+        found: u16,
+    },
+    /// The variant number is not known to the family
+    UnknownVariant {
+        /// The family number
+        family: u16,
+        /// The variant number
+        number: u64,
+    },
+    /// The variant carries a payload but none was encoded
+    MissingPayload,
+    /// The variant does not carry a payload but one was encoded
+    UnexpectedPayload,
+    /// The payload is not valid for the type carried by the variant
+    InvalidPayload,
+    /// A string in the payload is not valid UTF-8
+    InvalidUtf8,
+}
+
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DecodeError::UnexpectedEof => write!(f, "The input ended before the code was complete"),
+            DecodeError::VarintOverflow => write!(f, "A varint is too long"),
+            DecodeError::WrongFamily { expected, found } => {
+                write!(
+                    f,
+                    "Expected a code of family `{}` but found family `{}`",
+                    expected, found
+                )
+            }
+            DecodeError::UnknownVariant { family, number } => {
+                write!(f, "Variant `{}` is unknown to family `{}`", number, family)
+            }
+            DecodeError::MissingPayload => {
+                write!(f, "The variant carries a payload but none was encoded")
+            }
+            DecodeError::UnexpectedPayload => write!(f, "The variant does not carry a payload"),
+            DecodeError::InvalidPayload => write!(f, "The payload is invalid for the variant"),
+            DecodeError::InvalidUtf8 => write!(f, "A string in the payload is not valid UTF-8"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for DecodeError {}
+
+/// A decoded code borrowing its payload from the input
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct RawCode<'a> {
+    family: u8,
+    number: u64,
+    payload: Option<&'a [u8]>,
+}
+
+impl<'a> RawCode<'a> {
+    /// Decodes the code at the start of the bytes, returning it with the number of bytes read
+    pub fn decode(bytes: &'a [u8]) -> Result<(Self, usize), DecodeError> {
+        let (&family, rest) = bytes.split_first().ok_or(DecodeError::UnexpectedEof)?;
+        let (header, header_len) = read_varint(rest)?;
+        let mut read = 1 + header_len;
+
+        let payload = if header & 1 == 1 {
+            let (length, length_len) = read_varint(&bytes[read..])?;
+            read += length_len;
+            let end = usize::try_from(length)
+                .ok()
+                .and_then(|length| read.checked_add(length))
+                .filter(|&end| end <= bytes.len())
+                .ok_or(DecodeError::UnexpectedEof)?;
+            let payload = &bytes[read..end];
+            read = end;
+
+            Some(payload)
+        } else {
+            None
+        };
+
+        Ok((
+            RawCode {
+                family,
+                number: header >> 1,
+                payload,
+            },
+            read,
+        ))
+    }
+
+    /// The family number of the code
+    pub fn family(&self) -> u8 {
+        self.family
+    }
+
+    /// The variant number of the code within its family
+    pub fn number(&self) -> u64 {
+        self.number
+    }
+
+    /// The encoded payload, `None` if the variant does not carry one
+    pub fn payload(&self) -> Option<&'a [u8]> {
+        self.payload
+    }
+
+    /// Borrows a `String` payload
+    pub fn str(&self) -> Result<&'a str, DecodeError> {
+        core::str::from_utf8(self.payload.ok_or(DecodeError::MissingPayload)?)
+            .map_err(|_| DecodeError::InvalidUtf8)
+    }
+
+    /// Borrows a `Vec<u8>` payload
+    pub fn bytes(&self) -> Result<&'a [u8], DecodeError> {
+        self.payload.ok_or(DecodeError::MissingPayload)
+    }
+
+    /// Iterates over the items of a `Vec<Vec<u8>>` payload
+    pub fn list(&self) -> Result<RawList<'a>, DecodeError> {
+        Ok(RawList(self.payload.ok_or(DecodeError::MissingPayload)?))
+    }
+
+    /// Iterates over the items of a `Vec<String>` payload
+    pub fn str_list(
+        &self,
+    ) -> Result<impl Iterator<Item = Result<&'a str, DecodeError>>, DecodeError> {
+        Ok(self.list()?.map(|item| {
+            item.and_then(|item| core::str::from_utf8(item).map_err(|_| DecodeError::InvalidUtf8))
+        }))
+    }
+
+    /// Checks that the code belongs to the family of `T`
+    pub fn expect_family<T: Code>(&self) -> Result<(), DecodeError> {
+        if self.family as u16 == T::FAMILY_ID {
+            Ok(())
+        } else {
+            Err(DecodeError::WrongFamily {
+                expected: T::FAMILY_ID,
+                found: u16::from(self.family),
+            })
+        }
+    }
+}
+
+/// Iterates over the length prefixed items of a list payload without copying them
+#[derive(Debug, Clone)]
+pub struct RawList<'a>(&'a [u8]);
+
+impl<'a> Iterator for RawList<'a> {
+    type Item = Result<&'a [u8], DecodeError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.0.is_empty() {
+            return None;
+        }
+
+        let item = read_varint(self.0).and_then(|(length, length_len)| {
+            let end = usize::try_from(length)
+                .ok()
+                .and_then(|length| length_len.checked_add(length))
+                .filter(|&end| end <= self.0.len())
+                .ok_or(DecodeError::UnexpectedEof)?;
+
+            Ok((&self.0[length_len..end], end))
+        });
+
+        match item {
+            Ok((item, end)) => {
+                self.0 = &self.0[end..];
+                Some(Ok(item))
+            }
+            Err(error) => {
+                self.0 = &[];
+                Some(Err(error))
+            }
+        }
+    }
+}
+
+/// A type carried as the payload of a variant
+pub trait WirePayload: Sized {
+    /// The length of the encoded payload in bytes
+    fn encoded_len(&self) -> usize;
+
+    /// Appends the encoded payload to the buffer
+    fn write(&self, buffer: &mut Vec<u8>);
+
+    /// Decodes the payload from a `RawCode`
+    fn read(raw: &RawCode<'_>) -> Result<Self, DecodeError>;
+}
+
+impl WirePayload for String {
+    fn encoded_len(&self) -> usize {
+        self.len()
+    }
+
+    fn write(&self, buffer: &mut Vec<u8>) {
+        buffer.extend_from_slice(self.as_bytes());
+    }
+
+    fn read(raw: &RawCode<'_>) -> Result<Self, DecodeError> {
+        raw.str().map(String::from)
+    }
+}
+
+impl WirePayload for Vec<u8> {
+    fn encoded_len(&self) -> usize {
+        self.len()
+    }
+
+    fn write(&self, buffer: &mut Vec<u8>) {
+        buffer.extend_from_slice(self);
+    }
+
+    fn read(raw: &RawCode<'_>) -> Result<Self, DecodeError> {
+        raw.bytes().map(Vec::from)
+    }
+}
+
+impl WirePayload for Vec<String> {
+    fn encoded_len(&self) -> usize {
+        self.iter()
+            .map(|item| varint_len(item.len() as u64) + item.len())
+            .sum()
+    }
+
+    fn write(&self, buffer: &mut Vec<u8>) {
+        for item in self {
+            write_varint(buffer, item.len() as u64);
+            buffer.extend_from_slice(item.as_bytes());
+        }
+    }
+
+    fn read(raw: &RawCode<'_>) -> Result<Self, DecodeError> {
+        raw.str_list()?.map(|item| item.map(String::from)).collect()
+    }
+}
+
+impl WirePayload for Vec<Vec<u8>> {
+    fn encoded_len(&self) -> usize {
+        self.iter()
+            .map(|item| varint_len(item.len() as u64) + item.len())
+            .sum()
+    }
+
+    fn write(&self, buffer: &mut Vec<u8>) {
+        for item in self {
+            write_varint(buffer, item.len() as u64);
+            buffer.extend_from_slice(item);
+        }
+    }
+
+    fn read(raw: &RawCode<'_>) -> Result<Self, DecodeError> {
+        raw.list()?.map(|item| item.map(Vec::from)).collect()
+    }
+}
+
+impl WirePayload for KeyLength {
+    fn encoded_len(&self) -> usize {
+        varint_len(self.number() as u64)
+    }
+
+    fn write(&self, buffer: &mut Vec<u8>) {
+        write_varint(buffer, self.number() as u64);
+    }
+
+    fn read(raw: &RawCode<'_>) -> Result<Self, DecodeError> {
+        let payload = raw.bytes()?;
+        let (number, read) = read_varint(payload)?;
+        if read != payload.len() {
+            return Err(DecodeError::InvalidPayload);
+        }
+
+        u16::try_from(number)
+            .ok()
+            .and_then(KeyLength::from_number)
+            .ok_or(DecodeError::InvalidPayload)
+    }
+}
+
+/// Appends the family and variant number of a code to the buffer
+#[doc(hidden)]
+pub fn write_header(buffer: &mut Vec<u8>, family: u16, number: u16, has_payload: bool) {
+    buffer.push(family as u8);
+    write_varint(buffer, (number as u64) << 1 | has_payload as u64);
+}
+
+/// Appends a code carrying a payload to the buffer
+#[doc(hidden)]
+pub fn write_with_payload<P: WirePayload>(
+    buffer: &mut Vec<u8>,
+    family: u16,
+    number: u16,
+    payload: &P,
+) {
+    write_header(buffer, family, number, true);
+    write_varint(buffer, payload.encoded_len() as u64);
+    payload.write(buffer);
+}
+
+/// Checks that a unit variant was not encoded with a payload
+#[doc(hidden)]
+pub fn unit<T>(raw: &RawCode<'_>, code: T) -> Result<T, DecodeError> {
+    match raw.payload() {
+        None => Ok(code),
+        Some(_) => Err(DecodeError::UnexpectedPayload),
+    }
+}
+
+fn write_varint(buffer: &mut Vec<u8>, mut value: u64) {
+    while value >= 0x80 {
+        buffer.push(value as u8 | 0x80);
+        value >>= 7;
+    }
+    buffer.push(value as u8);
+}
+
+fn varint_len(value: u64) -> usize {
+    let mut length = 1;
+    let mut value = value >> 7;
+    while value > 0 {
+        length += 1;
+        value >>= 7;
+    }
+
+    length
+}
+
+fn read_varint(bytes: &[u8]) -> Result<(u64, usize), DecodeError> {
+    let mut value = 0u64;
+
+    for (index, &byte) in bytes.iter().enumerate() {
+        if index == 10 || (index == 9 && byte > 1) {
+            return Err(DecodeError::VarintOverflow);
+        }
+
+        value |= ((byte & 0x7f) as u64) << (7 * index);
+        if byte & 0x80 == 0 {
+            return Ok((value, index + 1));
+        }
+    }
+
+    Err(DecodeError::UnexpectedEof)
+}
@@ -0,0 +1,270 @@
+//! Codes attached to errors as they propagate.
+//!
+//! `CodedError` wraps an error together with a code and an optional context message. With the `anyhow` feature,
+//! `WithCode` attaches a code to the error of a `Result` or to an `anyhow::Error`, eg.
+//! `fs::read(path).with_code(FileOps::ReadDenied)?`. The codes of an error are listed by `attached_codes()`
+//! from the outermost to the innermost, and `try_downcast` returns the outermost one as `DownCastErrors::Coded`
+//! before inspecting the root cause.
+//! ### Examples
+//! ```
+//! # #[cfg(feature = "anyhow")]
+//! # {
+//! use custom_codes::coded::{attached_codes, WithCode};
+//! use custom_codes::{try_downcast, DbOps, DownCastErrors, FileOps};
+//!
+//! fn load() -> anyhow::Result<Vec<u8>> {
+//!     let bytes = std::fs::read("/nonexistent/users.db").with_code(FileOps::OpenError)?;
+//!     Ok(bytes)
+//! }
+//!
+//! let error = load().with_code_context(DbOps::DbNotFound, "loading the users").unwrap_err();
+//!
+//! let codes: Vec<_> = attached_codes(error.as_ref()).collect();
+//! assert_eq!(codes[0].code::<DbOps>(), Some(&DbOps::DbNotFound));
+//! assert_eq!(codes[1].code::<FileOps>(), Some(&FileOps::OpenError));
+//!
+//! match try_downcast(&error) {
+//!     DownCastErrors::Coded(coded) => assert_eq!(coded.name(), "DbNotFound"),
+//!     _ => panic!("the attached code is returned first"),
+//! }
+//! # }
+//! ```
+
+use crate::{Code, GrpcCode, RetryHint, Retryable, Severity};
+use std::any::Any;
+use std::error::Error;
+use std::fmt;
+
+/// A code of any family attached to an error
+pub trait AnyCode: Any + fmt::Debug + Send + Sync {
+    /// The name of the family, see `Code::FAMILY`
+    fn family(&self) -> &'static str;
+    /// The name of the variant, see `Code::name()`
+    fn name(&self) -> &'static str;
+    /// The numeric code, see `Code::code()`
+    fn code(&self) -> u32;
+    /// See `Code::is_success()`
+    fn is_success(&self) -> bool;
+    /// See `Code::severity()`
+    fn severity(&self) -> Severity;
+    /// See `Retryable::retry_hint()`
+    fn retry_hint(&self) -> RetryHint;
+    /// See `Code::http_status()`
+    fn http_status(&self) -> u16;
+    /// See `Code::grpc_code()`
+    fn grpc_code(&self) -> GrpcCode;
+    /// The doc comment of the variant
+    fn description(&self) -> String;
+    /// The code as `Any` to recover its type
+    fn as_any(&self) -> &dyn Any;
+}
+
+impl<T: Code + fmt::Debug + Send + Sync + 'static> AnyCode for T {
+    fn family(&self) -> &'static str {
+        T::FAMILY
+    }
+
+    fn name(&self) -> &'static str {
+        Code::name(self)
+    }
+
+    fn code(&self) -> u32 {
+        Code::code(self)
+    }
+
+    fn is_success(&self) -> bool {
+        Code::is_success(self)
+    }
+
+    fn severity(&self) -> Severity {
+        Code::severity(self)
+    }
+
+    fn retry_hint(&self) -> RetryHint {
+        Retryable::retry_hint(self)
+    }
+
+    fn http_status(&self) -> u16 {
+        Code::http_status(self)
+    }
+
+    fn grpc_code(&self) -> GrpcCode {
+        Code::grpc_code(self)
+    }
+
+    fn description(&self) -> String {
+        crate::code::variant_of(self).map_or_else(String::new, |variant| variant.description())
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+/// An error carrying a code and an optional context message, its source is the error the code is attached to
+#[derive(Debug)]
+pub struct CodedError {
+    code: Box<dyn AnyCode>,
+    context: Option<String>,
+    source: Source,
+}
+
+/// The error a code is attached to, an `anyhow::Error` is kept as is so the errors it wraps remain
+/// reachable through `source()`
+#[derive(Debug)]
+enum Source {
+    Boxed(Box<dyn Error + Send + Sync + 'static>),
+    #[cfg(feature = "anyhow")]
+    Anyhow(anyhow::Error),
+}
+
+impl CodedError {
+    /// Attaches `code` to `source`
+    pub fn new<C, E>(code: C, source: E) -> Self
+    where
+        C: Code + fmt::Debug + Send + Sync + 'static,
+        E: Into<Box<dyn Error + Send + Sync + 'static>>,
+    {
+        CodedError {
+            code: Box::new(code),
+            context: None,
+            source: Source::Boxed(source.into()),
+        }
+    }
+
+    #[cfg(feature = "anyhow")]
+    fn from_anyhow<C>(code: C, source: anyhow::Error) -> Self
+    where
+        C: Code + fmt::Debug + Send + Sync + 'static,
+    {
+        CodedError {
+            code: Box::new(code),
+            context: None,
+            source: Source::Anyhow(source),
+        }
+    }
+
+    /// Describes what was being done when the error occurred
+    pub fn with_context(mut self, context: impl Into<String>) -> Self {
+        self.context = Some(context.into());
+        self
+    }
+
+    /// The attached code if it is of family `T`
+    pub fn code<T: Code + 'static>(&self) -> Option<&T> {
+        self.code.as_any().downcast_ref()
+    }
+
+    /// The attached code whatever its family
+    pub fn any_code(&self) -> &dyn AnyCode {
+        &*self.code
+    }
+
+    /// The name of the family of the attached code
+    pub fn family(&self) -> &'static str {
+        self.code.family()
+    }
+
+    /// The name of the variant of the attached code
+    pub fn name(&self) -> &'static str {
+        self.code.name()
+    }
+
+    /// The context message, if any
+    pub fn context(&self) -> Option<&str> {
+        self.context.as_deref()
+    }
+}
+
+impl fmt::Display for CodedError {
+    /// Writes the context, or the family and variant of the code when there is no context
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.context {
+            Some(context) => f.write_str(context),
+            None => write!(f, "{}::{}", self.code.family(), self.code.name()),
+        }
+    }
+}
+
+impl Error for CodedError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match &self.source {
+            Source::Boxed(source) => Some(&**source),
+            #[cfg(feature = "anyhow")]
+            Source::Anyhow(source) => Some(source.as_ref()),
+        }
+    }
+}
+
+/// Every `CodedError` in the `source()` chain of `error`, from the outermost to the innermost
+pub fn attached_codes<'e>(
+    error: &'e (dyn Error + 'static),
+) -> impl Iterator<Item = &'e CodedError> + 'e {
+    std::iter::successors(Some(error), |&error| error.source())
+        .filter_map(|error| error.downcast_ref::<CodedError>())
+}
+
+/// The outermost `CodedError` in the `source()` chain of `error`
+pub fn outermost_code<'e>(error: &'e (dyn Error + 'static)) -> Option<&'e CodedError> {
+    attached_codes(error).next()
+}
+
+/// The innermost `CodedError` in the `source()` chain of `error`
+pub fn innermost_code<'e>(error: &'e (dyn Error + 'static)) -> Option<&'e CodedError> {
+    attached_codes(error).last()
+}
+
+/// Attaches codes to errors, see the module documentation
+#[cfg(feature = "anyhow")]
+pub trait WithCode {
+    /// The type holding the error once the code is attached
+    type Output;
+
+    /// Attaches `code` to the error
+    fn with_code<C>(self, code: C) -> Self::Output
+    where
+        C: Code + fmt::Debug + Send + Sync + 'static;
+
+    /// Attaches `code` to the error together with a context message
+    fn with_code_context<C>(self, code: C, context: impl Into<String>) -> Self::Output
+    where
+        C: Code + fmt::Debug + Send + Sync + 'static;
+}
+
+#[cfg(feature = "anyhow")]
+impl<T, E: Into<anyhow::Error>> WithCode for Result<T, E> {
+    type Output = anyhow::Result<T>;
+
+    fn with_code<C>(self, code: C) -> Self::Output
+    where
+        C: Code + fmt::Debug + Send + Sync + 'static,
+    {
+        self.map_err(|error| error.into().with_code(code))
+    }
+
+    fn with_code_context<C>(self, code: C, context: impl Into<String>) -> Self::Output
+    where
+        C: Code + fmt::Debug + Send + Sync + 'static,
+    {
+        self.map_err(|error| error.into().with_code_context(code, context))
+    }
+}
+
+#[cfg(feature = "anyhow")]
+impl WithCode for anyhow::Error {
+    type Output = anyhow::Error;
+
+    fn with_code<C>(self, code: C) -> Self::Output
+    where
+        C: Code + fmt::Debug + Send + Sync + 'static,
+    {
+        anyhow::Error::new(CodedError::from_anyhow(code, self))
+    }
+
+    fn with_code_context<C>(self, code: C, context: impl Into<String>) -> Self::Output
+    where
+        C: Code + fmt::Debug + Send + Sync + 'static,
+    {
+        anyhow::Error::new(CodedError::from_anyhow(code, self).with_context(context))
+    }
+}
@@ -0,0 +1,578 @@
+//! Forward compatible deserialization of codes.
+//!
+//! A newer service may send a variant that an older consumer does not know yet, which fails to deserialize
+//! and breaks rolling deployments. Wrapping a code in `Compat` decodes such a variant into `Compat::Unknown`
+//! which preserves the name, numeric code and payload it was received with and serializes them back unchanged.
+//!
+//! `Compat` reads the default serde representation of the code, eg. `"DbFound"` or `{"DbList":["users"]}`,
+//! as well as numeric codes written by `repr::NumericCode`, eg. `4012`.
+//! Known variants are always written in the default representation.
+//!
+//! ### Examples
+//! ```
+//! use custom_codes::compat::{Compat, Payload};
+//! use custom_codes::DbOps;
+//!
+//! let known: Compat<DbOps> = serde_json::from_str(r#""DbFound""#).unwrap();
+//! assert_eq!(known, Compat::Known(DbOps::DbFound));
+//!
+//! let known: Compat<DbOps> = serde_json::from_str(r#"{"DbList":["users"]}"#).unwrap();
+//! assert_eq!(known, Compat::Known(DbOps::DbList(vec!["users".into()])));
+//!
+//! let json = r#"{"DbReplicated":{"replicas":3}}"#;
+//! let unknown: Compat<DbOps> = serde_json::from_str(json).unwrap();
+//! match &unknown {
+//!     Compat::Unknown { name, code, payload } => {
+//!         assert_eq!(name.as_deref(), Some("DbReplicated"));
+//!         assert_eq!(*code, None);
+//!         assert!(matches!(payload, Some(Payload::Map(_))));
+//!     }
+//!     Compat::Known(_) => panic!("`DbReplicated` is not a `DbOps` variant"),
+//! }
+//! assert_eq!(serde_json::to_string(&unknown).unwrap(), json);
+//!
+//! let unknown: Compat<DbOps> = serde_json::from_str("4999").unwrap();
+//! assert_eq!(serde_json::to_string(&unknown).unwrap(), "4999");
+//! ```
+
+use crate::Code;
+use alloc::{string::String, vec::Vec};
+use core::convert::TryFrom;
+use core::fmt;
+use core::marker::PhantomData;
+use serde::de::value::{MapDeserializer, SeqDeserializer};
+use serde::de::{
+    self, DeserializeSeed, EnumAccess, IgnoredAny, IntoDeserializer, MapAccess, SeqAccess,
+    Unexpected, VariantAccess, Visitor,
+};
+use serde::ser::{SerializeMap, SerializeSeq};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// A code which may be a variant unknown to this version of the crate
+#[derive(Debug, PartialEq, Clone)]
+pub enum Compat<T> {
+    /// A variant known to this version of the crate
+    Known(T),
+    /// A variant unknown to this version of the crate, preserved as it was received
+    Unknown {
+        /// The name of the variant, `None` if a numeric code was received
+        name: Option<String>,
+        /// The numeric code of the variant, `None` if a name was received
+        code: Option<u32>,
+        /// The payload carried by the variant, if any
+        payload: Option<Payload>,
+    },
+}
+
+impl<T> Compat<T> {
+    /// Returns the known code, `None` if the variant is unknown
+    pub fn known(&self) -> Option<&T> {
+        match self {
+            Compat::Known(code) => Some(code),
+            Compat::Unknown { .. } => None,
+        }
+    }
+
+    /// Checks whether the variant is unknown to this version of the crate
+    pub fn is_unknown(&self) -> bool {
+        matches!(self, Compat::Unknown { .. })
+    }
+}
+
+impl<T> From<T> for Compat<T> {
+    fn from(code: T) -> Self {
+        Compat::Known(code)
+    }
+}
+
+/// A self describing value holding the payload of an unknown variant
+#[derive(Debug, PartialEq, Clone)]
+pub enum Payload {
+    /// A unit or null value
+    Unit,
+    /// A boolean
+    Bool(bool),
+    /// A signed integer
+    I64(i64),
+    /// An unsigned integer
+    U64(u64),
+    /// A floating point number
+    F64(f64),
+    /// A string
+    String(String),
+    /// Raw bytes
+    Bytes(Vec<u8>),
+    /// A sequence of values
+    Seq(Vec<Payload>),
+    /// A map of keys to values in the order they were received
+    Map(Vec<(Payload, Payload)>),
+}
+
+impl<T: Serialize> Serialize for Compat<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Compat::Known(code) => code.serialize(serializer),
+            Compat::Unknown {
+                name: Some(name),
+                payload: None,
+                ..
+            } => serializer.serialize_str(name),
+            Compat::Unknown {
+                name: Some(name),
+                payload: Some(payload),
+                ..
+            } => {
+                let mut map = serializer.serialize_map(Some(1))?;
+                map.serialize_entry(name, payload)?;
+                map.end()
+            }
+            Compat::Unknown {
+                name: None,
+                code: Some(code),
+                ..
+            } => serializer.serialize_u32(*code),
+            Compat::Unknown { .. } => serializer.serialize_unit(),
+        }
+    }
+}
+
+impl<'de, T: Code + Deserialize<'de>> Deserialize<'de> for Compat<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_any(CompatVisitor(PhantomData))
+    }
+}
+
+struct CompatVisitor<T>(PhantomData<T>);
+
+impl<'de, T: Code + Deserialize<'de>> Visitor<'de> for CompatVisitor<T> {
+    type Value = Compat<T>;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "a `{}` variant or numeric code", T::FAMILY)
+    }
+
+    fn visit_str<E: de::Error>(self, value: &str) -> Result<Compat<T>, E> {
+        if let Some(code) = T::from_name(value) {
+            Ok(Compat::Known(code))
+        } else if T::VARIANTS.iter().any(|variant| variant.name == value) {
+            Err(E::custom(format_args!(
+                "`{}::{}` is missing its payload",
+                T::FAMILY,
+                value
+            )))
+        } else {
+            Ok(Compat::Unknown {
+                name: Some(value.into()),
+                code: None,
+                payload: None,
+            })
+        }
+    }
+
+    fn visit_u64<E: de::Error>(self, value: u64) -> Result<Compat<T>, E> {
+        let code = u32::try_from(value)
+            .map_err(|_| E::invalid_value(de::Unexpected::Unsigned(value), &self))?;
+
+        Ok(match T::from_code(code) {
+            Some(known) => Compat::Known(known),
+            None => Compat::Unknown {
+                name: None,
+                code: Some(code),
+                payload: None,
+            },
+        })
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Compat<T>, A::Error> {
+        let name: String = match map.next_key()? {
+            Some(name) => name,
+            None => return Err(de::Error::invalid_length(0, &self)),
+        };
+
+        let found = if T::VARIANTS.iter().any(|variant| variant.name == name) {
+            Compat::Known(T::deserialize(KnownVariant {
+                name: &name,
+                map: &mut map,
+            })?)
+        } else {
+            Compat::Unknown {
+                name: Some(name),
+                code: None,
+                payload: Some(map.next_value()?),
+            }
+        };
+
+        match map.next_key::<IgnoredAny>()? {
+            None => Ok(found),
+            Some(_) => Err(de::Error::invalid_length(2, &self)),
+        }
+    }
+}
+
+/// Presents a map entry whose key is a known variant to the derived `Deserialize` of the code
+struct KnownVariant<'a, A> {
+    name: &'a str,
+    map: &'a mut A,
+}
+
+impl<'de, 'a, A: MapAccess<'de>> Deserializer<'de> for KnownVariant<'a, A> {
+    type Error = A::Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, A::Error> {
+        visitor.visit_enum(self)
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf option unit unit_struct newtype_struct seq tuple
+        tuple_struct map struct enum identifier ignored_any
+    }
+}
+
+impl<'de, 'a, A: MapAccess<'de>> EnumAccess<'de> for KnownVariant<'a, A> {
+    type Error = A::Error;
+    type Variant = Self;
+
+    fn variant_seed<V: DeserializeSeed<'de>>(self, seed: V) -> Result<(V::Value, Self), A::Error> {
+        let variant = seed.deserialize(de::value::StrDeserializer::new(self.name))?;
+
+        Ok((variant, self))
+    }
+}
+
+impl<'de, 'a, A: MapAccess<'de>> VariantAccess<'de> for KnownVariant<'a, A> {
+    type Error = A::Error;
+
+    fn unit_variant(self) -> Result<(), A::Error> {
+        self.map.next_value::<IgnoredAny>().map(|_| ())
+    }
+
+    fn newtype_variant_seed<T: DeserializeSeed<'de>>(self, seed: T) -> Result<T::Value, A::Error> {
+        self.map.next_value_seed(seed)
+    }
+
+    fn tuple_variant<V: Visitor<'de>>(
+        self,
+        _len: usize,
+        _visitor: V,
+    ) -> Result<V::Value, A::Error> {
+        Err(de::Error::custom(
+            "codes only have unit and newtype variants",
+        ))
+    }
+
+    fn struct_variant<V: Visitor<'de>>(
+        self,
+        _fields: &'static [&'static str],
+        _visitor: V,
+    ) -> Result<V::Value, A::Error> {
+        Err(de::Error::custom(
+            "codes only have unit and newtype variants",
+        ))
+    }
+}
+
+impl Serialize for Payload {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Payload::Unit => serializer.serialize_unit(),
+            Payload::Bool(value) => serializer.serialize_bool(*value),
+            Payload::I64(value) => serializer.serialize_i64(*value),
+            Payload::U64(value) => serializer.serialize_u64(*value),
+            Payload::F64(value) => serializer.serialize_f64(*value),
+            Payload::String(value) => serializer.serialize_str(value),
+            Payload::Bytes(value) => serializer.serialize_bytes(value),
+            Payload::Seq(values) => {
+                let mut seq = serializer.serialize_seq(Some(values.len()))?;
+                for value in values {
+                    seq.serialize_element(value)?;
+                }
+                seq.end()
+            }
+            Payload::Map(entries) => {
+                let mut map = serializer.serialize_map(Some(entries.len()))?;
+                for (key, value) in entries {
+                    map.serialize_entry(key, value)?;
+                }
+                map.end()
+            }
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Payload {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_any(PayloadVisitor)
+    }
+}
+
+struct PayloadVisitor;
+
+impl<'de> Visitor<'de> for PayloadVisitor {
+    type Value = Payload;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "any value")
+    }
+
+    fn visit_bool<E: de::Error>(self, value: bool) -> Result<Payload, E> {
+        Ok(Payload::Bool(value))
+    }
+
+    fn visit_i64<E: de::Error>(self, value: i64) -> Result<Payload, E> {
+        Ok(Payload::I64(value))
+    }
+
+    fn visit_u64<E: de::Error>(self, value: u64) -> Result<Payload, E> {
+        Ok(Payload::U64(value))
+    }
+
+    fn visit_f64<E: de::Error>(self, value: f64) -> Result<Payload, E> {
+        Ok(Payload::F64(value))
+    }
+
+    fn visit_str<E: de::Error>(self, value: &str) -> Result<Payload, E> {
+        Ok(Payload::String(value.into()))
+    }
+
+    fn visit_string<E: de::Error>(self, value: String) -> Result<Payload, E> {
+        Ok(Payload::String(value))
+    }
+
+    fn visit_bytes<E: de::Error>(self, value: &[u8]) -> Result<Payload, E> {
+        Ok(Payload::Bytes(value.into()))
+    }
+
+    fn visit_byte_buf<E: de::Error>(self, value: Vec<u8>) -> Result<Payload, E> {
+        Ok(Payload::Bytes(value))
+    }
+
+    fn visit_unit<E: de::Error>(self) -> Result<Payload, E> {
+        Ok(Payload::Unit)
+    }
+
+    fn visit_none<E: de::Error>(self) -> Result<Payload, E> {
+        Ok(Payload::Unit)
+    }
+
+    fn visit_some<D: Deserializer<'de>>(self, deserializer: D) -> Result<Payload, D::Error> {
+        Payload::deserialize(deserializer)
+    }
+
+    fn visit_newtype_struct<D: Deserializer<'de>>(
+        self,
+        deserializer: D,
+    ) -> Result<Payload, D::Error> {
+        Payload::deserialize(deserializer)
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Payload, A::Error> {
+        let mut values = Vec::new();
+        while let Some(value) = seq.next_element()? {
+            values.push(value);
+        }
+
+        Ok(Payload::Seq(values))
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Payload, A::Error> {
+        let mut entries = Vec::new();
+        while let Some(entry) = map.next_entry()? {
+            entries.push(entry);
+        }
+
+        Ok(Payload::Map(entries))
+    }
+}
+
+/// Reads a `Payload` back into the type it was received as
+impl<'de, E: de::Error> IntoDeserializer<'de, E> for Payload {
+    type Deserializer = PayloadDeserializer<E>;
+
+    fn into_deserializer(self) -> PayloadDeserializer<E> {
+        PayloadDeserializer {
+            payload: self,
+            error: PhantomData,
+        }
+    }
+}
+
+impl Payload {
+    fn unexpected(&self) -> Unexpected<'_> {
+        match self {
+            Payload::Unit => Unexpected::Unit,
+            Payload::Bool(value) => Unexpected::Bool(*value),
+            Payload::I64(value) => Unexpected::Signed(*value),
+            Payload::U64(value) => Unexpected::Unsigned(*value),
+            Payload::F64(value) => Unexpected::Float(*value),
+            Payload::String(value) => Unexpected::Str(value),
+            Payload::Bytes(value) => Unexpected::Bytes(value),
+            Payload::Seq(_) => Unexpected::Seq,
+            Payload::Map(_) => Unexpected::Map,
+        }
+    }
+}
+
+/// A deserializer reading a `Payload`, see `IntoDeserializer`
+pub struct PayloadDeserializer<E> {
+    payload: Payload,
+    error: PhantomData<fn() -> E>,
+}
+
+impl<'de, E: de::Error> Deserializer<'de> for PayloadDeserializer<E> {
+    type Error = E;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, E> {
+        match self.payload {
+            Payload::Unit => visitor.visit_unit(),
+            Payload::Bool(value) => visitor.visit_bool(value),
+            Payload::I64(value) => visitor.visit_i64(value),
+            Payload::U64(value) => visitor.visit_u64(value),
+            Payload::F64(value) => visitor.visit_f64(value),
+            Payload::String(value) => visitor.visit_string(value),
+            Payload::Bytes(value) => visitor.visit_byte_buf(value),
+            Payload::Seq(values) => {
+                let mut seq = SeqDeserializer::new(values.into_iter());
+                let value = visitor.visit_seq(&mut seq)?;
+                seq.end()?;
+
+                Ok(value)
+            }
+            Payload::Map(entries) => {
+                let mut map = MapDeserializer::new(entries.into_iter());
+                let value = visitor.visit_map(&mut map)?;
+                map.end()?;
+
+                Ok(value)
+            }
+        }
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, E> {
+        match self.payload {
+            Payload::Unit => visitor.visit_none(),
+            _ => visitor.visit_some(self),
+        }
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, E> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, E> {
+        match self.payload {
+            Payload::String(variant) => visitor.visit_enum(PayloadEnum {
+                variant: Payload::String(variant),
+                value: None,
+                error: PhantomData,
+            }),
+            Payload::Map(mut entries) if entries.len() == 1 => {
+                let (variant, value) = entries.remove(0);
+                visitor.visit_enum(PayloadEnum {
+                    variant,
+                    value: Some(value),
+                    error: PhantomData,
+                })
+            }
+            other => Err(de::Error::invalid_type(other.unexpected(), &"an enum")),
+        }
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf unit unit_struct seq tuple
+        tuple_struct map struct identifier ignored_any
+    }
+}
+
+/// An enum read from a `Payload`, either the name of a unit variant or a map from a variant to its content
+struct PayloadEnum<E> {
+    variant: Payload,
+    value: Option<Payload>,
+    error: PhantomData<fn() -> E>,
+}
+
+impl<'de, E: de::Error> EnumAccess<'de> for PayloadEnum<E> {
+    type Error = E;
+    type Variant = Self;
+
+    fn variant_seed<V: DeserializeSeed<'de>>(self, seed: V) -> Result<(V::Value, Self), E> {
+        let variant = seed.deserialize(self.variant.into_deserializer())?;
+
+        Ok((
+            variant,
+            PayloadEnum {
+                variant: Payload::Unit,
+                value: self.value,
+                error: PhantomData,
+            },
+        ))
+    }
+}
+
+impl<'de, E: de::Error> VariantAccess<'de> for PayloadEnum<E> {
+    type Error = E;
+
+    fn unit_variant(self) -> Result<(), E> {
+        match self.value {
+            None | Some(Payload::Unit) => Ok(()),
+            Some(other) => Err(de::Error::invalid_type(
+                other.unexpected(),
+                &"a unit variant",
+            )),
+        }
+    }
+
+    fn newtype_variant_seed<T: DeserializeSeed<'de>>(self, seed: T) -> Result<T::Value, E> {
+        match self.value {
+            Some(value) => seed.deserialize(value.into_deserializer()),
+            None => Err(de::Error::invalid_type(
+                Unexpected::UnitVariant,
+                &"a newtype variant",
+            )),
+        }
+    }
+
+    fn tuple_variant<V: Visitor<'de>>(self, _len: usize, visitor: V) -> Result<V::Value, E> {
+        match self.value {
+            Some(value @ Payload::Seq(_)) => value.into_deserializer().deserialize_any(visitor),
+            Some(other) => Err(de::Error::invalid_type(
+                other.unexpected(),
+                &"a tuple variant",
+            )),
+            None => Err(de::Error::invalid_type(
+                Unexpected::UnitVariant,
+                &"a tuple variant",
+            )),
+        }
+    }
+
+    fn struct_variant<V: Visitor<'de>>(
+        self,
+        _fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, E> {
+        match self.value {
+            Some(value @ Payload::Map(_)) | Some(value @ Payload::Seq(_)) => {
+                value.into_deserializer().deserialize_any(visitor)
+            }
+            Some(other) => Err(de::Error::invalid_type(
+                other.unexpected(),
+                &"a struct variant",
+            )),
+            None => Err(de::Error::invalid_type(
+                Unexpected::UnitVariant,
+                &"a struct variant",
+            )),
+        }
+    }
+}
@@ -1,28 +1,45 @@
+#[cfg(feature = "alloc")]
+use alloc::{string::String, vec::Vec};
+#[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
-/// Give the Outcome of an operation
-/// ### Examples
-/// ```
-/// # use custom_codes::Outcome;
-/// let foo = Outcome::Success;
-/// assert_eq!(foo, Outcome::Success);
-/// ```
-#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
-pub enum Outcome {
-    ///Result of Operation completed successfully
-    Success,
-    /// Result of Operation produced an error
-    Failure,
-    /// The result of the operation was forwarded to another operation
-    Forward,
+define_codes! {
+    /// Give the Outcome of an operation
+    /// ### Examples
+    /// ```
+    /// # use custom_codes::Outcome;
+    /// let foo = Outcome::Success;
+    /// assert_eq!(foo, Outcome::Success);
+    /// ```
+    #[derive(Debug, PartialEq, Eq, Clone)]
+    #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+    pub enum Outcome = 1 {
+        ///Result of Operation completed successfully
+        Success = 1,
+        /// Result of Operation produced an error
+        Failure = 2,
+        /// The result of the operation was forwarded to another operation
+        Forward = 3,
+    }
+    failures [
+        Failure,
+    ]
+}
+/// The doc comment of `GenericOutcome`, also written by the generated schemas which describe it
+macro_rules! generic_outcome_docs {
+    () => {
+        "Give the Generic Outcome of an operation with custom result as a generic"
+    };
 }
-/// Give the Generic Outcome of an operation with custom result as a generic
+
+#[doc = generic_outcome_docs!()]
 /// ### Examples
 /// ```
 /// # use custom_codes::GenericOutcome;
 /// let foo = GenericOutcome::Success("Foo");
 /// ```
-#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum GenericOutcome<Reason> {
     ///Result of Operation completed successfully
     Success(Reason),
@@ -31,522 +48,732 @@ pub enum GenericOutcome<Reason> {
     /// The result of the operation was forwarded to another operation
     Forward(Reason),
 }
-/// Access status of token or access
-/// ### Examples
-/// ```
-/// # use custom_codes::AccessStatus;
-/// let foo = AccessStatus::Granted;
-/// assert_eq!(foo, AccessStatus::Granted);
-/// ```
-#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
-pub enum AccessStatus {
-    /// Process or User has been given a lease
-    Granted,
-    /// Token has been discarded
-    Revoked,
-    /// Access to token or session has expired
-    Expired,
-    /// Access has been denied
-    Denied,
-    /// The access token has been rejected because it is not authentic/genuine
-    Rejected,
-    /// Operation is being executed
-    InProgress,
-    /// Random Authentication Code (RAC) Token is genuine/authentic and therefore has been accepted
-    AccpetedRAC,
-    ///  Random Authentication Code (RAC) Token is not genuine/authentic and therefore has been rejected
-    RejectedRAC,
-    /// Status of an operation is not initialized
-    Unspecified,
+define_codes! {
+    /// Access status of token or access
+    /// ### Examples
+    /// ```
+    /// # use custom_codes::AccessStatus;
+    /// let foo = AccessStatus::Granted;
+    /// assert_eq!(foo, AccessStatus::Granted);
+    /// ```
+    #[derive(Debug, PartialEq, Eq, Clone)]
+    #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+    pub enum AccessStatus = 2 {
+        /// Process or User has been given a lease
+        Granted = 1,
+        /// Token has been discarded
+        Revoked = 2,
+        /// Access to token or session has expired
+        Expired = 3,
+        /// Access has been denied
+        Denied = 4,
+        /// The access token has been rejected because it is not authentic/genuine
+        Rejected = 5,
+        /// Operation is being executed
+        InProgress = 6,
+        /// Misspelled, use `AccessStatus::AcceptedRAC` instead
+        #[deprecated(note = "misspelled, use `AccessStatus::AcceptedRAC` instead")]
+        AccpetedRAC => AcceptedRAC,
+        /// Random Authentication Code (RAC) Token is genuine/authentic and therefore has been accepted
+        #[cfg_attr(feature = "serde", serde(alias = "AccpetedRAC"))]
+        #[cfg_attr(
+            all(feature = "serde", feature = "legacy-names"),
+            serde(rename(serialize = "AccpetedRAC"))
+        )]
+        AcceptedRAC = 7,
+        ///  Random Authentication Code (RAC) Token is not genuine/authentic and therefore has been rejected
+        RejectedRAC = 8,
+        /// Status of an operation is not initialized
+        Unspecified = 9,
+    }
+    failures [
+        Revoked, Expired, Denied, Rejected, RejectedRAC, Unspecified,
+    ]
+    severities [
+        Revoked => Warning, Expired => Warning, Denied => Warning, Rejected => Warning,
+        RejectedRAC => Warning,
+    ]
+    http [
+        Revoked => 403, Expired => 401, Denied => 403, Rejected => 403, RejectedRAC => 403,
+    ]
 }
 
-/// Creates a custom `boolean` value with more features than a Rustlang boolean which has only `true` or `false`
-/// ### Examples
-/// ```
-/// # use custom_codes::CustomBool;
-/// let foo = CustomBool::Unspecified;
-/// assert_eq!(foo, CustomBool::Unspecified);
-/// ```
-#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
-pub enum CustomBool {
-    /// Similar to boolean true
-    True,
-    /// Similar to boolean false
-    False,
-    /// Status of an operation is not initialized
-    Unspecified,
+define_codes! {
+    /// Creates a custom `boolean` value with more features than a Rustlang boolean which has only `true` or `false`
+    /// ### Examples
+    /// ```
+    /// # use custom_codes::CustomBool;
+    /// let foo = CustomBool::Unspecified;
+    /// assert_eq!(foo, CustomBool::Unspecified);
+    /// ```
+    #[derive(Debug, PartialEq, Eq, Clone)]
+    #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+    pub enum CustomBool = 3 {
+        /// Similar to boolean true
+        True = 1,
+        /// Similar to boolean false
+        False = 2,
+        /// Status of an operation is not initialized
+        Unspecified = 3,
+    }
+    failures [
+        False, Unspecified,
+    ]
 }
 
-/// Custom Response Codes Using Enums For Efficient Comparison
-/// ### Examples
-/// ```
-/// # use custom_codes::DbOps;
-/// let foo = DbOps::Inserted;
-/// assert_eq!(foo, DbOps::Inserted);
-/// ```
-#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
-pub enum DbOps {
-    /// A Repository has been created
-    RepoCreated,
-    /// A Repository has been initialized by loading all contents of the repo
-    RepoInitialized,
-    /// The repository trying to be created already exists
-    RepoAlreadyExists,
-    /// An empty Repository with no databases
-    RepoEmpty,
-    /// An repository is not available on disk
-    RepoNotFound,
-    /// A Repository has been deleted
-    RepoDropped,
-    /// A checksum of the database shows a database is consistent
-    DbIntegrityConsistent,
-    /// A checksum fo the database shows the database is not consistent
-    DbIntegrityCorrupted,
-    /// A checksum of the document is consistent
-    DocumentIntegrityConsistent,
-    /// A checksum fo the document is not consistent
-    DocumentIntegrityCorrupted,
-    /// Database has been created
-    DbCreated,
-    /// Database has been Found after a search
-    DbFound,
-    /// Database being inserted already exists in the repo
-    DbAlreadyExists,
-    /// Database does not exist
-    DbNotFound,
-    /// Database is empty
-    DbEmpty,
-    /// A list containing Databases
-    DbList(Vec<String>),
-    /// A Database has neen modified
-    DbModified,
-    /// A database has been removed
-    DbDropped,
-    /// Document has been created
-    DocumentCreated,
-    /// Document has been inserted
-    DocumentInserted,
-    /// Document has been Found after a search
-    DocumentFound,
-    /// Document is empty
-    DocumentEmpty,
-    /// List of Documents
-    DocumentList(Vec<String>),
-    /// Document being inserted already exists in the DB
-    DocumentAlreadyExists,
-    /// Document does not exist
-    DocumentNotFound,
-    /// A Document has neen modified
-    DocumentModified,
-    /// Document has been removed
-    DocumentDropped,
-    /// Field has been created
-    FieldCreated,
-    /// Field has been inserted
-    FieldInserted,
-    /// Field has been Found after a search
-    FieldFound,
-    /// Field being inserted already exists in the DB
-    FieldAlreadyExists,
-    /// Field does not exist
-    FieldNotFound,
-    /// Contents of a field in heap allocated bytes
-    FieldContents(Vec<u8>),
-    /// List of fields in a document
-    FieldList(Vec<Vec<u8>>),
-    /// A Field has neen modified
-    FieldModified,
-    /// Field has been removed
-    FieldDropped,
-    /// A `Write` to create a database is successfull
-    Created,
-    /// A `Write` to a commit log was successful
-    Commited,
-    /// A `Write` to a commit log was unsuccessful
-    UnCommited,
-    /// A `Write` is successful
-    Inserted,
-    /// A `Change` is successful
-    Changed,
-    /// A `Change` is unsuccessful
-    Unchanged,
-    /// A `Command` is skipped since the document does not exist
-    Skipped,
-    /// Document already exists
-    AlreadyExists,
-    /// Document has been updated
-    Modified,
-    /// A document value has been `Swapped` replacing it with the new value provided
-    /// Especially useful for Key/Value stores
-    Swapped,
-    /// An Document has been deleted
-    Deleted,
-    /// Key Found in KV Store/Database, Field or Document depending on query
-    KeyFound,
-    /// Key Not Found in Database, Field or Document depending on query
-    KeyNotFound,
-    /// A Command or Query Has Not Been Executed
-    NotExecuted,
-    /// Two or more queries are not supposed to be used command eg. using `get` and `list` together
-    QueryConflictError,
-    /// Database is empty
-    Empty,
-    /// The command has been added to a queue
-    Queued,
-    /// Command has been removed from queue
-    DeQueued,
-    /// Log compaction successful
-    CompactionTrue,
-    /// Log compaction unsuccessful
-    CompactionFalse,
-    /// Connection to a database is not available
-    ConnRefused,
-    /// Port is in use
-    PortAddrInUse,
-    /// Driver to the database has an error
-    DriverError,
-    /// An error occured when trying to execute a command at database level
-    RuntimeError,
-    /// Encountered Errors When Trying to connect to a database
-    EncounteredErrors(String),
-    /// No permissions to complete the I/O operation
-    PermissionDenied,
-    /// A cluster in online and ready to receive commands
-    ClusterOnline,
-    /// A cluster is offline
-    ClusterOffline,
-    /// Cluster details synced across all nodes
-    ClusterSynced,
-    /// Added a node to the cluster
-    ClusterNodeAdded,
-    /// Updated configuration to the cluster
-    ConfigUpdated,
-    /// A node has been permanently removed
-    ClusterNodeDropped,
-    /// The details have been inserted to the specified logs
-    Logged,
-    /// The details have not been logged
-    NotLogged,
-    /// The Operation was interrupted and can be resumed
-    Interrupted,
-    /// Status of an operation is not initialized
-    Unspecified,
+define_codes! {
+    /// Custom Response Codes Using Enums For Efficient Comparison
+    /// ### Examples
+    /// ```
+    /// # use custom_codes::DbOps;
+    /// let foo = DbOps::Inserted;
+    /// assert_eq!(foo, DbOps::Inserted);
+    /// ```
+    #[derive(Debug, PartialEq, Eq, Clone)]
+    #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+    pub enum DbOps = 4 {
+        /// A Repository has been created
+        RepoCreated = 1,
+        /// A Repository has been initialized by loading all contents of the repo
+        RepoInitialized = 2,
+        /// The repository trying to be created already exists
+        RepoAlreadyExists = 3,
+        /// An empty Repository with no databases
+        RepoEmpty = 4,
+        /// An repository is not available on disk
+        RepoNotFound = 5,
+        /// A Repository has been deleted
+        RepoDropped = 6,
+        /// A checksum of the database shows a database is consistent
+        DbIntegrityConsistent = 7,
+        /// A checksum fo the database shows the database is not consistent
+        DbIntegrityCorrupted = 8,
+        /// A checksum of the document is consistent
+        DocumentIntegrityConsistent = 9,
+        /// A checksum fo the document is not consistent
+        DocumentIntegrityCorrupted = 10,
+        /// Database has been created
+        DbCreated = 11,
+        /// Database has been Found after a search
+        DbFound = 12,
+        /// Database being inserted already exists in the repo
+        DbAlreadyExists = 13,
+        /// Database does not exist
+        DbNotFound = 14,
+        /// Database is empty
+        DbEmpty = 15,
+        /// A list containing Databases
+        #[cfg(feature = "alloc")]
+        DbList(Vec<String>) = 16,
+        /// A Database has neen modified
+        DbModified = 17,
+        /// A database has been removed
+        DbDropped = 18,
+        /// Document has been created
+        DocumentCreated = 19,
+        /// Document has been inserted
+        DocumentInserted = 20,
+        /// Document has been Found after a search
+        DocumentFound = 21,
+        /// Document is empty
+        DocumentEmpty = 22,
+        /// List of Documents
+        #[cfg(feature = "alloc")]
+        DocumentList(Vec<String>) = 23,
+        /// Document being inserted already exists in the DB
+        DocumentAlreadyExists = 24,
+        /// Document does not exist
+        DocumentNotFound = 25,
+        /// A Document has neen modified
+        DocumentModified = 26,
+        /// Document has been removed
+        DocumentDropped = 27,
+        /// Field has been created
+        FieldCreated = 28,
+        /// Field has been inserted
+        FieldInserted = 29,
+        /// Field has been Found after a search
+        FieldFound = 30,
+        /// Field being inserted already exists in the DB
+        FieldAlreadyExists = 31,
+        /// Field does not exist
+        FieldNotFound = 32,
+        /// Contents of a field in heap allocated bytes
+        #[cfg(feature = "alloc")]
+        FieldContents(Vec<u8>) = 33,
+        /// List of fields in a document
+        #[cfg(feature = "alloc")]
+        FieldList(Vec<Vec<u8>>) = 34,
+        /// A Field has neen modified
+        FieldModified = 35,
+        /// Field has been removed
+        FieldDropped = 36,
+        /// A `Write` to create a database is successfull
+        Created = 37,
+        /// Misspelled, use `DbOps::Committed` instead
+        #[deprecated(note = "misspelled, use `DbOps::Committed` instead")]
+        Commited => Committed,
+        /// A `Write` to a commit log was successful
+        #[cfg_attr(feature = "serde", serde(alias = "Commited"))]
+        #[cfg_attr(
+            all(feature = "serde", feature = "legacy-names"),
+            serde(rename(serialize = "Commited"))
+        )]
+        Committed = 38,
+        /// Misspelled, use `DbOps::UnCommitted` instead
+        #[deprecated(note = "misspelled, use `DbOps::UnCommitted` instead")]
+        UnCommited => UnCommitted,
+        /// A `Write` to a commit log was unsuccessful
+        #[cfg_attr(feature = "serde", serde(alias = "UnCommited"))]
+        #[cfg_attr(
+            all(feature = "serde", feature = "legacy-names"),
+            serde(rename(serialize = "UnCommited"))
+        )]
+        UnCommitted = 39,
+        /// A `Write` is successful
+        Inserted = 40,
+        /// A `Change` is successful
+        Changed = 41,
+        /// A `Change` is unsuccessful
+        Unchanged = 42,
+        /// A `Command` is skipped since the document does not exist
+        Skipped = 43,
+        /// Document already exists
+        AlreadyExists = 44,
+        /// Document has been updated
+        Modified = 45,
+        /// A document value has been `Swapped` replacing it with the new value provided
+        /// Especially useful for Key/Value stores
+        Swapped = 46,
+        /// An Document has been deleted
+        Deleted = 47,
+        /// Key Found in KV Store/Database, Field or Document depending on query
+        KeyFound = 48,
+        /// Key Not Found in Database, Field or Document depending on query
+        KeyNotFound = 49,
+        /// A Command or Query Has Not Been Executed
+        NotExecuted = 50,
+        /// Two or more queries are not supposed to be used command eg. using `get` and `list` together
+        QueryConflictError = 51,
+        /// Database is empty
+        Empty = 52,
+        /// The command has been added to a queue
+        Queued = 53,
+        /// Command has been removed from queue
+        DeQueued = 54,
+        /// Log compaction successful
+        CompactionTrue = 55,
+        /// Log compaction unsuccessful
+        CompactionFalse = 56,
+        /// Connection to a database is not available
+        ConnRefused = 57,
+        /// Port is in use
+        PortAddrInUse = 58,
+        /// Driver to the database has an error
+        DriverError = 59,
+        /// An error occured when trying to execute a command at database level
+        RuntimeError = 60,
+        /// Encountered Errors When Trying to connect to a database
+        #[cfg(feature = "alloc")]
+        EncounteredErrors(String) = 61,
+        /// No permissions to complete the I/O operation
+        PermissionDenied = 62,
+        /// A cluster in online and ready to receive commands
+        ClusterOnline = 63,
+        /// A cluster is offline
+        ClusterOffline = 64,
+        /// Cluster details synced across all nodes
+        ClusterSynced = 65,
+        /// Added a node to the cluster
+        ClusterNodeAdded = 66,
+        /// Updated configuration to the cluster
+        ConfigUpdated = 67,
+        /// A node has been permanently removed
+        ClusterNodeDropped = 68,
+        /// The details have been inserted to the specified logs
+        Logged = 69,
+        /// The details have not been logged
+        NotLogged = 70,
+        /// The Operation was interrupted and can be resumed
+        Interrupted = 71,
+        /// Status of an operation is not initialized
+        Unspecified = 72,
+    }
+    failures [
+        RepoAlreadyExists, RepoNotFound, DbIntegrityCorrupted, DocumentIntegrityCorrupted,
+        DbAlreadyExists, DbNotFound, DocumentAlreadyExists, DocumentNotFound, FieldAlreadyExists,
+        FieldNotFound, UnCommitted, Unchanged, Skipped, AlreadyExists, KeyNotFound, NotExecuted,
+        QueryConflictError, CompactionFalse, ConnRefused, PortAddrInUse, DriverError, RuntimeError,
+        EncounteredErrors, PermissionDenied, ClusterOffline, NotLogged, Interrupted, Unspecified,
+    ]
+    severities [
+        DbIntegrityCorrupted => Critical, DocumentIntegrityCorrupted => Critical, ClusterOffline => Critical,
+        RepoAlreadyExists => Warning, RepoNotFound => Warning, DbAlreadyExists => Warning,
+        DbNotFound => Warning, DocumentAlreadyExists => Warning, DocumentNotFound => Warning,
+        FieldAlreadyExists => Warning, FieldNotFound => Warning, AlreadyExists => Warning,
+        KeyNotFound => Warning, Interrupted => Warning, Unchanged => Notice, Skipped => Notice,
+    ]
+    retries [
+        QueryConflictError => WithBackoff, ConnRefused => AfterReconnect, ClusterOffline => WithBackoff,
+        Interrupted => Immediately,
+    ]
+    http [
+        RepoAlreadyExists => 409, RepoNotFound => 404, DbAlreadyExists => 409, DbNotFound => 404,
+        DocumentAlreadyExists => 409, DocumentNotFound => 404, FieldAlreadyExists => 409, FieldNotFound => 404,
+        AlreadyExists => 409, KeyNotFound => 404, QueryConflictError => 409, ConnRefused => 503,
+        PermissionDenied => 403, ClusterOffline => 503,
+    ]
+    grpc [
+        QueryConflictError => Aborted, DbIntegrityCorrupted => DataLoss, DocumentIntegrityCorrupted => DataLoss,
+    ]
 }
 
-/// Command Operations for execution
-/// ### Examples
-/// ```
-/// # use custom_codes::ExecCommand;
-/// let foo = ExecCommand::Queued;
-/// assert_eq!(foo, ExecCommand::Queued);
-/// ```
-#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
-pub enum ExecCommand {
-    /// Command Operation added to Queue
-    Queued,
-    /// Command removed from queue
-    DeQueued,
-    /// Command in progress
-    InProgress,
-    /// Command paused
-    Paused,
-    /// Command suspended but not killed
-    Frozen,
-    /// Command killed and removed from execution stack
-    Killed,
-    /// Command has finished execution
-    Executed,
-    /// The Operation was interrupted and can be resumed
-    Interrupted,
-    /// Status of an operation is not initialized
-    Unspecified,
+define_codes! {
+    /// Command Operations for execution
+    /// ### Examples
+    /// ```
+    /// # use custom_codes::ExecCommand;
+    /// let foo = ExecCommand::Queued;
+    /// assert_eq!(foo, ExecCommand::Queued);
+    /// ```
+    #[derive(Debug, PartialEq, Eq, Clone)]
+    #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+    pub enum ExecCommand = 5 {
+        /// Command Operation added to Queue
+        Queued = 1,
+        /// Command removed from queue
+        DeQueued = 2,
+        /// Command in progress
+        InProgress = 3,
+        /// Command paused
+        Paused = 4,
+        /// Command suspended but not killed
+        Frozen = 5,
+        /// Command killed and removed from execution stack
+        Killed = 6,
+        /// Command has finished execution
+        Executed = 7,
+        /// The Operation was interrupted and can be resumed
+        Interrupted = 8,
+        /// Status of an operation is not initialized
+        Unspecified = 9,
+    }
+    failures [
+        Killed, Interrupted, Unspecified,
+    ]
+    severities [
+        Interrupted => Warning,
+    ]
+    retries [
+        Interrupted => Immediately,
+    ]
 }
 
-/// File operations
-/// ### Examples
-/// ```
-/// # use custom_codes::FileOps;
-/// let foo = FileOps::DirAccessDenied;
-/// assert_eq!(foo, FileOps::DirAccessDenied);
-/// ```
-#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
-pub enum FileOps {
-    /// Directory Created
-    DirCreated,
-    /// Directory Access Denied
-    DirAccessDenied,
-    /// Directory is read only
-    DirIsReadOnly,
-    /// Directory is append only
-    DirectoryAppendOnly,
-    /// File is read only
-    FileIsReadOnly,
-    /// File is append only
-    FileIsAppendOnly,
-    /// Delete operation denied due to permissions
-    DeletionDenied,
-    /// Deletion could not be done as another operation is reading the file
-    DeletionToBeDone,
-    /// Read has been completed especially useful in `async` operations
-    ReadDone,
-    /// Write operation has been competed
-    WriteDone,
-    /// The file or directory already exists
-    AlreadyExists,
-    /// Update operation completed
-    UpdateDone,
-    /// Deletion operation completed
-    DeletionDone,
-    /// Read is in progress
-    ReadInProgress,
-    /// Write is in progress
-    WriteInProgess,
-    /// Update is in progress
-    UpdateInProgress,
-    /// File has been deleted
-    DeletionInProgress,
-    /// Directory has been opened
-    DirOpened,
-    /// Directory is no longer open
-    DirClosed,
-    /// Directory has been modified
-    DirModified,
-    /// File has been opened
-    OpenedFile,
-    /// File has been closed
-    ClosedFile,
-    /// Unable To Open File
-    OpenError,
-    /// The operation comleted with a given error
-    EncounteredErrors(String),
-    /// Creation of the file is denied
-    CreateDenied,
-    /// Read acess to file is denied
-    ReadDenied,
-    /// Update to the file is denied
-    UpdateDenied,
-    /// Writing to the file has been denied
-    WriteDenied,
-    /// Appending to the file has been denied
-    AppendDenied,
-    /// Metadata has been added to file or directory
-    MetadataAdded,
-    /// File or directory does not contain Metadata
-    MetadataNotAvailable,
-    /// metadata changed
-    MetadataChanged,
-    /// Netadata has been Deleted
-    MetadataDeleted,
-    /// Open a file. any open can read contents
-    OpenTrue,
-    /// Do not open file
-    OpenFalse,
-    /// Create a file if it does not exist
-    CreateTrue,
-    /// Do Not Create a file it does not exist
-    CreateFalse,
-    /// Write to a file
-    WriteTrue,
-    /// Do not write to file
-    WriteFalse,
-    /// Append to a file
-    AppendTrue,
-    /// The Operation was interrupted and can be resumed
-    Interrupted,
-    /// Delete a file if it exists,
-    DeleteTrue,
+define_codes! {
+    /// File operations
+    /// ### Examples
+    /// ```
+    /// # use custom_codes::FileOps;
+    /// let foo = FileOps::DirAccessDenied;
+    /// assert_eq!(foo, FileOps::DirAccessDenied);
+    /// ```
+    #[derive(Debug, PartialEq, Eq, Clone)]
+    #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+    pub enum FileOps = 6 {
+        /// Directory Created
+        DirCreated = 1,
+        /// Directory Access Denied
+        DirAccessDenied = 2,
+        /// Directory is read only
+        DirIsReadOnly = 3,
+        /// Directory is append only
+        DirectoryAppendOnly = 4,
+        /// File is read only
+        FileIsReadOnly = 5,
+        /// File is append only
+        FileIsAppendOnly = 6,
+        /// Delete operation denied due to permissions
+        DeletionDenied = 7,
+        /// Deletion could not be done as another operation is reading the file
+        DeletionToBeDone = 8,
+        /// Read has been completed especially useful in `async` operations
+        ReadDone = 9,
+        /// Write operation has been competed
+        WriteDone = 10,
+        /// The file or directory already exists
+        AlreadyExists = 11,
+        /// Update operation completed
+        UpdateDone = 12,
+        /// Deletion operation completed
+        DeletionDone = 13,
+        /// Read is in progress
+        ReadInProgress = 14,
+        /// Misspelled, use `FileOps::WriteInProgress` instead
+        #[deprecated(note = "misspelled, use `FileOps::WriteInProgress` instead")]
+        WriteInProgess => WriteInProgress,
+        /// Write is in progress
+        #[cfg_attr(feature = "serde", serde(alias = "WriteInProgess"))]
+        #[cfg_attr(
+            all(feature = "serde", feature = "legacy-names"),
+            serde(rename(serialize = "WriteInProgess"))
+        )]
+        WriteInProgress = 15,
+        /// Update is in progress
+        UpdateInProgress = 16,
+        /// File has been deleted
+        DeletionInProgress = 17,
+        /// Directory has been opened
+        DirOpened = 18,
+        /// Directory is no longer open
+        DirClosed = 19,
+        /// Directory has been modified
+        DirModified = 20,
+        /// File has been opened
+        OpenedFile = 21,
+        /// File has been closed
+        ClosedFile = 22,
+        /// Unable To Open File
+        OpenError = 23,
+        /// The operation comleted with a given error
+        #[cfg(feature = "alloc")]
+        EncounteredErrors(String) = 24,
+        /// Creation of the file is denied
+        CreateDenied = 25,
+        /// Read acess to file is denied
+        ReadDenied = 26,
+        /// Update to the file is denied
+        UpdateDenied = 27,
+        /// Writing to the file has been denied
+        WriteDenied = 28,
+        /// Appending to the file has been denied
+        AppendDenied = 29,
+        /// Metadata has been added to file or directory
+        MetadataAdded = 30,
+        /// File or directory does not contain Metadata
+        MetadataNotAvailable = 31,
+        /// metadata changed
+        MetadataChanged = 32,
+        /// Netadata has been Deleted
+        MetadataDeleted = 33,
+        /// Open a file. any open can read contents
+        OpenTrue = 34,
+        /// Do not open file
+        OpenFalse = 35,
+        /// Create a file if it does not exist
+        CreateTrue = 36,
+        /// Do Not Create a file it does not exist
+        CreateFalse = 37,
+        /// Write to a file
+        WriteTrue = 38,
+        /// Do not write to file
+        WriteFalse = 39,
+        /// Append to a file
+        AppendTrue = 40,
+        /// The Operation was interrupted and can be resumed
+        Interrupted = 41,
+        /// Delete a file if it exists,
+        DeleteTrue = 42,
+    }
+    failures [
+        DirAccessDenied, DeletionDenied, AlreadyExists, OpenError, EncounteredErrors, CreateDenied,
+        ReadDenied, UpdateDenied, WriteDenied, AppendDenied, MetadataNotAvailable, Interrupted,
+    ]
+    severities [
+        AlreadyExists => Warning, Interrupted => Warning,
+    ]
+    retries [
+        Interrupted => Immediately,
+    ]
+    http [
+        DirAccessDenied => 403, DeletionDenied => 403, AlreadyExists => 409, CreateDenied => 403,
+        ReadDenied => 403, UpdateDenied => 403, WriteDenied => 403, AppendDenied => 403,
+    ]
 }
 
-/// Compression of files, bytes or directories
-/// ### Examples
-/// ```
-/// # use custom_codes::Compression;
-/// let foo = Compression::Done;
-/// assert_eq!(foo, Compression::Done);
-/// ```
-#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
-pub enum Compression {
-    /// Compression done
-    Done,
-    /// Compression in progress
-    InProgress,
-    /// Compression already done
-    AlreadyCompressed,
-    /// Compression is not possible
-    Impossible,
-    /// A process is writing to the file being compressed
-    ToBeDone,
-    /// Streaming and compressing at the same time
-    StreamCompress,
-    /// The Operation was interrupted and can be resumed
-    Interrupted,
-    /// Status of an operation is not initialized
-    Unspecified,
+define_codes! {
+    /// Compression of files, bytes or directories
+    /// ### Examples
+    /// ```
+    /// # use custom_codes::Compression;
+    /// let foo = Compression::Done;
+    /// assert_eq!(foo, Compression::Done);
+    /// ```
+    #[derive(Debug, PartialEq, Eq, Clone)]
+    #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+    pub enum Compression = 7 {
+        /// Compression done
+        Done = 1,
+        /// Compression in progress
+        InProgress = 2,
+        /// Compression already done
+        AlreadyCompressed = 3,
+        /// Compression is not possible
+        Impossible = 4,
+        /// A process is writing to the file being compressed
+        ToBeDone = 5,
+        /// Streaming and compressing at the same time
+        StreamCompress = 6,
+        /// The Operation was interrupted and can be resumed
+        Interrupted = 7,
+        /// Status of an operation is not initialized
+        Unspecified = 8,
+    }
+    failures [
+        Impossible, Interrupted, Unspecified,
+    ]
+    retries [
+        Interrupted => Immediately,
+    ]
 }
 
-/// Status of a subscription that happens over a timespec
-/// ### Examples
-/// ```
-/// # use custom_codes::Subscription;
-/// let foo = Subscription::Subscribed;
-/// assert_eq!(foo, Subscription::Subscribed);
-/// ```
-#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
-pub enum Subscription {
-    /// Subscription is active
-    Subscribed,
-    /// Subscription is inactive
-    Unsubscribed,
-    /// Subscription is not yet activated
-    NotActivated,
-    /// Status of an operation is not initialized
-    Unspecified,
+define_codes! {
+    /// Status of a subscription that happens over a timespec
+    /// ### Examples
+    /// ```
+    /// # use custom_codes::Subscription;
+    /// let foo = Subscription::Subscribed;
+    /// assert_eq!(foo, Subscription::Subscribed);
+    /// ```
+    #[derive(Debug, PartialEq, Eq, Clone)]
+    #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+    pub enum Subscription = 8 {
+        /// Subscription is active
+        Subscribed = 1,
+        /// Subscription is inactive
+        Unsubscribed = 2,
+        /// Subscription is not yet activated
+        NotActivated = 3,
+        /// Status of an operation is not initialized
+        Unspecified = 4,
+    }
+    failures [
+        Unspecified,
+    ]
 }
 
-/// Status of an activity
-/// ### Examples
-/// ```
-/// # use custom_codes::ActivityStatus;
-/// let foo = ActivityStatus::Activated;
-/// assert_eq!(foo, ActivityStatus::Activated);
-/// ```
-#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
-pub enum ActivityStatus {
-    /// Status of an operation has been started
-    Activated,
-    /// Status of an operation has been stopped
-    Deactivated,
-    /// Status of an operation is not initialized
-    Unspecified,
+define_codes! {
+    /// Status of an activity
+    /// ### Examples
+    /// ```
+    /// # use custom_codes::ActivityStatus;
+    /// let foo = ActivityStatus::Activated;
+    /// assert_eq!(foo, ActivityStatus::Activated);
+    /// ```
+    #[derive(Debug, PartialEq, Eq, Clone)]
+    #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+    pub enum ActivityStatus = 9 {
+        /// Status of an operation has been started
+        Activated = 1,
+        /// Status of an operation has been stopped
+        Deactivated = 2,
+        /// Status of an operation is not initialized
+        Unspecified = 3,
+    }
+    failures [
+        Unspecified,
+    ]
 }
-/// Initialize an activity
-/// ### Examples
-/// ```
-/// # use custom_codes::ActivityToggle;
-/// let foo = ActivityToggle::Activate;
-/// assert_eq!(foo, ActivityToggle::Activate);
-/// ```
-#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
-pub enum ActivityToggle {
-    /// Activate an operation
-    Activate,
-    /// Deactivate an operation
-    Deactivate,
-    /// Status of an operation is not initialized
-    Unspecified,
+define_codes! {
+    /// Initialize an activity
+    /// ### Examples
+    /// ```
+    /// # use custom_codes::ActivityToggle;
+    /// let foo = ActivityToggle::Activate;
+    /// assert_eq!(foo, ActivityToggle::Activate);
+    /// ```
+    #[derive(Debug, PartialEq, Eq, Clone)]
+    #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+    pub enum ActivityToggle = 10 {
+        /// Activate an operation
+        Activate = 1,
+        /// Deactivate an operation
+        Deactivate = 2,
+        /// Status of an operation is not initialized
+        Unspecified = 3,
+    }
+    failures [
+        Unspecified,
+    ]
 }
-/// Command Line Options
-/// ### Examples
-/// ```
-/// # use custom_codes::Cli;
-/// let foo = Cli::ParseInProgress;
-/// assert_eq!(foo, Cli::ParseInProgress);
-/// ```
-#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
-pub enum Cli {
-    /// Parsing
-    ParseInProgress,
-    /// Parsed Arguments
-    ParseArgs,
-    /// Parsing Argument field in progress
-    ArgFieldParseInPorgress,
-    /// Parsed Argument field
-    ParsedArgField,
-    /// Parsing Asynchronously
-    ParseAsync,
-    /// Parsing field Asynchronously
-    ArgFieldAsyncParseInPorgress,
-    /// Not enough commandline arguments
-    NotEnoughArgs,
-    /// Encountered parsing errors
-    EncounteredErrors(String),
+define_codes! {
+    /// Command Line Options
+    /// ### Examples
+    /// ```
+    /// # use custom_codes::Cli;
+    /// let foo = Cli::ParseInProgress;
+    /// assert_eq!(foo, Cli::ParseInProgress);
+    /// ```
+    #[derive(Debug, PartialEq, Eq, Clone)]
+    #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+    pub enum Cli = 11 {
+        /// Parsing
+        ParseInProgress = 1,
+        /// Parsed Arguments
+        ParseArgs = 2,
+        /// Misspelled, use `Cli::ArgFieldParseInProgress` instead
+        #[deprecated(note = "misspelled, use `Cli::ArgFieldParseInProgress` instead")]
+        ArgFieldParseInPorgress => ArgFieldParseInProgress,
+        /// Parsing Argument field in progress
+        #[cfg_attr(feature = "serde", serde(alias = "ArgFieldParseInPorgress"))]
+        #[cfg_attr(
+            all(feature = "serde", feature = "legacy-names"),
+            serde(rename(serialize = "ArgFieldParseInPorgress"))
+        )]
+        ArgFieldParseInProgress = 3,
+        /// Parsed Argument field
+        ParsedArgField = 4,
+        /// Parsing Asynchronously
+        ParseAsync = 5,
+        /// Misspelled, use `Cli::ArgFieldAsyncParseInProgress` instead
+        #[deprecated(note = "misspelled, use `Cli::ArgFieldAsyncParseInProgress` instead")]
+        ArgFieldAsyncParseInPorgress => ArgFieldAsyncParseInProgress,
+        /// Parsing field Asynchronously
+        #[cfg_attr(feature = "serde", serde(alias = "ArgFieldAsyncParseInPorgress"))]
+        #[cfg_attr(
+            all(feature = "serde", feature = "legacy-names"),
+            serde(rename(serialize = "ArgFieldAsyncParseInPorgress"))
+        )]
+        ArgFieldAsyncParseInProgress = 6,
+        /// Not enough commandline arguments
+        NotEnoughArgs = 7,
+        /// Encountered parsing errors
+        #[cfg(feature = "alloc")]
+        EncounteredErrors(String) = 8,
+    }
+    failures [
+        NotEnoughArgs, EncounteredErrors,
+    ]
+    severities [
+        NotEnoughArgs => Warning,
+    ]
+    http [
+        NotEnoughArgs => 400,
+    ]
 }
-/// Date and time custom codes
-/// ### Examples
-/// ```
-/// # use custom_codes::DateTimeOp;
-/// let foo = DateTimeOp::DateCreated;
-/// assert_eq!(foo, DateTimeOp::DateCreated);
-/// ```
-#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
-pub enum DateTimeOp {
-    /// Date created
-    DateCreated,
-    /// Date deleted
-    DateDeleted,
-    /// Date Modified
-    DateModified,
-    /// Date period not specified
-    DateUnspecified,
-    /// Date created
-    TimeCreated,
-    /// Date deleted
-    TimeDeleted,
-    /// Date Modified
-    TimeModified,
-    /// Time period not specified
-    TimeUnspecified,
-    /// Both date and time not specied
-    DateTimeUnspecified,
+define_codes! {
+    /// Date and time custom codes
+    /// ### Examples
+    /// ```
+    /// # use custom_codes::DateTimeOp;
+    /// let foo = DateTimeOp::DateCreated;
+    /// assert_eq!(foo, DateTimeOp::DateCreated);
+    /// ```
+    #[derive(Debug, PartialEq, Eq, Clone)]
+    #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+    pub enum DateTimeOp = 12 {
+        /// Date created
+        DateCreated = 1,
+        /// Date deleted
+        DateDeleted = 2,
+        /// Date Modified
+        DateModified = 3,
+        /// Date period not specified
+        DateUnspecified = 4,
+        /// Date created
+        TimeCreated = 5,
+        /// Date deleted
+        TimeDeleted = 6,
+        /// Date Modified
+        TimeModified = 7,
+        /// Time period not specified
+        TimeUnspecified = 8,
+        /// Both date and time not specied
+        DateTimeUnspecified = 9,
+    }
+    failures [
+        DateUnspecified, TimeUnspecified, DateTimeUnspecified,
+    ]
 }
 
-/// Security operations on encryption and keys
-/// ### Examples
-/// ```
-/// # use custom_codes::SecOps;
-/// let foo = SecOps::KeyCorrupted;
-/// assert_eq!(foo, SecOps::KeyCorrupted);
-/// ```
-#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
-pub enum SecOps {
-    /// Key could not be verified as it did not finish streaming
-    KeyCorrupted,
-    /// Key Authentic and approved
-    KeyAuthentic,
-    /// Key has been tampered with
-    KeyInvalid,
-    /// Timed Out while verification was in progress
-    TimedOut,
-    /// Possible Identity Forgery
-    PossibleIdTheft,
-    /// Data encrypted has been corrupted
-    DataCorrupted,
-    /// Data has been tampered with
-    DataInvalid,
-    /// Data has been encrypted
-    Encrypted,
-    /// Key generation successful,
-    KeyGenSucceded,
-    /// Key generation failed
-    KeyGenFailed,
-    /// Key has been deleted
-    KeyDeleted,
-    /// random data generated from Cryptographically Secure PRNG (CSPRNG)
-    CryptoRandomGenerated,
-    /// random data generated from Cryptographically Secure PRNG (CSPRNG) was not generated successfully
-    CryptoRandomGenFailure,
-    /// random data generated from Noncryptographic PRNG
-    NonCryptoRandomGenerated,
-    /// random data generated from Noncryptographic PRNG was not generated successfully
-    NonCryptoRandomGenFailure,
-    /// Message Authentication Code is authentic
-    AuthenticMAC,
-    /// Message Authentication Code is corrupted
-    InvalidMAC,
-    /// Random Authentication Code (RAC) Token is genuine/authentic
-    ValidRAC,
-    ///  Random Authentication Code (RAC) Token is not genuine/authentic
-    InvalidRAC,
-    /// Key Length is equal to the length needed by the cryptography algorithm
-    KeyLengthSane,
-    /// Key length too short
-    KeyTooShort(KeyLength),
-    /// Key Length is too long
-    KeyTooLong(KeyLength),
+define_codes! {
+    /// Security operations on encryption and keys
+    /// ### Examples
+    /// ```
+    /// # use custom_codes::SecOps;
+    /// let foo = SecOps::KeyCorrupted;
+    /// assert_eq!(foo, SecOps::KeyCorrupted);
+    /// ```
+    #[derive(Debug, PartialEq, Eq, Clone)]
+    #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+    pub enum SecOps = 13 {
+        /// Key could not be verified as it did not finish streaming
+        KeyCorrupted = 1,
+        /// Key Authentic and approved
+        KeyAuthentic = 2,
+        /// Key has been tampered with
+        KeyInvalid = 3,
+        /// Timed Out while verification was in progress
+        TimedOut = 4,
+        /// Possible Identity Forgery
+        PossibleIdTheft = 5,
+        /// Data encrypted has been corrupted
+        DataCorrupted = 6,
+        /// Data has been tampered with
+        DataInvalid = 7,
+        /// Data has been encrypted
+        Encrypted = 8,
+        /// Misspelled, use `SecOps::KeyGenSucceeded` instead
+        #[deprecated(note = "misspelled, use `SecOps::KeyGenSucceeded` instead")]
+        KeyGenSucceded => KeyGenSucceeded,
+        /// Key generation successful,
+        #[cfg_attr(feature = "serde", serde(alias = "KeyGenSucceded"))]
+        #[cfg_attr(
+            all(feature = "serde", feature = "legacy-names"),
+            serde(rename(serialize = "KeyGenSucceded"))
+        )]
+        KeyGenSucceeded = 9,
+        /// Key generation failed
+        KeyGenFailed = 10,
+        /// Key has been deleted
+        KeyDeleted = 11,
+        /// random data generated from Cryptographically Secure PRNG (CSPRNG)
+        CryptoRandomGenerated = 12,
+        /// random data generated from Cryptographically Secure PRNG (CSPRNG) was not generated successfully
+        CryptoRandomGenFailure = 13,
+        /// random data generated from Noncryptographic PRNG
+        NonCryptoRandomGenerated = 14,
+        /// random data generated from Noncryptographic PRNG was not generated successfully
+        NonCryptoRandomGenFailure = 15,
+        /// Message Authentication Code is authentic
+        AuthenticMAC = 16,
+        /// Message Authentication Code is corrupted
+        InvalidMAC = 17,
+        /// Random Authentication Code (RAC) Token is genuine/authentic
+        ValidRAC = 18,
+        ///  Random Authentication Code (RAC) Token is not genuine/authentic
+        InvalidRAC = 19,
+        /// Key Length is equal to the length needed by the cryptography algorithm
+        KeyLengthSane = 20,
+        /// Key length too short
+        KeyTooShort(KeyLength) = 21,
+        /// Key Length is too long
+        KeyTooLong(KeyLength) = 22,
+    }
+    failures [
+        KeyCorrupted, KeyInvalid, TimedOut, PossibleIdTheft, DataCorrupted, DataInvalid,
+        KeyGenFailed, CryptoRandomGenFailure, NonCryptoRandomGenFailure, InvalidMAC, InvalidRAC,
+        KeyTooShort, KeyTooLong,
+    ]
+    severities [
+        PossibleIdTheft => Alert, KeyCorrupted => Critical, DataCorrupted => Critical,
+        InvalidMAC => Warning, InvalidRAC => Warning,
+    ]
+    retries [
+        TimedOut => WithBackoff, CryptoRandomGenFailure => Immediately, NonCryptoRandomGenFailure => Immediately,
+    ]
+    http [
+        KeyInvalid => 400, TimedOut => 504, DataInvalid => 400, InvalidMAC => 400, InvalidRAC => 400,
+        KeyTooShort => 400, KeyTooLong => 400,
+    ]
+    grpc [
+        DataCorrupted => DataLoss,
+    ]
+    custom_impls [Display, Error]
 }
 
-impl std::fmt::Display for SecOps {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+#[allow(deprecated)]
+impl core::fmt::Display for SecOps {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         match self {
             SecOps::KeyCorrupted => write!(f, "Key could not be verified as it did not finish streaming"),
             SecOps::KeyAuthentic => write!(f, "Key Authentic and approved"),
@@ -556,7 +783,7 @@ impl std::fmt::Display for SecOps {
             SecOps::DataCorrupted => write!(f, "Data encrypted has been corrupted"),
             SecOps::DataInvalid => write!(f, "Data has been tampered with"),
             SecOps::Encrypted => write!(f, "Data has been encrypted"),
-            SecOps::KeyGenSucceded => write!(f, "Key generation successful"),
+            SecOps::KeyGenSucceeded => write!(f, "Key generation successful"),
             SecOps::KeyGenFailed => write!(f, "Key generation failed"),
             SecOps::KeyDeleted => write!(f, "Key has been deleted"),
             SecOps::CryptoRandomGenerated => write!(f, "random data generated from Cryptographically Secure PRNG (CSPRNG)"),
@@ -574,6 +801,8 @@ impl std::fmt::Display for SecOps {
     }
 }
 
+#[cfg(feature = "std")]
+#[allow(deprecated)]
 impl std::error::Error for SecOps {
     fn description(&self) -> &str {
         match self {
@@ -585,7 +814,7 @@ impl std::error::Error for SecOps {
             SecOps::DataCorrupted => "Data encrypted has been corrupted",
             SecOps::DataInvalid => "Data has been tampered with",
             SecOps::Encrypted => "Data has been encrypted",
-            SecOps::KeyGenSucceded => "Key generation successful",
+            SecOps::KeyGenSucceeded => "Key generation successful",
             SecOps::KeyGenFailed => "Key generation failed",
             SecOps::KeyDeleted => "Key has been deleted",
             SecOps::CryptoRandomGenerated => "random data generated from Cryptographically Secure PRNG (CSPRNG)",
@@ -612,7 +841,7 @@ impl std::error::Error for SecOps {
             SecOps::DataCorrupted => Some(&SecOps::DataCorrupted),
             SecOps::DataInvalid => Some(&SecOps::DataInvalid),
             SecOps::Encrypted => Some(&SecOps::Encrypted),
-            SecOps::KeyGenSucceded => Some(&SecOps::KeyGenSucceded),
+            SecOps::KeyGenSucceeded => Some(&SecOps::KeyGenSucceeded),
             SecOps::KeyGenFailed => Some(&SecOps::KeyGenFailed),
             SecOps::KeyDeleted => Some(&SecOps::KeyDeleted),
             SecOps::CryptoRandomGenerated => Some(&SecOps::CryptoRandomGenerated),
@@ -630,41 +859,45 @@ impl std::error::Error for SecOps {
     }
 }
 
-/// The length of a key in bytes needed by a cryptography algorithm
-/// ### Examples
-/// ```
-/// # use custom_codes::KeyLength;
-/// let foo = KeyLength::Bytes32;
-/// assert_eq!(foo, KeyLength::Bytes32);
-/// ```
-#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
-pub enum KeyLength {
-    /// A Key length of 8 bytes
-    Bytes8,
-    /// A Key length of 16 bytes
-    Bytes16,
-    /// A Key length of 24 bytes
-    Bytes24,
-    /// A Key length of 32 bytes
-    Bytes32,
-    /// A Key length of 64 bytes
-    Bytes64,
-    /// A Key length of 128 bytes
-    Bytes128,
-    /// A Key length of 256 bytes
-    Bytes256,
-    /// A Key length of 512 bytes
-    Bytes512,
-    /// A Key length of 1024 bytes
-    Bytes1024,
-    /// A Key length of 2048 bytes
-    Bytes2048,
-    /// A Key length of 4096 bytes
-    Bytes4096,
+define_codes! {
+    /// The length of a key in bytes needed by a cryptography algorithm
+    /// ### Examples
+    /// ```
+    /// # use custom_codes::KeyLength;
+    /// let foo = KeyLength::Bytes32;
+    /// assert_eq!(foo, KeyLength::Bytes32);
+    /// ```
+    #[derive(Debug, PartialEq, Eq, Clone)]
+    #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+    pub enum KeyLength = 14 {
+        /// A Key length of 8 bytes
+        Bytes8 = 1,
+        /// A Key length of 16 bytes
+        Bytes16 = 2,
+        /// A Key length of 24 bytes
+        Bytes24 = 3,
+        /// A Key length of 32 bytes
+        Bytes32 = 4,
+        /// A Key length of 64 bytes
+        Bytes64 = 5,
+        /// A Key length of 128 bytes
+        Bytes128 = 6,
+        /// A Key length of 256 bytes
+        Bytes256 = 7,
+        /// A Key length of 512 bytes
+        Bytes512 = 8,
+        /// A Key length of 1024 bytes
+        Bytes1024 = 9,
+        /// A Key length of 2048 bytes
+        Bytes2048 = 10,
+        /// A Key length of 4096 bytes
+        Bytes4096 = 11,
+    }
+    custom_impls [Display, Error]
 }
 
-impl std::fmt::Display for KeyLength {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl core::fmt::Display for KeyLength {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         match self {
             KeyLength::Bytes8 => write!(f, "KeyLength::Bytes8"),
             KeyLength::Bytes16 => write!(f, "KeyLength::Bytes16"),
@@ -681,6 +914,7 @@ impl std::fmt::Display for KeyLength {
     }
 }
 
+#[cfg(feature = "std")]
 impl std::error::Error for KeyLength {
     fn description(&self) -> &str {
         match self {
@@ -715,249 +949,310 @@ impl std::error::Error for KeyLength {
     }
 }
 
-/// Hardware Resources of a physical computer
-/// ### Examples
-/// ```
-/// # use custom_codes::HardwareResources;
-/// let foo = HardwareResources::CpuMaxed;
-/// assert_eq!(foo, HardwareResources::CpuMaxed);
-/// ```
-#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
-pub enum HardwareResources {
-    /// CPU is under heavy load
-    CpuUnderHeavyLoad,
-    /// RAM is under heavy load
-    RamUnderHeavyLoad,
-    /// CPU is maxed out
-    CpuMaxed,
-    /// CPU is Overclocked
-    CpuOverclocked,
-    /// CPU is underclocked eg. to save power
-    CpuUnderclocked,
-    /// Cpu is idle
-    CpuIdle,
-    /// CPU heat is normal
-    CpuTempOk,
-    /// CPU heat is high
-    CpuThrottling,
-    /// Etherned Card Available for use
-    EthernetDevUp,
-    ///Ethernet is unavailavle for use
-    EthernetDevDown,
-    /// Ethernet card accessed by processed
-    EthernetDevAccessed,
-    /// Carrier chip available
-    CarrierDevUp,
-    /// Carrier chip unavailable
-    CarrierDevDown,
-    /// Carrier chip does not exist in hardware
-    CarrierDevChipMissing,
-    ///Drivers are missing for components listed
-    DriverMissing,
-    ///Camera or optical module available
-    OpticDevUp,
-    ///Camera or optical module unavailable
-    OpticDevDown,
-    ///Camera or optical module accessed
-    OpticalDevAccessed,
-    ///Camera or optical module access is denied
-    OpticalDevAccessDenied,
-    /// Camera or optical module powered off
-    OpticalDevPoweredOff,
-    /// Optical chip does not exist in hardware
-    OticalDevChipMissing,
-    /// NFC device is available
-    NfcDevUp,
-    /// NFC device is unavailable
-    NfcDevDown,
-    /// NFC device is active
-    NfcDevActive,
-    /// NFC device is paired to other device
-    NfcDevPaired,
-    /// NFC device is accessed
-    NfcDevAccessed,
-    /// NFC device access is denied
-    NfcDevDenied,
-    /// NFC device is closed
-    NfcDevClosed,
-    /// NFC device is powered of
-    NfcDevPoweredOff,
-    /// WiFi module avaliable
-    WiFiDevUp,
-    /// WiFi module unavaliable
-    WiFiDevDown,
-    /// WiFi module access is denied,
-    WiFiDevAccessDenied,
-    /// WiFi module powered off
-    WiFiDevPoweredOff,
-    /// WiFi has been accessed by process
-    WiFiDevAccessed,
-    /// WiFi is in hotspot mode
-    WiFiDevHotSpotMode,
-    /// Bluetooth is available
-    BluetoothDevUp,
-    /// Bluetooth is paired
-    BluetoothDevPaired,
-    /// Bluetooth device is trusted
-    BluetoothDevDevTrusted,
-    /// Bluetooth has been untrusted
-    BluetoothDevDevUntrusted,
-    /// Bluetooth access is denied
-    BluetoothDevAccessDenied,
-    /// Bluetooth device has been unpaired
-    BluetoothDevUnpaired,
-    /// Bluetooth is in share mode for data
-    BluetoothDevShareMode,
-    /// Bluetooth is in audio or data sink
-    BluetoothDevSinkMode,
-    /// Bluetooth is transmitting
-    BluetoothDevTxMode,
-    /// Bluetooth is Receiving
-    BluetoothDevRxMode,
-    /// Bluetooth is closed
-    BluetoothDevClosed,
-    /// Bluetooth is powered off
-    BluetoothDevPowerOff,
-    /// Bluetooth is unavailable
-    BluetoothDevDown,
-    /// USB controller is available
-    UsbDevUp,
-    /// USB controller is available
-    UsbDevDown,
-    /// USB controller is powered off
-    UsbDevPoweredOff,
-    /// USB device has been added
-    UsbDevAdded,
-    /// USB is transmitting
-    UsbDevTxMode,
-    /// USB is recieving
-    UsbDevRxMode,
-    /// USB device has been removed
-    UsbDevRemoved,
-    /// USB device access is denied
-    UsbDevAccessDenied,
-    /// GPS module available
-    GpsDevUp,
-    /// GPS module unavailable
-    GpsDevDown,
-    /// GPS is transmitting
-    GpsDevTxMode,
-    /// GPS module receiving
-    GpsDevRxmode,
-    /// GPS Device access denied
-    GpsDevAccessDenied,
-    /// The device is in airplane mode
-    AirplaneMode,
-    /// Hardware not specified
-    Unspecified,
+define_codes! {
+    /// Hardware Resources of a physical computer
+    /// ### Examples
+    /// ```
+    /// # use custom_codes::HardwareResources;
+    /// let foo = HardwareResources::CpuMaxed;
+    /// assert_eq!(foo, HardwareResources::CpuMaxed);
+    /// ```
+    #[derive(Debug, PartialEq, Eq, Clone)]
+    #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+    pub enum HardwareResources = 15 {
+        /// CPU is under heavy load
+        CpuUnderHeavyLoad = 1,
+        /// RAM is under heavy load
+        RamUnderHeavyLoad = 2,
+        /// CPU is maxed out
+        CpuMaxed = 3,
+        /// CPU is Overclocked
+        CpuOverclocked = 4,
+        /// CPU is underclocked eg. to save power
+        CpuUnderclocked = 5,
+        /// Cpu is idle
+        CpuIdle = 6,
+        /// CPU heat is normal
+        CpuTempOk = 7,
+        /// CPU heat is high
+        CpuThrottling = 8,
+        /// Etherned Card Available for use
+        EthernetDevUp = 9,
+        ///Ethernet is unavailavle for use
+        EthernetDevDown = 10,
+        /// Ethernet card accessed by processed
+        EthernetDevAccessed = 11,
+        /// Carrier chip available
+        CarrierDevUp = 12,
+        /// Carrier chip unavailable
+        CarrierDevDown = 13,
+        /// Carrier chip does not exist in hardware
+        CarrierDevChipMissing = 14,
+        ///Drivers are missing for components listed
+        DriverMissing = 15,
+        ///Camera or optical module available
+        OpticDevUp = 16,
+        ///Camera or optical module unavailable
+        OpticDevDown = 17,
+        ///Camera or optical module accessed
+        OpticalDevAccessed = 18,
+        ///Camera or optical module access is denied
+        OpticalDevAccessDenied = 19,
+        /// Camera or optical module powered off
+        OpticalDevPoweredOff = 20,
+        /// Misspelled, use `HardwareResources::OpticalDevChipMissing` instead
+        #[deprecated(note = "misspelled, use `HardwareResources::OpticalDevChipMissing` instead")]
+        OticalDevChipMissing => OpticalDevChipMissing,
+        /// Optical chip does not exist in hardware
+        #[cfg_attr(feature = "serde", serde(alias = "OticalDevChipMissing"))]
+        #[cfg_attr(
+            all(feature = "serde", feature = "legacy-names"),
+            serde(rename(serialize = "OticalDevChipMissing"))
+        )]
+        OpticalDevChipMissing = 21,
+        /// NFC device is available
+        NfcDevUp = 22,
+        /// NFC device is unavailable
+        NfcDevDown = 23,
+        /// NFC device is active
+        NfcDevActive = 24,
+        /// NFC device is paired to other device
+        NfcDevPaired = 25,
+        /// NFC device is accessed
+        NfcDevAccessed = 26,
+        /// NFC device access is denied
+        NfcDevDenied = 27,
+        /// NFC device is closed
+        NfcDevClosed = 28,
+        /// NFC device is powered of
+        NfcDevPoweredOff = 29,
+        /// WiFi module avaliable
+        WiFiDevUp = 30,
+        /// WiFi module unavaliable
+        WiFiDevDown = 31,
+        /// WiFi module access is denied,
+        WiFiDevAccessDenied = 32,
+        /// WiFi module powered off
+        WiFiDevPoweredOff = 33,
+        /// WiFi has been accessed by process
+        WiFiDevAccessed = 34,
+        /// WiFi is in hotspot mode
+        WiFiDevHotSpotMode = 35,
+        /// Bluetooth is available
+        BluetoothDevUp = 36,
+        /// Bluetooth is paired
+        BluetoothDevPaired = 37,
+        /// Bluetooth device is trusted
+        BluetoothDevDevTrusted = 38,
+        /// Bluetooth has been untrusted
+        BluetoothDevDevUntrusted = 39,
+        /// Bluetooth access is denied
+        BluetoothDevAccessDenied = 40,
+        /// Bluetooth device has been unpaired
+        BluetoothDevUnpaired = 41,
+        /// Bluetooth is in share mode for data
+        BluetoothDevShareMode = 42,
+        /// Bluetooth is in audio or data sink
+        BluetoothDevSinkMode = 43,
+        /// Bluetooth is transmitting
+        BluetoothDevTxMode = 44,
+        /// Bluetooth is Receiving
+        BluetoothDevRxMode = 45,
+        /// Bluetooth is closed
+        BluetoothDevClosed = 46,
+        /// Bluetooth is powered off
+        BluetoothDevPowerOff = 47,
+        /// Bluetooth is unavailable
+        BluetoothDevDown = 48,
+        /// USB controller is available
+        UsbDevUp = 49,
+        /// USB controller is available
+        UsbDevDown = 50,
+        /// USB controller is powered off
+        UsbDevPoweredOff = 51,
+        /// USB device has been added
+        UsbDevAdded = 52,
+        /// USB is transmitting
+        UsbDevTxMode = 53,
+        /// USB is recieving
+        UsbDevRxMode = 54,
+        /// USB device has been removed
+        UsbDevRemoved = 55,
+        /// USB device access is denied
+        UsbDevAccessDenied = 56,
+        /// GPS module available
+        GpsDevUp = 57,
+        /// GPS module unavailable
+        GpsDevDown = 58,
+        /// GPS is transmitting
+        GpsDevTxMode = 59,
+        /// Misspelled, use `HardwareResources::GpsDevRxMode` instead
+        #[deprecated(note = "misspelled, use `HardwareResources::GpsDevRxMode` instead")]
+        GpsDevRxmode => GpsDevRxMode,
+        /// GPS module receiving
+        #[cfg_attr(feature = "serde", serde(alias = "GpsDevRxmode"))]
+        #[cfg_attr(
+            all(feature = "serde", feature = "legacy-names"),
+            serde(rename(serialize = "GpsDevRxmode"))
+        )]
+        GpsDevRxMode = 60,
+        /// GPS Device access denied
+        GpsDevAccessDenied = 61,
+        /// The device is in airplane mode
+        AirplaneMode = 62,
+        /// Hardware not specified
+        Unspecified = 63,
+    }
+    failures [
+        CarrierDevChipMissing, DriverMissing, OpticalDevAccessDenied, OpticalDevChipMissing,
+        NfcDevDenied, WiFiDevAccessDenied, BluetoothDevAccessDenied, UsbDevAccessDenied,
+        GpsDevAccessDenied, Unspecified,
+    ]
+    http [
+        OpticalDevAccessDenied => 403, NfcDevDenied => 403, WiFiDevAccessDenied => 403,
+        BluetoothDevAccessDenied => 403, UsbDevAccessDenied => 403, GpsDevAccessDenied => 403,
+    ]
 }
 
-/// Networking Specific
-/// ### Examples
-/// ```
-/// # use custom_codes::Networking;
-/// let foo = Networking::NetworkAccessDenied;
-/// assert_eq!(foo, Networking::NetworkAccessDenied);
-/// ```
-#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
-pub enum Networking {
-    /// Permission to access network is denied
-    NetworkAccessDenied,
-    /// Network is available but unable to reach destination host
-    HostUnreachable,
-    /// Connection was reset and has been terminated
-    ConnectionReset,
-    /// Connection to the address has been stopped
-    ConnectionAborted,
-    /// The network operation failed because process has not finished connecting
-    NotConnected,
-    /// Headers for a particular protocol corrupted
-    HeadersCorrupted,
-    /// Server access was reached but didnt respond
-    ServerNotResponding,
-    /// IP Address is already in use
-    IpInUse,
-    /// Port is in use but IP is available
-    PortInUse,
-    /// The IP address and Port are both in use
-    AddrInUse,
-    /// IP Address is available
-    IpAvailable,
-    /// Network Operation Failed because a pipe is broken
-    BrokenPipe,
-    /// Connection already exists
-    NetConnExists,
-    /// Operation parameters are Invalid
-    InvalidNetInput,
-    /// Operation parameters are fine but operation data is invalid
-    InvalidNetData,
-    /// The network operation timed out
-    NetTimedOut,
-    /// The Network Operation was interrupted and can be resumed
-    Interrupted,
-    /// Unexpected end of connection
-    UnexpectedNetEof,
-    /// Connection Driver is buggy
-    NetDriverBuggy,
-    /// Network Unspecified
-    Unspecified,
+define_codes! {
+    /// Networking Specific
+    /// ### Examples
+    /// ```
+    /// # use custom_codes::Networking;
+    /// let foo = Networking::NetworkAccessDenied;
+    /// assert_eq!(foo, Networking::NetworkAccessDenied);
+    /// ```
+    #[derive(Debug, PartialEq, Eq, Clone)]
+    #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+    pub enum Networking = 16 {
+        /// Permission to access network is denied
+        NetworkAccessDenied = 1,
+        /// Network is available but unable to reach destination host
+        HostUnreachable = 2,
+        /// Connection was reset and has been terminated
+        ConnectionReset = 3,
+        /// Connection to the address has been stopped
+        ConnectionAborted = 4,
+        /// The network operation failed because process has not finished connecting
+        NotConnected = 5,
+        /// Headers for a particular protocol corrupted
+        HeadersCorrupted = 6,
+        /// Server access was reached but didnt respond
+        ServerNotResponding = 7,
+        /// IP Address is already in use
+        IpInUse = 8,
+        /// Port is in use but IP is available
+        PortInUse = 9,
+        /// The IP address and Port are both in use
+        AddrInUse = 10,
+        /// IP Address is available
+        IpAvailable = 11,
+        /// Network Operation Failed because a pipe is broken
+        BrokenPipe = 12,
+        /// Connection already exists
+        NetConnExists = 13,
+        /// Operation parameters are Invalid
+        InvalidNetInput = 14,
+        /// Operation parameters are fine but operation data is invalid
+        InvalidNetData = 15,
+        /// The network operation timed out
+        NetTimedOut = 16,
+        /// The Network Operation was interrupted and can be resumed
+        Interrupted = 17,
+        /// Unexpected end of connection
+        UnexpectedNetEof = 18,
+        /// Connection Driver is buggy
+        NetDriverBuggy = 19,
+        /// Network Unspecified
+        Unspecified = 20,
+    }
+    failures [
+        NetworkAccessDenied, HostUnreachable, ConnectionReset, ConnectionAborted, NotConnected,
+        HeadersCorrupted, ServerNotResponding, IpInUse, PortInUse, AddrInUse, BrokenPipe,
+        InvalidNetInput, InvalidNetData, NetTimedOut, Interrupted, UnexpectedNetEof, NetDriverBuggy,
+        Unspecified,
+    ]
+    retries [
+        HostUnreachable => WithBackoff, ConnectionReset => AfterReconnect, ConnectionAborted => AfterReconnect,
+        NotConnected => AfterReconnect, ServerNotResponding => WithBackoff, BrokenPipe => AfterReconnect,
+        NetTimedOut => WithBackoff, Interrupted => Immediately, UnexpectedNetEof => AfterReconnect,
+    ]
+    http [
+        NetworkAccessDenied => 403, HostUnreachable => 503, NotConnected => 503, ServerNotResponding => 504,
+        InvalidNetInput => 400, InvalidNetData => 400, NetTimedOut => 504,
+    ]
+    grpc [
+        ConnectionReset => Unavailable, ConnectionAborted => Unavailable, BrokenPipe => Unavailable,
+        UnexpectedNetEof => Unavailable,
+    ]
 }
 
-/// Hardware security access
-/// ### Examples
-/// ```
-/// # use custom_codes::SecHardware;
-/// let foo = SecHardware::UsbKeyUp;
-/// assert_eq!(foo, SecHardware::UsbKeyUp);
-/// ```
-#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
-pub enum SecHardware {
-    /// USB Stick Hardware Key is available
-    UsbKeyUp,
-    /// USB Stick Hardware Key is unavailable
-    UsbKeyDown,
-    /// USB Stick Hardware Key is transmitting,
-    UsbKeyDevTxMode,
-    /// For receiving updates and is not avaiable for use
-    UsbKeyDevRxMode,
-    /// Device access denied
-    UsbKeyDevAccessDenied,
-    /// Fingerprint device is available
-    FingerPrintDevUp,
-    /// Fingerprint device is unavailable
-    FingerPrintDevDown,
-    /// Fingerprint device is powered of
-    FingerPrintDevPoweredOff,
-    /// Fingerprint device is transmitting
-    FingerPrintDevTxMode,
-    /// For receiving updates and is not avaiable for use
-    FingerPrintDevRxMode,
-    /// Fingerprint Device access denied
-    FingerPrintDevAccessDenied,
-    /// Iris scanner is available
-    IrisDevUp,
-    /// Iris scanner is unavailable
-    IrisDevDown,
-    /// Iris scanner is powered off
-    IrisDevPoweredOff,
-    /// Iris scanner is transmitting
-    IrisDevTxMode,
-    /// For receiving updates and is not avaiable for use
-    IrisDevRxMode,
-    /// Iris Scanner Device access denied
-    IrisDevAccessDenied,
-    /// Infra-red device is available
-    IrFloodDevUp,
-    /// Infra-red device is unavailable
-    IrFloodDevDown,
-    /// Infra-red device is powered off
-    IrFloodDevPoweredOff,
-    /// Infra-red device is transmitting
-    IrDevTxMode,
-    /// For receiving updates and is not avaiable for use
-    IrDevRxMode,
-    /// Infra-red Device access denied
-    IrDevAccessDenied,
-    /// Hardware Unspecified
-    Unspecified,
+define_codes! {
+    /// Hardware security access
+    /// ### Examples
+    /// ```
+    /// # use custom_codes::SecHardware;
+    /// let foo = SecHardware::UsbKeyUp;
+    /// assert_eq!(foo, SecHardware::UsbKeyUp);
+    /// ```
+    #[derive(Debug, PartialEq, Eq, Clone)]
+    #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+    pub enum SecHardware = 17 {
+        /// USB Stick Hardware Key is available
+        UsbKeyUp = 1,
+        /// USB Stick Hardware Key is unavailable
+        UsbKeyDown = 2,
+        /// USB Stick Hardware Key is transmitting,
+        UsbKeyDevTxMode = 3,
+        /// For receiving updates and is not avaiable for use
+        UsbKeyDevRxMode = 4,
+        /// Device access denied
+        UsbKeyDevAccessDenied = 5,
+        /// Fingerprint device is available
+        FingerPrintDevUp = 6,
+        /// Fingerprint device is unavailable
+        FingerPrintDevDown = 7,
+        /// Fingerprint device is powered of
+        FingerPrintDevPoweredOff = 8,
+        /// Fingerprint device is transmitting
+        FingerPrintDevTxMode = 9,
+        /// For receiving updates and is not avaiable for use
+        FingerPrintDevRxMode = 10,
+        /// Fingerprint Device access denied
+        FingerPrintDevAccessDenied = 11,
+        /// Iris scanner is available
+        IrisDevUp = 12,
+        /// Iris scanner is unavailable
+        IrisDevDown = 13,
+        /// Iris scanner is powered off
+        IrisDevPoweredOff = 14,
+        /// Iris scanner is transmitting
+        IrisDevTxMode = 15,
+        /// For receiving updates and is not avaiable for use
+        IrisDevRxMode = 16,
+        /// Iris Scanner Device access denied
+        IrisDevAccessDenied = 17,
+        /// Infra-red device is available
+        IrFloodDevUp = 18,
+        /// Infra-red device is unavailable
+        IrFloodDevDown = 19,
+        /// Infra-red device is powered off
+        IrFloodDevPoweredOff = 20,
+        /// Infra-red device is transmitting
+        IrDevTxMode = 21,
+        /// For receiving updates and is not avaiable for use
+        IrDevRxMode = 22,
+        /// Infra-red Device access denied
+        IrDevAccessDenied = 23,
+        /// Hardware Unspecified
+        Unspecified = 24,
+    }
+    failures [
+        UsbKeyDevAccessDenied, FingerPrintDevAccessDenied, IrisDevAccessDenied, IrDevAccessDenied,
+        Unspecified,
+    ]
+    http [
+        UsbKeyDevAccessDenied => 403, FingerPrintDevAccessDenied => 403, IrisDevAccessDenied => 403,
+        IrDevAccessDenied => 403,
+    ]
 }
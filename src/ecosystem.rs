@@ -0,0 +1,280 @@
+//! Downcasting of errors from popular crates in the ecosystem by both `try_downcast` and `try_downcast_dyn`.
+//! Each crate is enabled by a cargo feature of the same name: `serde_json`, `toml`, `tokio`, `reqwest` and `hyper`.
+//!
+//! Since a downcast only succeeds for the exact type, the version of the crate used by an application
+//! must match the one used here: `serde_json 1`, `toml 0.8`, `tokio 1`, `reqwest 0.12` and `hyper 1`.
+//!
+//! ### Examples
+//! ```
+//! # #[cfg(feature = "serde_json")]
+//! # {
+//! # use custom_codes::{try_downcast_dyn, DownCastErrors};
+//! let error = serde_json::from_str::<Vec<u8>>("[1,\n 2").unwrap_err();
+//! match try_downcast_dyn(&error) {
+//!     DownCastErrors::JsonEof { line, column } => assert_eq!((line, column), (2, 2)),
+//!     _ => panic!("expected `JsonEof`"),
+//! }
+//!
+//! let error = serde_json::from_str::<u8>("\"foo\"").unwrap_err();
+//! assert!(matches!(
+//!     try_downcast_dyn(&error),
+//!     DownCastErrors::JsonData { line: 1, column: 5 }
+//! ));
+//!
+//! let error = serde_json::from_str::<serde_json::Value>("{]").unwrap_err();
+//! assert!(matches!(
+//!     try_downcast_dyn(&error),
+//!     DownCastErrors::JsonSyntax { line: 1, column: 2 }
+//! ));
+//! # }
+//! ```
+//!
+//! ```
+//! # #[cfg(feature = "toml")]
+//! # {
+//! # use custom_codes::{try_downcast_dyn, DownCastErrors};
+//! #[derive(Debug, serde::Deserialize)]
+//! struct Config {
+//!     port: u16,
+//! }
+//!
+//! let error = toml::from_str::<Config>("port = \"foo\"").unwrap_err();
+//! match try_downcast_dyn(&error) {
+//!     DownCastErrors::TomlDe { span, .. } => assert_eq!(span, Some(7..12)),
+//!     _ => panic!("expected `TomlDe`"),
+//! }
+//! # }
+//! ```
+//!
+//! ```
+//! # #[cfg(feature = "tokio")]
+//! # {
+//! # use custom_codes::{try_downcast_dyn, DownCastErrors};
+//! use std::time::Duration;
+//!
+//! let runtime = tokio::runtime::Builder::new_current_thread()
+//!     .enable_time()
+//!     .build()
+//!     .unwrap();
+//!
+//! let elapsed = runtime
+//!     .block_on(async {
+//!         tokio::time::timeout(Duration::from_millis(1), tokio::time::sleep(Duration::from_secs(1))).await
+//!     })
+//!     .unwrap_err();
+//! assert!(matches!(
+//!     try_downcast_dyn(&elapsed),
+//!     DownCastErrors::TokioElapsed
+//! ));
+//!
+//! let join_error = runtime
+//!     .block_on(async {
+//!         let task = tokio::spawn(tokio::time::sleep(Duration::from_secs(1)));
+//!         task.abort();
+//!         task.await
+//!     })
+//!     .unwrap_err();
+//! assert!(matches!(
+//!     try_downcast_dyn(&join_error),
+//!     DownCastErrors::TokioJoinError { cancelled: true }
+//! ));
+//! # }
+//! ```
+//!
+//! ```
+//! # #[cfg(feature = "reqwest")]
+//! # {
+//! # use custom_codes::{try_downcast_dyn, DownCastErrors, HttpErrorKind};
+//! let error = reqwest::Client::new().get("not a url").build().unwrap_err();
+//! assert!(matches!(
+//!     try_downcast_dyn(&error),
+//!     DownCastErrors::Reqwest { kind: HttpErrorKind::Builder, status: None, .. }
+//! ));
+//! # }
+//! ```
+//!
+//! ```
+//! # #[cfg(feature = "hyper")]
+//! # {
+//! # use custom_codes::{try_downcast_dyn, DownCastErrors, HttpErrorKind};
+//! use http_body_util::Empty;
+//! use hyper::body::Bytes;
+//! use hyper_util::rt::TokioIo;
+//! use tokio::io::{AsyncReadExt, AsyncWriteExt};
+//!
+//! let runtime = tokio::runtime::Builder::new_current_thread().build().unwrap();
+//!
+//! let error = runtime.block_on(async {
+//!     let (client, mut server) = tokio::io::duplex(1024);
+//!     let (mut sender, connection) = hyper::client::conn::http1::handshake::<_, Empty<Bytes>>(TokioIo::new(client))
+//!         .await
+//!         .unwrap();
+//!     tokio::spawn(connection);
+//!     let response = tokio::spawn(sender.send_request(hyper::Request::new(Empty::new())));
+//!
+//!     let mut request = [0u8; 1024];
+//!     server.read(&mut request).await.unwrap();
+//!     server.write_all(b"not http\r\n\r\n").await.unwrap();
+//!
+//!     response.await.unwrap().unwrap_err()
+//! });
+//! assert!(matches!(
+//!     try_downcast_dyn(&error),
+//!     DownCastErrors::Hyper(HttpErrorKind::Parse)
+//! ));
+//! # }
+//! ```
+
+use crate::DownCastErrors;
+use std::error::Error;
+
+/// The stage at which an HTTP client or connection failed
+#[cfg(any(feature = "reqwest", feature = "hyper"))]
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum HttpErrorKind {
+    /// The request could not be built, eg. the URL is invalid
+    Builder,
+    /// A connection to the server could not be established
+    Connect,
+    /// The request or connection timed out
+    Timeout,
+    /// The request could not be sent
+    Request,
+    /// Following a redirect failed, eg. too many redirects
+    Redirect,
+    /// The response has an error status code
+    Status,
+    /// Reading or writing the body failed
+    Body,
+    /// The response body could not be decoded
+    Decode,
+    /// The message received is not valid HTTP
+    Parse,
+    /// The request was canceled before it completed
+    Canceled,
+    /// The connection was closed
+    Closed,
+    /// The connection closed before a complete message was received
+    IncompleteMessage,
+    /// The API was used incorrectly by the caller
+    User,
+    /// Any error not part of this list
+    Other,
+}
+
+/// Walks an error and its `source()` chain, returning the first error from a crate enabled by a feature
+#[allow(unused_variables)]
+pub(crate) fn downcast(error: &(dyn Error + 'static)) -> Option<DownCastErrors<'static>> {
+    let mut current = Some(error);
+
+    while let Some(error) = current {
+        #[cfg(feature = "serde_json")]
+        {
+            if let Some(json_error) = error.downcast_ref::<serde_json::Error>() {
+                let (line, column) = (json_error.line(), json_error.column());
+
+                return Some(match json_error.classify() {
+                    serde_json::error::Category::Syntax => {
+                        DownCastErrors::JsonSyntax { line, column }
+                    }
+                    serde_json::error::Category::Data => DownCastErrors::JsonData { line, column },
+                    serde_json::error::Category::Eof => DownCastErrors::JsonEof { line, column },
+                    serde_json::error::Category::Io => match json_error.io_error_kind() {
+                        Some(kind) => DownCastErrors::from(kind),
+                        None => DownCastErrors::Unspecified,
+                    },
+                });
+            }
+        }
+
+        #[cfg(feature = "toml")]
+        {
+            if let Some(toml_error) = error.downcast_ref::<toml::de::Error>() {
+                return Some(DownCastErrors::TomlDe {
+                    message: toml_error.message().to_owned(),
+                    span: toml_error.span(),
+                });
+            }
+        }
+
+        #[cfg(feature = "tokio")]
+        {
+            if error
+                .downcast_ref::<tokio::time::error::Elapsed>()
+                .is_some()
+            {
+                return Some(DownCastErrors::TokioElapsed);
+            } else if let Some(join_error) = error.downcast_ref::<tokio::task::JoinError>() {
+                return Some(DownCastErrors::TokioJoinError {
+                    cancelled: join_error.is_cancelled(),
+                });
+            }
+        }
+
+        #[cfg(feature = "reqwest")]
+        {
+            if let Some(reqwest_error) = error.downcast_ref::<reqwest::Error>() {
+                return Some(DownCastErrors::Reqwest {
+                    kind: reqwest_kind(reqwest_error),
+                    status: reqwest_error.status().map(|status| status.as_u16()),
+                    url: reqwest_error.url().map(|url| url.to_string()),
+                });
+            }
+        }
+
+        #[cfg(feature = "hyper")]
+        {
+            if let Some(hyper_error) = error.downcast_ref::<hyper::Error>() {
+                return Some(DownCastErrors::Hyper(hyper_kind(hyper_error)));
+            }
+        }
+
+        current = error.source();
+    }
+
+    None
+}
+
+#[cfg(feature = "reqwest")]
+fn reqwest_kind(error: &reqwest::Error) -> HttpErrorKind {
+    if error.is_builder() {
+        HttpErrorKind::Builder
+    } else if error.is_timeout() {
+        HttpErrorKind::Timeout
+    } else if error.is_connect() {
+        HttpErrorKind::Connect
+    } else if error.is_redirect() {
+        HttpErrorKind::Redirect
+    } else if error.is_status() {
+        HttpErrorKind::Status
+    } else if error.is_body() {
+        HttpErrorKind::Body
+    } else if error.is_decode() {
+        HttpErrorKind::Decode
+    } else if error.is_request() {
+        HttpErrorKind::Request
+    } else {
+        HttpErrorKind::Other
+    }
+}
+
+#[cfg(feature = "hyper")]
+fn hyper_kind(error: &hyper::Error) -> HttpErrorKind {
+    if error.is_parse() || error.is_parse_status() {
+        HttpErrorKind::Parse
+    } else if error.is_timeout() {
+        HttpErrorKind::Timeout
+    } else if error.is_canceled() {
+        HttpErrorKind::Canceled
+    } else if error.is_incomplete_message() {
+        HttpErrorKind::IncompleteMessage
+    } else if error.is_closed() {
+        HttpErrorKind::Closed
+    } else if error.is_body_write_aborted() {
+        HttpErrorKind::Body
+    } else if error.is_user() {
+        HttpErrorKind::User
+    } else {
+        HttpErrorKind::Other
+    }
+}
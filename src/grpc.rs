@@ -0,0 +1,122 @@
+//! gRPC status codes of the codes.
+//!
+//! Every variant declares the HTTP status and the gRPC status code of a response reporting it,
+//! read with `Code::http_status()` and `Code::grpc_code()`. The gRPC code follows the HTTP status
+//! unless the family declares another one, eg. `DbOps::QueryConflictError` is `409 Conflict` over HTTP
+//! and `ABORTED` over gRPC.
+//! ### Examples
+//! ```
+//! use custom_codes::{Code, DbOps, GrpcCode};
+//!
+//! assert_eq!(DbOps::DocumentNotFound.http_status(), 404);
+//! assert_eq!(DbOps::DocumentNotFound.grpc_code(), GrpcCode::NotFound);
+//! assert_eq!(DbOps::QueryConflictError.grpc_code(), GrpcCode::Aborted);
+//! assert_eq!(GrpcCode::Aborted as i32, 10);
+//! ```
+
+use core::fmt;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// The canonical status codes of gRPC, numbered as on the wire
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[repr(i32)]
+pub enum GrpcCode {
+    /// Not an error, returned on success
+    Ok = 0,
+    /// The operation was cancelled, typically by the caller
+    Cancelled = 1,
+    /// An error which does not belong to another code
+    Unknown = 2,
+    /// The client specified an invalid argument
+    InvalidArgument = 3,
+    /// The deadline expired before the operation could complete
+    DeadlineExceeded = 4,
+    /// A requested entity was not found
+    NotFound = 5,
+    /// The entity a client attempted to create already exists
+    AlreadyExists = 6,
+    /// The caller does not have permission to execute the operation
+    PermissionDenied = 7,
+    /// A resource has been exhausted, eg. a quota or the disk space
+    ResourceExhausted = 8,
+    /// The system is not in a state required for the operation
+    FailedPrecondition = 9,
+    /// The operation was aborted, typically because of a concurrency conflict
+    Aborted = 10,
+    /// The operation was attempted past the valid range
+    OutOfRange = 11,
+    /// The operation is not implemented or not supported
+    Unimplemented = 12,
+    /// An invariant expected by the system has been broken
+    Internal = 13,
+    /// The service is currently unavailable, retrying may succeed
+    Unavailable = 14,
+    /// Unrecoverable data loss or corruption
+    DataLoss = 15,
+    /// The request does not have valid authentication credentials
+    Unauthenticated = 16,
+}
+
+impl GrpcCode {
+    /// The gRPC code of a response with an HTTP status, a success is `Ok` and a status without
+    /// a closer gRPC code is `FailedPrecondition` for a client error, `Internal` for a server error
+    /// and `Unknown` otherwise
+    /// ### Examples
+    /// ```
+    /// # use custom_codes::GrpcCode;
+    /// assert_eq!(GrpcCode::from_http(201), GrpcCode::Ok);
+    /// assert_eq!(GrpcCode::from_http(403), GrpcCode::PermissionDenied);
+    /// assert_eq!(GrpcCode::from_http(418), GrpcCode::FailedPrecondition);
+    /// ```
+    pub const fn from_http(status: u16) -> Self {
+        match status {
+            200..=299 => GrpcCode::Ok,
+            400 => GrpcCode::InvalidArgument,
+            401 => GrpcCode::Unauthenticated,
+            403 => GrpcCode::PermissionDenied,
+            404 => GrpcCode::NotFound,
+            409 => GrpcCode::AlreadyExists,
+            416 => GrpcCode::OutOfRange,
+            429 => GrpcCode::ResourceExhausted,
+            499 => GrpcCode::Cancelled,
+            501 => GrpcCode::Unimplemented,
+            503 => GrpcCode::Unavailable,
+            504 => GrpcCode::DeadlineExceeded,
+            _ if status / 100 == 4 => GrpcCode::FailedPrecondition,
+            _ if status / 100 == 5 => GrpcCode::Internal,
+            _ => GrpcCode::Unknown,
+        }
+    }
+
+    /// The name of the code as written by gRPC, eg. `NOT_FOUND`
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            GrpcCode::Ok => "OK",
+            GrpcCode::Cancelled => "CANCELLED",
+            GrpcCode::Unknown => "UNKNOWN",
+            GrpcCode::InvalidArgument => "INVALID_ARGUMENT",
+            GrpcCode::DeadlineExceeded => "DEADLINE_EXCEEDED",
+            GrpcCode::NotFound => "NOT_FOUND",
+            GrpcCode::AlreadyExists => "ALREADY_EXISTS",
+            GrpcCode::PermissionDenied => "PERMISSION_DENIED",
+            GrpcCode::ResourceExhausted => "RESOURCE_EXHAUSTED",
+            GrpcCode::FailedPrecondition => "FAILED_PRECONDITION",
+            GrpcCode::Aborted => "ABORTED",
+            GrpcCode::OutOfRange => "OUT_OF_RANGE",
+            GrpcCode::Unimplemented => "UNIMPLEMENTED",
+            GrpcCode::Internal => "INTERNAL",
+            GrpcCode::Unavailable => "UNAVAILABLE",
+            GrpcCode::DataLoss => "DATA_LOSS",
+            GrpcCode::Unauthenticated => "UNAUTHENTICATED",
+        }
+    }
+}
+
+impl fmt::Display for GrpcCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
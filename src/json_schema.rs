@@ -0,0 +1,155 @@
+//! JSON Schema and OpenAPI 3.1 components describing the serde representation of the codes.
+//!
+//! A family is described by a `oneOf` holding one alternative per variant, described by the doc comment
+//! of the variant. Following serde's externally tagged representation, a unit variant is a string and
+//! a variant carrying a payload is an object with a single property named after the variant.
+//! Deprecated names which are still accepted, eg. `AccpetedRAC`, are listed as `deprecated` alternatives.
+//! Names follow the `legacy-names` feature like serialization does, the correct spelling then being
+//! the alternative.
+//! ### Examples
+//! ```
+//! use serde_json::json;
+//!
+//! let components = custom_codes::json_schema::openapi_components();
+//! let db_ops = &components["schemas"]["DbOps"];
+//! assert_eq!(db_ops["description"], "Custom Response Codes Using Enums For Efficient Comparison");
+//! assert!(db_ops["oneOf"].as_array().unwrap().contains(&json!({
+//!     "type": "string",
+//!     "const": "DocumentNotFound",
+//!     "description": "Document does not exist",
+//! })));
+//! ```
+
+use crate::{Code, Family, Outcome, FAMILIES};
+use serde_json::{json, Map, Value};
+
+/// The prefix of references between the schemas of `openapi_components()`
+pub const OPENAPI_REFS: &str = "#/components/schemas/";
+/// The prefix of references between the schemas of `json_schema()`
+pub const JSON_SCHEMA_REFS: &str = "#/$defs/";
+
+/// The doc comment of `GenericOutcome` which is not a code family
+const GENERIC_OUTCOME_DESCRIPTION: &str = generic_outcome_docs!();
+
+/// A JSON Schema 2020-12 document defining every code family and `GenericOutcome` under `$defs`
+pub fn json_schema() -> Value {
+    json!({
+        "$schema": "https://json-schema.org/draft/2020-12/schema",
+        "$defs": schemas(JSON_SCHEMA_REFS),
+    })
+}
+
+/// OpenAPI 3.1 components defining every code family and `GenericOutcome` under `schemas`.
+/// The reason of `GenericOutcome` accepts any value, use `generic_outcome_schema()` to narrow it
+pub fn openapi_components() -> Value {
+    json!({ "schemas": schemas(OPENAPI_REFS) })
+}
+
+fn schemas(refs: &str) -> Map<String, Value> {
+    let mut schemas: Map<String, Value> = FAMILIES
+        .iter()
+        .map(|family| (family.name.into(), family_schema(family, refs)))
+        .collect();
+    schemas.insert("GenericOutcome".into(), generic_outcome_schema(json!({})));
+
+    schemas
+}
+
+/// The schema of a code family, `refs` is the prefix of references to other families
+/// such as `KeyLength` carried by `SecOps::KeyTooShort`
+/// ### Examples
+/// ```
+/// use custom_codes::json_schema::{family_schema, JSON_SCHEMA_REFS};
+/// use custom_codes::{Family, SecOps};
+/// use serde_json::json;
+///
+/// let schema = family_schema(&Family::of::<SecOps>(), JSON_SCHEMA_REFS);
+/// assert!(schema["oneOf"].as_array().unwrap().contains(&json!({
+///     "type": "object",
+///     "properties": { "KeyTooShort": { "$ref": "#/$defs/KeyLength" } },
+///     "required": ["KeyTooShort"],
+///     "additionalProperties": false,
+///     "description": "Key length too short",
+/// })));
+/// ```
+pub fn family_schema(family: &Family, refs: &str) -> Value {
+    let variants = family.variants.iter().map(|variant| {
+        let name = family.serialized_name(variant.name);
+        variant_schema(name, variant.payload, &variant.description(), refs)
+    });
+    // The other spelling serde accepts, deprecated unless `legacy-names` makes it the correct one
+    let alternatives = family.variants.iter().filter_map(|variant| {
+        let name = family.accepted_name(variant.name)?;
+        let mut schema = variant_schema(name, variant.payload, &variant.description(), refs);
+        if name != variant.name {
+            schema["deprecated"] = Value::Bool(true);
+        }
+
+        Some(schema)
+    });
+
+    json!({
+        "title": family.name,
+        "description": family.description(),
+        "oneOf": variants.chain(alternatives).collect::<Vec<_>>(),
+    })
+}
+
+/// The schema of `GenericOutcome` with the given schema of its reason
+/// ### Examples
+/// ```
+/// use custom_codes::json_schema::generic_outcome_schema;
+/// use serde_json::json;
+///
+/// let schema = generic_outcome_schema(json!({ "type": "string" }));
+/// assert_eq!(schema["oneOf"][0]["properties"]["Success"], json!({ "type": "string" }));
+/// ```
+pub fn generic_outcome_schema(reason: Value) -> Value {
+    // The variants of `GenericOutcome` mirror those of `Outcome` and share their doc comments
+    let variants = Outcome::VARIANTS
+        .iter()
+        .map(|variant| tagged_schema(variant.name, reason.clone(), &variant.description()))
+        .collect::<Vec<_>>();
+
+    json!({
+        "title": "GenericOutcome",
+        "description": GENERIC_OUTCOME_DESCRIPTION,
+        "oneOf": variants,
+    })
+}
+
+fn variant_schema(name: &str, payload: Option<&str>, description: &str, refs: &str) -> Value {
+    match payload {
+        Some(payload) => tagged_schema(name, payload_schema(payload, refs), description),
+        None => json!({
+            "type": "string",
+            "const": name,
+            "description": description,
+        }),
+    }
+}
+
+fn tagged_schema(name: &str, payload: Value, description: &str) -> Value {
+    let mut properties = Map::new();
+    properties.insert(name.into(), payload);
+
+    json!({
+        "type": "object",
+        "properties": properties,
+        "required": [name],
+        "additionalProperties": false,
+        "description": description,
+    })
+}
+
+fn payload_schema(payload: &str, refs: &str) -> Value {
+    let byte = json!({ "type": "integer", "minimum": 0, "maximum": 255 });
+
+    match payload {
+        "String" => json!({ "type": "string" }),
+        "Vec<String>" => json!({ "type": "array", "items": { "type": "string" } }),
+        "Vec<u8>" => json!({ "type": "array", "items": byte }),
+        "Vec<Vec<u8>>" => json!({ "type": "array", "items": { "type": "array", "items": byte } }),
+        family => json!({ "$ref": format!("{}{}", refs, family) }),
+    }
+}
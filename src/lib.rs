@@ -1,3 +1,4 @@
+#![cfg_attr(not(feature = "std"), no_std)]
 #![deny(missing_docs)]
 #![deny(unsafe_code)]
 //! This are Custom Enum codes for memorable and uniform response codes.
@@ -18,17 +19,132 @@
 //!
 //! open_file("foo.txt");
 //! ```
+//!
+//! ## Features
+//! The code enums compile for `no_std` targets with default features disabled.
+//! - `std` enables `std::error::Error` implementations, downcasting, `coded::CodedError` attaching codes to errors,
+//!   the `response::Response` envelope and the `metrics` counters of emitted codes, it implies `alloc`
+//! - `alloc` enables the variants carrying a `String` or `Vec` such as `DbOps::EncounteredErrors`
+//!   along with `summary::OutcomeSummary` summarizing the codes of a batch, `catalog::Catalog` localizing messages
+//!   and `lock::Lockfile` checking that families stay compatible between versions
+//! - `serde` derives `Serialize` and `Deserialize` for the code enums, see `repr` for other representations
+//! - `toml` teaches `try_downcast` to classify `toml` errors and reads `catalog::Catalog` messages from TOML files
+//! - `anyhow` enables `try_downcast` for `anyhow::Error` and `coded::WithCode` attaching codes to its errors
+//! - `json-schema` adds `json_schema`, JSON Schema and OpenAPI components describing the serde representation
+//! - `problem-details` adds `problem::ProblemDetails`, RFC 9457 problem details built from codes and `DownCastErrors`
+//! - `prost` adds `proto::CodeEnvelope`, a Protocol Buffers message converting from and into any code
+//! - `log` and `tracing` convert a `Severity` into the levels of those crates,
+//!   `tracing` also adds `trace`, recording codes as structured fields of events and spans
+//! - `metrics` adds `metrics::MetricsRecorder` forwarding the counts of emitted codes to the `metrics` crate
+//! - `build` adds `build::generate()`, generating code families from a TOML file in a build script
+//! - `legacy-names` serializes the correctly spelled variants with their old misspelled names
+//!
+//! The `custom_codes_ffi` crate of the workspace exposes the codes to C through the header generated by `c_header`.
+//!
+//! ## Custom families
+//! Applications declare families of their own with `define_codes!`, which declares the families of this crate.
+//! They get the same stable numbers, messages, severities, retry hints, HTTP statuses and gRPC codes
+//! as the built-in families.
+//!
+//! ## Misspelled variants
+//! Misspelled variants such as `AccessStatus::AccpetedRAC` are deprecated constants equal to the correctly
+//! spelled variants, so they keep the variant indices of serde's index based formats.
+//! Both spellings are accepted when deserializing, while the correct spelling is serialized
+//! unless the `legacy-names` feature is enabled for consumers which have not migrated yet.
+//! ```
+//! # #[cfg(all(feature = "serde", not(feature = "legacy-names")))]
+//! # {
+//! use custom_codes::AccessStatus;
+//!
+//! let status: AccessStatus = serde_json::from_str(r#""AccpetedRAC""#).unwrap();
+//! assert_eq!(status, AccessStatus::AcceptedRAC);
+//! assert_eq!(serde_json::to_string(&status).unwrap(), r#""AcceptedRAC""#);
+//! # }
+//! ```
+
+#[cfg(feature = "alloc")]
+extern crate alloc;
 
 /// re-exports
+pub use crate::code::{Code, CodeAlias, CodeVariant, Family, ParseCodeError, FAMILIES};
 pub use crate::custom_codes::*;
+pub use crate::grpc::GrpcCode;
+pub use crate::retry::{RetryHint, Retryable};
+pub use crate::set::CodeSet;
+pub use crate::severity::Severity;
 
+#[cfg(feature = "build")]
+pub mod build;
+#[cfg(feature = "alloc")]
+pub mod c_header;
+#[macro_use]
+mod code;
+#[cfg(feature = "alloc")]
+pub mod catalog;
+#[cfg(feature = "alloc")]
+pub mod codec;
+#[cfg(feature = "std")]
+pub mod coded;
+#[cfg(all(feature = "serde", feature = "alloc"))]
+pub mod compat;
+#[macro_use]
 mod custom_codes;
+pub mod grpc;
+#[cfg(feature = "json-schema")]
+pub mod json_schema;
+#[cfg(feature = "alloc")]
+pub mod lock;
+#[cfg(feature = "std")]
+pub mod metrics;
+#[cfg(feature = "problem-details")]
+pub mod problem;
+#[cfg(feature = "alloc")]
+pub mod proto;
+#[cfg(feature = "serde")]
+pub mod repr;
+#[cfg(feature = "std")]
+pub mod response;
+pub mod retry;
+pub mod set;
+pub mod severity;
+#[cfg(feature = "alloc")]
+pub mod summary;
+#[cfg(feature = "tracing")]
+pub mod trace;
+#[cfg(feature = "alloc")]
+pub mod typescript;
 
 /// Add Macros for support
+#[cfg(feature = "std")]
 mod anyhow_downcast;
-pub use crate::anyhow_downcast::{try_downcast, DownCastErrors, StringifyError};
+#[cfg(feature = "anyhow")]
+pub use crate::anyhow_downcast::try_downcast;
+#[cfg(feature = "std")]
+pub use crate::anyhow_downcast::{try_downcast_dyn, DownCastErrors, StringifyError};
+
+#[cfg(feature = "std")]
+mod ecosystem;
+#[cfg(any(feature = "reqwest", feature = "hyper"))]
+pub use crate::ecosystem::HttpErrorKind;
+
+#[cfg(feature = "tracing")]
+#[doc(hidden)]
+pub use tracing as __tracing;
+
+/// Items used by the expansion of `define_codes!`, not part of the public API
+#[doc(hidden)]
+pub mod __private {
+    pub use crate::code::{contains, grpc_of, http_of, retry_of, severity_of, write_message};
+    #[cfg(feature = "alloc")]
+    pub use crate::codec::{unit, write_header, write_with_payload};
+    #[cfg(feature = "alloc")]
+    pub use alloc::vec::Vec;
+    #[cfg(feature = "std")]
+    pub use std::error::Error;
+}
 
 /// Try downcasting any error to std::io::Error
+#[cfg(feature = "anyhow")]
 #[macro_export]
 macro_rules! downcast {
     ($error:expr) => {
@@ -0,0 +1,581 @@
+//! Lockfile of the code families, checking that a new version does not break persisted codes.
+//!
+//! Codes are persisted and sent on the wire by name and number, so removing a variant, changing its number
+//! or the type of its payload, or removing a deprecated name or pointing it at another variant breaks readers
+//! of existing data. `Lockfile::of()` snapshots families, the text written by its `Display` implementation
+//! declares them like `define_codes!` and is read back by `Lockfile::parse()`. `Lockfile::diff()` lists
+//! the changes between a locked snapshot and the current families, only additions are compatible, and so is
+//! renaming a variant which keeps its old name as a deprecated name.
+//!
+//! `custom_codes.lock` locks the families of this crate. The `custom_codes_lock` binary checks the current
+//! build against a lockfile and updates it, while applications lock their own families in a test.
+//! ### Examples
+//! ```
+//! use custom_codes::lock::{Change, Lockfile};
+//! use custom_codes::{DbOps, Family};
+//!
+//! let current = Lockfile::of(&[Family::of::<DbOps>()]);
+//! let locked = Lockfile::parse(&current.to_string().replace("RepoInitialized = 2\n", "")).unwrap();
+//!
+//! let diff = locked.diff(&current);
+//! assert!(!diff.is_breaking());
+//! assert_eq!(
+//!     diff.changes,
+//!     [Change::VariantAdded {
+//!         family: "DbOps".into(),
+//!         variant: "RepoInitialized".into(),
+//!         number: 2,
+//!     }]
+//! );
+//! ```
+
+use crate::{Family, FAMILIES};
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt;
+
+/// The file name of the lockfile checked by the `custom_codes_lock` binary by default
+pub const LOCKFILE: &str = "custom_codes.lock";
+
+/// A snapshot of code families with the names, numbers and payload types of their variants
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct Lockfile {
+    /// The locked families in the order they are written
+    pub families: Vec<LockedFamily>,
+}
+
+/// A locked code family
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct LockedFamily {
+    /// The name of the family, see `Code::FAMILY`
+    pub name: String,
+    /// The number of the family, see `Code::FAMILY_ID`
+    pub id: u16,
+    /// The variants of the family in the order they are declared
+    pub variants: Vec<LockedVariant>,
+    /// The deprecated names of the family and the variants replacing them, see `Code::ALIASES`
+    pub aliases: Vec<(String, String)>,
+}
+
+/// A locked variant
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct LockedVariant {
+    /// The name of the variant
+    pub name: String,
+    /// The number of the variant within its family
+    pub number: u16,
+    /// The type of the payload of the variant, if any, see `CodeVariant::payload`
+    pub payload: Option<String>,
+}
+
+impl Lockfile {
+    /// Snapshots `families`
+    pub fn of(families: &[Family]) -> Self {
+        let families = families
+            .iter()
+            .map(|family| LockedFamily {
+                name: family.name.into(),
+                id: family.id,
+                variants: family
+                    .variants
+                    .iter()
+                    .map(|variant| LockedVariant {
+                        name: variant.name.into(),
+                        number: variant.number,
+                        payload: variant.payload.map(Into::into),
+                    })
+                    .collect(),
+                aliases: family
+                    .aliases
+                    .iter()
+                    .map(|alias| (alias.name.into(), alias.canonical.into()))
+                    .collect(),
+            })
+            .collect();
+
+        Lockfile { families }
+    }
+
+    /// Snapshots the families of this crate, see `FAMILIES`
+    pub fn current() -> Self {
+        Lockfile::of(FAMILIES)
+    }
+
+    /// Reads a lockfile written by the `Display` implementation. Blank lines and lines starting with `#` are ignored
+    pub fn parse(source: &str) -> Result<Self, ParseError> {
+        let mut families: Vec<LockedFamily> = Vec::new();
+
+        for (index, line) in source.lines().enumerate() {
+            let line = line.trim();
+            let error = |reason| ParseError {
+                line: index + 1,
+                reason,
+            };
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            if let Some(header) = line.strip_prefix('[') {
+                let (name, id) = header
+                    .strip_suffix(']')
+                    .and_then(|header| header.split_once(" = "))
+                    .ok_or_else(|| error(ParseErrorReason::InvalidLine))?;
+                let id = id
+                    .parse()
+                    .map_err(|_| error(ParseErrorReason::InvalidNumber))?;
+                if families.iter().any(|family| family.name == name) {
+                    return Err(error(ParseErrorReason::DuplicateFamily(name.into())));
+                }
+                families.push(LockedFamily {
+                    name: name.into(),
+                    id,
+                    variants: Vec::new(),
+                    aliases: Vec::new(),
+                });
+                continue;
+            }
+
+            let family = families
+                .last_mut()
+                .ok_or_else(|| error(ParseErrorReason::ExpectedFamily))?;
+            if let Some((alias, canonical)) = line.split_once(" => ") {
+                family.aliases.push((alias.into(), canonical.into()));
+            } else {
+                let (declaration, number) = line
+                    .rsplit_once(" = ")
+                    .ok_or_else(|| error(ParseErrorReason::InvalidLine))?;
+                let number = number
+                    .parse()
+                    .map_err(|_| error(ParseErrorReason::InvalidNumber))?;
+                let (name, payload) = match declaration.split_once('(') {
+                    Some((name, payload)) => {
+                        let payload = payload
+                            .strip_suffix(')')
+                            .ok_or_else(|| error(ParseErrorReason::InvalidLine))?;
+                        (name, Some(payload.into()))
+                    }
+                    None => (declaration, None),
+                };
+                family.variants.push(LockedVariant {
+                    name: name.into(),
+                    number,
+                    payload,
+                });
+            }
+        }
+
+        Ok(Lockfile { families })
+    }
+
+    /// The changes from this locked snapshot to `current`, breaking changes first
+    pub fn diff(&self, current: &Lockfile) -> Diff {
+        let mut changes = Vec::new();
+
+        for locked in &self.families {
+            let family = || locked.name.clone();
+            let current = match current.family(&locked.name) {
+                Some(current) => current,
+                None => {
+                    changes.push(Change::FamilyRemoved { family: family() });
+                    continue;
+                }
+            };
+
+            if locked.id != current.id {
+                changes.push(Change::FamilyRenumbered {
+                    family: family(),
+                    locked: locked.id,
+                    current: current.id,
+                });
+            }
+            for variant in &locked.variants {
+                match current.variant(&variant.name) {
+                    // Renamed, the old name still reads the same code
+                    None if current.renamed(variant).is_some() => {}
+                    None => changes.push(Change::VariantRemoved {
+                        family: family(),
+                        variant: variant.name.clone(),
+                    }),
+                    Some(found) if found.number != variant.number => {
+                        changes.push(Change::VariantRenumbered {
+                            family: family(),
+                            variant: variant.name.clone(),
+                            locked: variant.number,
+                            current: found.number,
+                        })
+                    }
+                    Some(found) if found.payload != variant.payload => {
+                        changes.push(Change::PayloadChanged {
+                            family: family(),
+                            variant: variant.name.clone(),
+                            locked: variant.payload.clone(),
+                            current: found.payload.clone(),
+                        })
+                    }
+                    Some(_) => {}
+                }
+            }
+            for (alias, canonical) in &locked.aliases {
+                match current.aliases.iter().find(|(name, _)| name == alias) {
+                    None => changes.push(Change::AliasRemoved {
+                        family: family(),
+                        alias: alias.clone(),
+                    }),
+                    Some((_, found))
+                        if found != canonical
+                            && locked
+                                .variant(canonical)
+                                .and_then(|variant| current.renamed(variant))
+                                != Some(found.as_str()) =>
+                    {
+                        changes.push(Change::AliasRetargeted {
+                            family: family(),
+                            alias: alias.clone(),
+                            locked: canonical.clone(),
+                            current: found.clone(),
+                        })
+                    }
+                    Some(_) => {}
+                }
+            }
+
+            for variant in &current.variants {
+                if locked.variant(&variant.name).is_none() {
+                    changes.push(Change::VariantAdded {
+                        family: family(),
+                        variant: variant.name.clone(),
+                        number: variant.number,
+                    });
+                }
+            }
+            for (alias, canonical) in &current.aliases {
+                if !locked.aliases.iter().any(|(name, _)| name == alias) {
+                    changes.push(Change::AliasAdded {
+                        family: family(),
+                        alias: alias.clone(),
+                        canonical: canonical.clone(),
+                    });
+                }
+            }
+        }
+
+        for family in &current.families {
+            if self.family(&family.name).is_none() {
+                changes.push(Change::FamilyAdded {
+                    family: family.name.clone(),
+                    id: family.id,
+                });
+            }
+        }
+        changes.sort_by_key(|change| !change.is_breaking());
+
+        Diff { changes }
+    }
+
+    fn family(&self, name: &str) -> Option<&LockedFamily> {
+        self.families.iter().find(|family| family.name == name)
+    }
+}
+
+impl LockedFamily {
+    fn variant(&self, name: &str) -> Option<&LockedVariant> {
+        self.variants.iter().find(|variant| variant.name == name)
+    }
+
+    /// The new name of a locked variant kept as a deprecated name of a variant with the same number and payload
+    fn renamed(&self, locked: &LockedVariant) -> Option<&str> {
+        let (_, canonical) = self
+            .aliases
+            .iter()
+            .find(|(alias, _)| *alias == locked.name)?;
+        self.variant(canonical)
+            .filter(|variant| variant.number == locked.number && variant.payload == locked.payload)
+            .map(|variant| variant.name.as_str())
+    }
+}
+
+/// Writes the lockfile, every family as a `[Family = id]` header followed by its variants
+/// as `Name = number` or `Name(Payload) = number` and its deprecated names as `Alias => Canonical`
+impl fmt::Display for Lockfile {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "# Code families locked by `custom_codes::lock`, do not edit"
+        )?;
+        writeln!(
+            f,
+            "# Removing, renumbering or retyping a variant is a breaking change"
+        )?;
+
+        for family in &self.families {
+            writeln!(f, "\n[{} = {}]", family.name, family.id)?;
+            for variant in &family.variants {
+                match &variant.payload {
+                    Some(payload) => {
+                        writeln!(f, "{}({}) = {}", variant.name, payload, variant.number)?
+                    }
+                    None => writeln!(f, "{} = {}", variant.name, variant.number)?,
+                }
+            }
+            for (alias, canonical) in &family.aliases {
+                writeln!(f, "{} => {}", alias, canonical)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// The changes between a locked snapshot and the current families, see `Lockfile::diff`
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct Diff {
+    /// The changes, breaking changes first
+    pub changes: Vec<Change>,
+}
+
+impl Diff {
+    /// Whether any change breaks readers of codes persisted with the locked families
+    pub fn is_breaking(&self) -> bool {
+        self.changes.iter().any(Change::is_breaking)
+    }
+
+    /// Whether the families are unchanged
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+}
+
+/// Writes one change per line, breaking changes prefixed with `breaking:` and the others with `added:`
+impl fmt::Display for Diff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for change in &self.changes {
+            let kind = if change.is_breaking() {
+                "breaking"
+            } else {
+                "added"
+            };
+            writeln!(f, "{}: {}", kind, change)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// A change of a family between a locked snapshot and the current families
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum Change {
+    /// A locked family no longer exists
+    FamilyRemoved {
+        /// The name of the family
+        family: String,
+    },
+    /// The number of a family changed
+    FamilyRenumbered {
+        /// The name of the family
+        family: String,
+        /// The locked number
+        locked: u16,
+        /// The current number
+        current: u16,
+    },
+    /// A locked variant no longer exists, or has been renamed without keeping its old name as a deprecated name
+    VariantRemoved {
+        /// The name of the family
+        family: String,
+        /// The name of the variant
+        variant: String,
+    },
+    /// The number of a variant changed
+    VariantRenumbered {
+        /// The name of the family
+        family: String,
+        /// The name of the variant
+        variant: String,
+        /// The locked number
+        locked: u16,
+        /// The current number
+        current: u16,
+    },
+    /// The payload of a variant changed, was added or was removed
+    PayloadChanged {
+        /// The name of the family
+        family: String,
+        /// The name of the variant
+        variant: String,
+        /// The locked payload type
+        locked: Option<String>,
+        /// The current payload type
+        current: Option<String>,
+    },
+    /// A deprecated name is no longer accepted
+    AliasRemoved {
+        /// The name of the family
+        family: String,
+        /// The deprecated name
+        alias: String,
+    },
+    /// A deprecated name now stands for another variant
+    AliasRetargeted {
+        /// The name of the family
+        family: String,
+        /// The deprecated name
+        alias: String,
+        /// The locked variant it stood for
+        locked: String,
+        /// The current variant it stands for
+        current: String,
+    },
+    /// A family has been added
+    FamilyAdded {
+        /// The name of the family
+        family: String,
+        /// The number of the family
+        id: u16,
+    },
+    /// A variant has been added to a locked family
+    VariantAdded {
+        /// The name of the family
+        family: String,
+        /// The name of the variant
+        variant: String,
+        /// The number of the variant
+        number: u16,
+    },
+    /// A deprecated name has been added to a locked family
+    AliasAdded {
+        /// The name of the family
+        family: String,
+        /// The deprecated name
+        alias: String,
+        /// The name of the variant replacing it
+        canonical: String,
+    },
+}
+
+impl Change {
+    /// Whether the change breaks readers of codes persisted with the locked families,
+    /// every change but an addition does
+    pub fn is_breaking(&self) -> bool {
+        !matches!(
+            self,
+            Change::FamilyAdded { .. } | Change::VariantAdded { .. } | Change::AliasAdded { .. }
+        )
+    }
+}
+
+impl fmt::Display for Change {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let payload = |payload: &Option<String>| {
+            payload
+                .as_ref()
+                .map_or_else(|| "no payload".to_string(), |payload| payload.clone())
+        };
+
+        match self {
+            Change::FamilyRemoved { family } => write!(f, "family {} was removed", family),
+            Change::FamilyRenumbered {
+                family,
+                locked,
+                current,
+            } => write!(
+                f,
+                "family {} was renumbered from {} to {}",
+                family, locked, current
+            ),
+            Change::VariantRemoved { family, variant } => {
+                write!(f, "{}::{} was removed or renamed", family, variant)
+            }
+            Change::VariantRenumbered {
+                family,
+                variant,
+                locked,
+                current,
+            } => write!(
+                f,
+                "{}::{} was renumbered from {} to {}",
+                family, variant, locked, current
+            ),
+            Change::PayloadChanged {
+                family,
+                variant,
+                locked,
+                current,
+            } => write!(
+                f,
+                "the payload of {}::{} changed from {} to {}",
+                family,
+                variant,
+                payload(locked),
+                payload(current)
+            ),
+            Change::AliasRemoved { family, alias } => {
+                write!(f, "the deprecated name {}::{} was removed", family, alias)
+            }
+            Change::AliasRetargeted {
+                family,
+                alias,
+                locked,
+                current,
+            } => write!(
+                f,
+                "the deprecated name {}::{} was retargeted from {} to {}",
+                family, alias, locked, current
+            ),
+            Change::FamilyAdded { family, id } => write!(f, "family {} = {}", family, id),
+            Change::VariantAdded {
+                family,
+                variant,
+                number,
+            } => write!(f, "{}::{} = {}", family, variant, number),
+            Change::AliasAdded {
+                family,
+                alias,
+                canonical,
+            } => write!(f, "{}::{} => {}", family, alias, canonical),
+        }
+    }
+}
+
+/// A lockfile could not be read
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct ParseError {
+    /// The line which could not be read, starting at `1`
+    pub line: usize,
+    /// Why the line could not be read
+    pub reason: ParseErrorReason,
+}
+
+/// Why a line of a lockfile could not be read
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum ParseErrorReason {
+    /// A variant or deprecated name comes before the header of its family
+    ExpectedFamily,
+    /// The line is neither a family header, a variant nor a deprecated name
+    InvalidLine,
+    /// A family or variant number is not a number from `0` to `65535`
+    InvalidNumber,
+    /// A family is locked twice
+    DuplicateFamily(String),
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Line {}: ", self.line)?;
+        match &self.reason {
+            ParseErrorReason::ExpectedFamily => write!(f, "expected a family header"),
+            ParseErrorReason::InvalidLine => {
+                write!(
+                    f,
+                    "expected a family header, a variant or a deprecated name"
+                )
+            }
+            ParseErrorReason::InvalidNumber => write!(f, "invalid number"),
+            ParseErrorReason::DuplicateFamily(name) => {
+                write!(f, "the family `{}` is locked twice", name)
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ParseError {}
@@ -0,0 +1,265 @@
+//! Counts how often every code is emitted, by family and variant.
+//!
+//! A `Recorder` receives every counted code. `InMemoryRecorder` keeps one lock-free counter per variant,
+//! indexed by the numeric code, which can be read with `snapshot()` and rendered in the Prometheus text format.
+//! With the `metrics` feature, `MetricsRecorder` forwards the counts to the recorder installed in the `metrics` crate.
+//!
+//! Codes are counted with `count()` on a recorder, or with `record()` on the recorder installed for the process
+//! with `set_recorder()`. Logging a code with `trace::TraceCode::emit()` or `trace_code!` does not count it.
+//! ### Examples
+//! ```
+//! use custom_codes::metrics::{count, InMemoryRecorder};
+//! use custom_codes::{AccessStatus, Networking};
+//!
+//! let recorder = InMemoryRecorder::new();
+//! count(&recorder, &Networking::NetTimedOut);
+//! count(&recorder, &Networking::NetTimedOut);
+//! count(&recorder, &AccessStatus::Denied);
+//!
+//! let snapshot = recorder.snapshot();
+//! assert_eq!(snapshot.count(&Networking::NetTimedOut), 2);
+//! assert!(snapshot
+//!     .to_prometheus()
+//!     .contains("custom_codes_total{family=\"AccessStatus\",name=\"Denied\",code=\"2004\"} 1\n"));
+//! ```
+
+use crate::code::variant_of;
+use crate::{Code, CodeVariant, Family, FAMILIES};
+use std::fmt::{self, Write};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::OnceLock;
+
+/// The name of the counter of emitted codes
+pub const METRIC_NAME: &str = "custom_codes_total";
+
+/// Receives every counted code
+pub trait Recorder: Send + Sync {
+    /// Counts one occurrence of `variant` of `family`
+    fn record(&self, family: &Family, variant: &CodeVariant);
+}
+
+/// Counts one occurrence of `code` with `recorder`
+pub fn count<T: Code, R: Recorder + ?Sized>(recorder: &R, code: &T) {
+    if let Some(variant) = variant_of(code) {
+        recorder.record(&Family::of::<T>(), variant);
+    }
+}
+
+static RECORDER: OnceLock<&'static dyn Recorder> = OnceLock::new();
+
+/// Installs the recorder used by `record()` for the whole process, it can only be installed once
+/// ### Examples
+/// ```
+/// use custom_codes::metrics::{record, set_recorder, InMemoryRecorder};
+/// use custom_codes::DbOps;
+///
+/// let recorder: &'static InMemoryRecorder = Box::leak(Box::new(InMemoryRecorder::new()));
+/// set_recorder(recorder).unwrap();
+///
+/// record(&DbOps::QueryConflictError);
+/// assert_eq!(recorder.snapshot().count(&DbOps::QueryConflictError), 1);
+/// ```
+pub fn set_recorder(recorder: &'static dyn Recorder) -> Result<(), SetRecorderError> {
+    RECORDER.set(recorder).map_err(|_| SetRecorderError)
+}
+
+/// Counts one occurrence of `code` with the recorder installed by `set_recorder()`, if any
+pub fn record<T: Code>(code: &T) {
+    if let Some(recorder) = RECORDER.get() {
+        count(*recorder, code);
+    }
+}
+
+/// A recorder was already installed by `set_recorder()`
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct SetRecorderError;
+
+impl fmt::Display for SetRecorderError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "A recorder is already installed")
+    }
+}
+
+impl std::error::Error for SetRecorderError {}
+
+/// Keeps a lock-free counter for every variant of the families it is created with
+#[derive(Debug)]
+pub struct InMemoryRecorder {
+    families: Vec<Family>,
+    /// The index of the counter of variant `0` of every family, the counters of a family are contiguous
+    offsets: Vec<usize>,
+    counters: Vec<AtomicU64>,
+    /// Codes of a family the recorder was not created with
+    unknown: AtomicU64,
+}
+
+impl InMemoryRecorder {
+    /// Counts the codes of every family of the crate
+    pub fn new() -> Self {
+        InMemoryRecorder::with_families(FAMILIES)
+    }
+
+    /// Counts the codes of `families`, such as families declared by an application
+    pub fn with_families(families: &[Family]) -> Self {
+        let mut offsets = Vec::with_capacity(families.len());
+        let mut len = 0;
+        for family in families {
+            offsets.push(len);
+            len += family
+                .variants
+                .iter()
+                .map(|variant| variant.number as usize + 1)
+                .max()
+                .unwrap_or(0);
+        }
+
+        InMemoryRecorder {
+            families: families.to_vec(),
+            offsets,
+            counters: (0..len).map(|_| AtomicU64::new(0)).collect(),
+            unknown: AtomicU64::new(0),
+        }
+    }
+
+    fn counter(&self, family: u16, number: u16) -> Option<&AtomicU64> {
+        let index = self.families.iter().position(|known| known.id == family)?;
+        let end = self
+            .offsets
+            .get(index + 1)
+            .copied()
+            .unwrap_or(self.counters.len());
+
+        self.counters[self.offsets[index]..end].get(number as usize)
+    }
+
+    /// The count of every variant emitted at least once
+    pub fn snapshot(&self) -> Snapshot {
+        let mut counts = Vec::new();
+        for family in &self.families {
+            for variant in family.variants {
+                let count = self
+                    .counter(family.id, variant.number)
+                    .map_or(0, |counter| counter.load(Ordering::Relaxed));
+
+                if count > 0 {
+                    counts.push(CodeCount {
+                        family: family.name,
+                        name: variant.name,
+                        code: family.code(variant),
+                        count,
+                    });
+                }
+            }
+        }
+
+        Snapshot {
+            counts,
+            unknown: self.unknown.load(Ordering::Relaxed),
+        }
+    }
+
+    /// Sets every counter back to zero
+    pub fn reset(&self) {
+        for counter in &self.counters {
+            counter.store(0, Ordering::Relaxed);
+        }
+        self.unknown.store(0, Ordering::Relaxed);
+    }
+}
+
+impl Default for InMemoryRecorder {
+    fn default() -> Self {
+        InMemoryRecorder::new()
+    }
+}
+
+impl Recorder for InMemoryRecorder {
+    fn record(&self, family: &Family, variant: &CodeVariant) {
+        match self.counter(family.id, variant.number) {
+            Some(counter) => counter.fetch_add(1, Ordering::Relaxed),
+            None => self.unknown.fetch_add(1, Ordering::Relaxed),
+        };
+    }
+}
+
+/// The number of times a variant was emitted
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct CodeCount {
+    /// The name of the family
+    pub family: &'static str,
+    /// The name of the variant
+    pub name: &'static str,
+    /// The numeric code of the variant, see `Code::code()`
+    pub code: u32,
+    /// How many times the variant was emitted
+    pub count: u64,
+}
+
+/// The counts of an `InMemoryRecorder` at a point in time
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct Snapshot {
+    /// The count of every variant emitted at least once, ordered like `FAMILIES` and their variants
+    pub counts: Vec<CodeCount>,
+    /// How many codes of families unknown to the recorder were emitted
+    pub unknown: u64,
+}
+
+impl Snapshot {
+    /// How many times the variant of `code` was emitted
+    pub fn count<T: Code>(&self, code: &T) -> u64 {
+        let numeric = code.code();
+
+        self.counts
+            .iter()
+            .find(|count| count.code == numeric)
+            .map_or(0, |count| count.count)
+    }
+
+    /// Renders the counts as a Prometheus counter named `METRIC_NAME`, labelled by `family`, `name` and `code`
+    pub fn to_prometheus(&self) -> String {
+        let mut text = String::new();
+        self.write_prometheus(&mut text)
+            .expect("writing to a `String` does not fail");
+
+        text
+    }
+
+    fn write_prometheus(&self, out: &mut String) -> fmt::Result {
+        writeln!(
+            out,
+            "# HELP {} Codes emitted by family and variant",
+            METRIC_NAME
+        )?;
+        writeln!(out, "# TYPE {} counter", METRIC_NAME)?;
+        for count in &self.counts {
+            writeln!(
+                out,
+                "{}{{family=\"{}\",name=\"{}\",code=\"{}\"}} {}",
+                METRIC_NAME, count.family, count.name, count.code, count.count
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Forwards every counted code to the recorder installed in the `metrics` crate,
+/// as the counter `METRIC_NAME` labelled by `family`, `name` and `code`
+#[cfg(feature = "metrics")]
+#[derive(Debug, Default, Clone, Copy)]
+pub struct MetricsRecorder;
+
+#[cfg(feature = "metrics")]
+impl Recorder for MetricsRecorder {
+    fn record(&self, family: &Family, variant: &CodeVariant) {
+        let code = family.code(variant);
+
+        ::metrics::counter!(
+            METRIC_NAME,
+            "family" => family.name,
+            "name" => variant.name,
+            "code" => code.to_string(),
+        )
+        .increment(1);
+    }
+}
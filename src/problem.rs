@@ -0,0 +1,485 @@
+//! RFC 9457 Problem Details, formerly RFC 7807, describing a code in an `application/problem+json` response.
+//!
+//! The `type` of a problem built from a code is the URI of the documentation of its variant, eg.
+//! `https://docs.rs/custom_codes/2/custom_codes/enum.DbOps.html#variant.DocumentNotFound`, which is stable and
+//! identifies the variant when the problem is parsed back. The families defined by an application are
+//! documented elsewhere, so their problems are built and parsed under the base URI of their documentation
+//! with `from_code_under()` and `to_code_under()`. The `title` is the doc comment of the variant,
+//! the `status` is derived from the variant by `http_status()` and a `String` payload such as the one of
+//! `DbOps::EncounteredErrors` becomes the `detail`. The numeric code is added as the `code` extension member
+//! and the other payloads are kept in the `payload` extension member.
+//! ### Examples
+//! ```
+//! use custom_codes::problem::ProblemDetails;
+//! use custom_codes::DbOps;
+//!
+//! let problem = ProblemDetails::from_code(&DbOps::EncounteredErrors("disk full".into()))
+//!     .with_instance("/databases/users");
+//! let json = serde_json::to_string(&problem).unwrap();
+//!
+//! let parsed: ProblemDetails = serde_json::from_str(&json).unwrap();
+//! assert_eq!(parsed.status, Some(500));
+//! assert_eq!(parsed.detail.as_deref(), Some("disk full"));
+//! assert_eq!(parsed.to_code::<DbOps>().unwrap(), DbOps::EncounteredErrors("disk full".into()));
+//! ```
+
+use crate::code::{named, variant_of};
+use crate::{Code, DownCastErrors, FAMILIES};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::error::Error;
+use std::fmt;
+
+/// The media type of a serialized `ProblemDetails`
+pub const CONTENT_TYPE: &str = "application/problem+json";
+
+/// The prefix of the `type` of the problems describing the families of this crate
+pub const TYPE_BASE: &str = "https://docs.rs/custom_codes/2/custom_codes/";
+
+/// The `type` of a problem which has no other semantics than its HTTP status
+pub const ABOUT_BLANK: &str = "about:blank";
+
+/// A problem detail object as defined by RFC 9457
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct ProblemDetails {
+    /// A URI identifying the problem type
+    #[serde(rename = "type", default = "about_blank")]
+    pub type_uri: String,
+    /// A short summary of the problem type
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    /// The HTTP status code of the response carrying the problem
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status: Option<u16>,
+    /// An explanation specific to this occurrence of the problem
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+    /// A URI identifying this occurrence of the problem
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub instance: Option<String>,
+    /// The extension members of the problem
+    #[serde(flatten)]
+    pub extensions: Map<String, Value>,
+}
+
+fn about_blank() -> String {
+    ABOUT_BLANK.into()
+}
+
+impl ProblemDetails {
+    /// Describes a code of a family of this crate, see the module documentation for how each member is filled.
+    /// The `type` of a code of a family defined by an application is `about:blank`, see `from_code_under()`
+    pub fn from_code<T: Code + Serialize>(code: &T) -> Self {
+        if is_built_in(T::FAMILY_ID) {
+            Self::from_code_under(code, TYPE_BASE)
+        } else {
+            ProblemDetails {
+                type_uri: about_blank(),
+                ..Self::from_code_under(code, TYPE_BASE)
+            }
+        }
+    }
+
+    /// Describes a code whose family is documented under `base`, eg. `https://docs.rs/billing/1/billing/`
+    /// ### Examples
+    /// ```
+    /// use custom_codes::define_codes;
+    /// use custom_codes::problem::ProblemDetails;
+    ///
+    /// define_codes! {
+    ///     /// Outcome of charging a customer
+    ///     #[derive(Debug, PartialEq, Eq, Clone, serde::Serialize, serde::Deserialize)]
+    ///     pub enum Billing = 120 {
+    ///         /// The customer has been charged
+    ///         Charged = 1,
+    ///         /// The card of the customer has been declined
+    ///         CardDeclined = 2,
+    ///     }
+    ///     failures [CardDeclined]
+    ///     http [CardDeclined => 402]
+    /// }
+    ///
+    /// let base = "https://docs.rs/billing/1/billing/";
+    /// let problem = ProblemDetails::from_code_under(&Billing::CardDeclined, base);
+    /// assert_eq!(problem.type_uri, "https://docs.rs/billing/1/billing/enum.Billing.html#variant.CardDeclined");
+    /// assert_eq!(problem.to_code_under::<Billing>(base).unwrap(), Billing::CardDeclined);
+    /// ```
+    pub fn from_code_under<T: Code + Serialize>(code: &T, base: &str) -> Self {
+        let variant = variant_of(code);
+        let mut problem = ProblemDetails {
+            type_uri: type_uri::<T>(base, code.name()),
+            title: variant.map(|variant| variant.description()),
+            status: Some(http_status(code)),
+            detail: None,
+            instance: None,
+            extensions: Map::new(),
+        };
+        problem
+            .extensions
+            .insert("code".into(), Value::from(code.code()));
+
+        let payload = serde_json::to_value(code)
+            .ok()
+            .and_then(|tagged| match tagged {
+                Value::Object(tagged) => tagged.into_iter().next().map(|(_, payload)| payload),
+                _ => None,
+            });
+        match (variant.and_then(|variant| variant.payload), payload) {
+            (Some("String"), Some(Value::String(detail))) => problem.detail = Some(detail),
+            (Some(_), Some(payload)) => {
+                problem.extensions.insert("payload".into(), payload);
+            }
+            _ => (),
+        }
+
+        problem
+    }
+
+    /// Replaces the HTTP status derived from the code
+    pub fn with_status(mut self, status: u16) -> Self {
+        self.status = Some(status);
+        self
+    }
+
+    /// Sets the explanation specific to this occurrence of the problem
+    pub fn with_detail(mut self, detail: impl Into<String>) -> Self {
+        self.detail = Some(detail.into());
+        self
+    }
+
+    /// Sets the URI identifying this occurrence of the problem, eg. the path of the request
+    pub fn with_instance(mut self, instance: impl Into<String>) -> Self {
+        self.instance = Some(instance.into());
+        self
+    }
+
+    /// Adds an extension member, replacing any member with the same name
+    pub fn with_extension(mut self, name: impl Into<String>, value: impl Into<Value>) -> Self {
+        self.extensions.insert(name.into(), value.into());
+        self
+    }
+
+    /// Recovers the code described by the problem from its `type`, and its payload from the `detail`
+    /// or the `payload` extension member
+    pub fn to_code<T: Code + DeserializeOwned>(&self) -> Result<T, ProblemError> {
+        self.to_code_under(TYPE_BASE)
+    }
+
+    /// Recovers a code whose family is documented under `base` like `to_code()`, see `from_code_under()`
+    pub fn to_code_under<T: Code + DeserializeOwned>(&self, base: &str) -> Result<T, ProblemError> {
+        let (family, name) = self
+            .type_uri
+            .strip_prefix(base)
+            .and_then(|path| path.strip_prefix("enum."))
+            .and_then(|path| path.split_once(".html#variant."))
+            .ok_or_else(|| ProblemError::UnknownType(self.type_uri.clone()))?;
+
+        if family != T::FAMILY {
+            return Err(ProblemError::WrongFamily {
+                expected: T::FAMILY,
+                found: family.into(),
+            });
+        }
+
+        let variant =
+            named::<T>(name).ok_or_else(|| ProblemError::UnknownType(self.type_uri.clone()))?;
+
+        let payload = match variant.payload {
+            None => {
+                return T::from_name(name)
+                    .ok_or_else(|| ProblemError::UnknownType(self.type_uri.clone()))
+            }
+            Some("String") => self.detail.clone().map(Value::String),
+            Some(_) => self.extensions.get("payload").cloned(),
+        }
+        .ok_or(ProblemError::MissingDetail)?;
+
+        let mut tagged = Map::new();
+        tagged.insert(name.into(), payload);
+
+        serde_json::from_value(Value::Object(tagged)).map_err(|_| ProblemError::InvalidDetail)
+    }
+}
+
+impl<'se> From<&DownCastErrors<'se>> for ProblemDetails {
+    /// Describes an error classified by `try_downcast`, the error is described by its `type`
+    /// `enum.DownCastErrors.html#variant.<Variant>` which cannot be parsed back into a code
+    ///
+    /// An error carrying a code attached with `coded::WithCode` is described by that code instead,
+    /// its `detail` being the context of the code or else the error the code is attached to.
+    /// The `type` of a code of a family defined by an application is `about:blank`
+    fn from(error: &DownCastErrors<'se>) -> Self {
+        if let DownCastErrors::Coded(coded) = error {
+            let code = coded.any_code();
+            let mut extensions = Map::new();
+            extensions.insert("code".into(), Value::from(code.code()));
+
+            return ProblemDetails {
+                type_uri: if is_built_in((code.code() / 1000) as u16) {
+                    family_type_uri(TYPE_BASE, code.family(), code.name())
+                } else {
+                    about_blank()
+                },
+                title: Some(code.description()),
+                status: Some(code.http_status()),
+                detail: Some(match coded.context() {
+                    Some(context) => context.into(),
+                    None => coded.source().map_or_else(String::new, ToString::to_string),
+                }),
+                instance: None,
+                extensions,
+            };
+        }
+
+        let (name, title, status) = describe(error);
+
+        ProblemDetails {
+            type_uri: format!("{}enum.DownCastErrors.html#variant.{}", TYPE_BASE, name),
+            title: Some(title.into()),
+            status: Some(status),
+            detail: detail(error),
+            instance: None,
+            extensions: Map::new(),
+        }
+    }
+}
+
+/// The `type` of a problem describing the variant `name` of the family `T` documented under `base`
+pub fn type_uri<T: Code>(base: &str, name: &str) -> String {
+    family_type_uri(base, T::FAMILY, name)
+}
+
+fn family_type_uri(base: &str, family: &str, name: &str) -> String {
+    format!("{}enum.{}.html#variant.{}", base, family, name)
+}
+
+/// Whether the family numbered `id` is one of this crate, documented under `TYPE_BASE`
+fn is_built_in(id: u16) -> bool {
+    FAMILIES.iter().any(|family| family.id == id)
+}
+
+/// The HTTP status of a response reporting a code as declared by its family, see `Code::http_status`.
+/// A success is otherwise `200 OK` and a failure `500 Internal Server Error`
+/// ### Examples
+/// ```
+/// use custom_codes::problem::http_status;
+/// use custom_codes::{AccessStatus, DbOps, Networking};
+///
+/// assert_eq!(http_status(&DbOps::DocumentFound), 200);
+/// assert_eq!(http_status(&DbOps::DocumentNotFound), 404);
+/// assert_eq!(http_status(&AccessStatus::Denied), 403);
+/// assert_eq!(http_status(&Networking::NetTimedOut), 504);
+/// ```
+pub fn http_status<T: Code>(code: &T) -> u16 {
+    code.http_status()
+}
+
+/// The name, title and HTTP status of an error classified by `try_downcast`
+fn describe(error: &DownCastErrors<'_>) -> (&'static str, &'static str, u16) {
+    match error {
+        DownCastErrors::NotFound => ("NotFound", "An entity was not found", 404),
+        DownCastErrors::PermissionDenied => (
+            "PermissionDenied",
+            "The operation lacked the necessary privileges to complete",
+            403,
+        ),
+        DownCastErrors::ConnectionRefused => (
+            "ConnectionRefused",
+            "The connection was refused by the remote server",
+            503,
+        ),
+        DownCastErrors::ConnectionReset => (
+            "ConnectionReset",
+            "The connection was reset by the remote server",
+            502,
+        ),
+        DownCastErrors::ConnectionAborted => (
+            "ConnectionAborted",
+            "The connection was aborted by the remote server",
+            502,
+        ),
+        DownCastErrors::NotConnected => (
+            "NotConnected",
+            "The network operation failed because it was not connected yet",
+            503,
+        ),
+        DownCastErrors::AddrInUse => ("AddrInUse", "The address is already in use", 500),
+        DownCastErrors::AddrNotAvailable => {
+            ("AddrNotAvailable", "The address is not available", 500)
+        }
+        DownCastErrors::BrokenPipe => (
+            "BrokenPipe",
+            "The operation failed because a pipe was closed",
+            502,
+        ),
+        DownCastErrors::AlreadyExists => ("AlreadyExists", "An entity already exists", 409),
+        DownCastErrors::WouldBlock => (
+            "WouldBlock",
+            "The operation needs to block to complete",
+            503,
+        ),
+        DownCastErrors::InvalidInput => ("InvalidInput", "A parameter was incorrect", 400),
+        DownCastErrors::InvalidData => (
+            "InvalidData",
+            "Data not valid for the operation were encountered",
+            400,
+        ),
+        DownCastErrors::TimedOut => ("TimedOut", "The operation timed out", 504),
+        DownCastErrors::WriteZero => (
+            "WriteZero",
+            "A write returned without writing any byte",
+            500,
+        ),
+        DownCastErrors::Interrupted => ("Interrupted", "The operation was interrupted", 503),
+        DownCastErrors::Other => ("Other", "An I/O error occurred", 500),
+        DownCastErrors::UnexpectedEof => (
+            "UnexpectedEof",
+            "The end of the input was reached prematurely",
+            400,
+        ),
+        DownCastErrors::Unspecified => ("Unspecified", "This error is not specified yet", 500),
+        DownCastErrors::StripPrefixError(_) => (
+            "StripPrefixError",
+            "A path does not start with the prefix",
+            500,
+        ),
+        DownCastErrors::ParseIntError(_) => {
+            ("ParseIntError", "An integer could not be parsed", 400)
+        }
+        DownCastErrors::ParseFloatError(_) => {
+            ("ParseFloatError", "A float could not be parsed", 400)
+        }
+        DownCastErrors::Utf8Error { .. } => ("Utf8Error", "A byte slice is not valid UTF-8", 400),
+        DownCastErrors::FromUtf8Error { .. } => ("FromUtf8Error", "Bytes are not valid UTF-8", 400),
+        DownCastErrors::AddrParseError(_) => (
+            "AddrParseError",
+            "An IP or socket address could not be parsed",
+            400,
+        ),
+        DownCastErrors::SystemTimeError(_) => (
+            "SystemTimeError",
+            "A time is earlier than the time it was compared to",
+            500,
+        ),
+        DownCastErrors::VarError(_) => (
+            "VarError",
+            "An environment variable is not present or is not valid unicode",
+            500,
+        ),
+        DownCastErrors::NulError { .. } => {
+            ("NulError", "A string contains an interior nul byte", 400)
+        }
+        DownCastErrors::PoisonError => ("PoisonError", "A lock is poisoned", 500),
+        DownCastErrors::TryFromSliceError => (
+            "TryFromSliceError",
+            "A slice does not have the length of the array",
+            400,
+        ),
+        DownCastErrors::Stringify(_) => ("Stringify", "An error occurred", 500),
+        DownCastErrors::BorrowedStr(_) => ("BorrowedStr", "An error occurred", 500),
+        DownCastErrors::InvalidFile => ("InvalidFile", "The file is invalid", 400),
+        DownCastErrors::InvalidFileName => ("InvalidFileName", "Name of a file is invalid", 400),
+        DownCastErrors::InvalidFolder => ("InvalidFolder", "Path is not a directory", 400),
+        DownCastErrors::InvalidPath => ("InvalidPath", "Path is not valid", 400),
+        DownCastErrors::JsonSyntax { .. } => ("JsonSyntax", "The input is not valid JSON", 400),
+        DownCastErrors::JsonData { .. } => (
+            "JsonData",
+            "The JSON input does not match the expected type",
+            422,
+        ),
+        DownCastErrors::JsonEof { .. } => ("JsonEof", "The JSON input ended prematurely", 400),
+        #[cfg(feature = "toml")]
+        DownCastErrors::TomlDe { .. } => {
+            ("TomlDe", "The TOML document could not be deserialized", 400)
+        }
+        #[cfg(feature = "tokio")]
+        DownCastErrors::TokioElapsed => ("TokioElapsed", "A timeout elapsed", 504),
+        #[cfg(feature = "tokio")]
+        DownCastErrors::TokioJoinError { .. } => {
+            ("TokioJoinError", "A task did not run to completion", 500)
+        }
+        #[cfg(feature = "reqwest")]
+        DownCastErrors::Reqwest { .. } => ("Reqwest", "An upstream request failed", 502),
+        #[cfg(feature = "hyper")]
+        DownCastErrors::Hyper(_) => ("Hyper", "An upstream connection failed", 502),
+        #[cfg(feature = "anyhow")]
+        DownCastErrors::Unmatched(_) => ("Unmatched", "An error occurred", 500),
+        DownCastErrors::Coded(_) => ("Coded", "An error carrying a code", 500),
+        DownCastErrors::UnmatchedDyn(_) => ("UnmatchedDyn", "An error occurred", 500),
+    }
+}
+
+/// The explanation of an error classified by `try_downcast` carrying more than its kind
+fn detail(error: &DownCastErrors<'_>) -> Option<String> {
+    let detail = match error {
+        DownCastErrors::StripPrefixError(error) => error.to_string(),
+        DownCastErrors::ParseIntError(kind) => format!("{:?}", kind),
+        DownCastErrors::ParseFloatError(error) => error.to_string(),
+        DownCastErrors::Utf8Error { valid_up_to, .. }
+        | DownCastErrors::FromUtf8Error { valid_up_to, .. } => {
+            format!("Invalid UTF-8 after byte {}", valid_up_to)
+        }
+        DownCastErrors::AddrParseError(error) => error.to_string(),
+        DownCastErrors::VarError(error) => error.to_string(),
+        DownCastErrors::NulError { position, .. } => format!("Nul byte at position {}", position),
+        DownCastErrors::Stringify(message) => message.clone(),
+        DownCastErrors::BorrowedStr(message) => (*message).into(),
+        DownCastErrors::JsonSyntax { line, column }
+        | DownCastErrors::JsonData { line, column }
+        | DownCastErrors::JsonEof { line, column } => {
+            format!("At line {} column {}", line, column)
+        }
+        #[cfg(feature = "toml")]
+        DownCastErrors::TomlDe { message, .. } => message.clone(),
+        #[cfg(feature = "reqwest")]
+        DownCastErrors::Reqwest { url: Some(url), .. } => format!("Request to {} failed", url),
+        #[cfg(feature = "anyhow")]
+        DownCastErrors::Unmatched(error) => error.to_string(),
+        DownCastErrors::Coded(error) => error.to_string(),
+        DownCastErrors::UnmatchedDyn(error) => error.to_string(),
+        _ => return None,
+    };
+
+    Some(detail)
+}
+
+/// The reasons a `ProblemDetails` cannot be converted into a code
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum ProblemError {
+    /// The `type` does not identify a variant built by `ProblemDetails::from_code`
+    UnknownType(String),
+    /// The `type` identifies a variant of another family
+    WrongFamily {
+        /// The family being converted into
+        expected: &'static str,
+        /// The family identified by the `type`
+        found: String,
+    },
+    /// The variant carries a payload but the problem has no `detail` or `payload` member
+    MissingDetail,
+    /// The `detail` or `payload` member is invalid for the variant
+    InvalidDetail,
+}
+
+impl fmt::Display for ProblemError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ProblemError::UnknownType(type_uri) => {
+                write!(f, "The problem type `{}` is not a known code", type_uri)
+            }
+            ProblemError::WrongFamily { expected, found } => write!(
+                f,
+                "Expected a code of family `{}` but found family `{}`",
+                expected, found
+            ),
+            ProblemError::MissingDetail => write!(
+                f,
+                "The variant carries a payload but the problem has no detail"
+            ),
+            ProblemError::InvalidDetail => write!(f, "The detail is invalid for the variant"),
+        }
+    }
+}
+
+impl std::error::Error for ProblemError {}
@@ -0,0 +1,500 @@
+//! Protocol Buffers schema of the code families for services written in other languages.
+//!
+//! `schema()` generates `proto/custom_codes.proto` shipped with the crate. Every family is declared as an enum
+//! holding the stable variant numbers, the zero value `<FAMILY>_UNKNOWN` is left for values which are unset
+//! or unknown to the reader. The `CodeEnvelope` message carries a code of any family together with the payload
+//! of its variant in the `detail` field. Codes of families declared by applications with `define_codes!` are
+//! carried as an `OtherCode` holding the family and variant numbers, and payloads without a typed field as
+//! their binary encoding, see `codec`.
+//!
+//! The `prost` feature adds `CodeEnvelope` as a `prost::Message` which converts from and into any code.
+//! ### Examples
+//! ```
+//! # #[cfg(feature = "prost")]
+//! # {
+//! use custom_codes::proto::CodeEnvelope;
+//! use custom_codes::DbOps;
+//! use prost::Message;
+//!
+//! let envelope = CodeEnvelope::from_code(&DbOps::DbList(vec!["users".into()])).unwrap();
+//! let bytes = envelope.encode_to_vec();
+//!
+//! let decoded = CodeEnvelope::decode(bytes.as_slice()).unwrap();
+//! assert_eq!(decoded.to_code::<DbOps>().unwrap(), DbOps::DbList(vec!["users".into()]));
+//! # }
+//! ```
+
+use crate::code::{screaming_snake_case, SnakeCaseName};
+use crate::FAMILIES;
+use alloc::string::String;
+use core::fmt::{self, Write};
+
+/// The package declared by the schema
+pub const PACKAGE: &str = "custom_codes";
+
+/// A field of the `detail` oneof of `CodeEnvelope`
+struct DetailField {
+    /// The Rust type of the payload, as found in `CodeVariant::payload`
+    payload: &'static str,
+    proto_type: &'static str,
+    name: &'static str,
+    tag: u32,
+}
+
+/// Family numbers are used as the tags of the `code` oneof, the detail tags stay clear of them
+/// and of `OTHER_CODE_TAG`
+const DETAIL_FIELDS: &[DetailField] = &[
+    DetailField {
+        payload: "String",
+        proto_type: "string",
+        name: "text",
+        tag: 101,
+    },
+    DetailField {
+        payload: "Vec<String>",
+        proto_type: "StringList",
+        name: "strings",
+        tag: 102,
+    },
+    DetailField {
+        payload: "Vec<u8>",
+        proto_type: "bytes",
+        name: "contents",
+        tag: 103,
+    },
+    DetailField {
+        payload: "Vec<Vec<u8>>",
+        proto_type: "BytesList",
+        name: "byte_list",
+        tag: 104,
+    },
+    DetailField {
+        payload: "KeyLength",
+        proto_type: "KeyLength",
+        name: "key_size",
+        tag: 105,
+    },
+];
+
+/// The tag of the `code` oneof carrying codes of families without a tag of their own, the family numbers
+/// from `100` are left to applications
+const OTHER_CODE_TAG: u32 = 100;
+
+/// The tag of the `detail` oneof carrying the binary encoding of a payload without a typed field
+const ENCODED_DETAIL_TAG: u32 = 106;
+
+/// Generates the `.proto` schema declaring every code family and the `CodeEnvelope` message
+/// ### Examples
+/// ```
+/// let schema = custom_codes::proto::schema();
+/// assert!(schema.contains("  DB_OPS_DOCUMENT_NOT_FOUND = 25;\n"));
+/// ```
+pub fn schema() -> String {
+    let mut schema = String::new();
+    write_schema(&mut schema).expect("writing to a `String` does not fail");
+
+    schema
+}
+
+fn write_schema(out: &mut String) -> fmt::Result {
+    writeln!(
+        out,
+        "// Generated by `custom_codes::proto::schema()`, do not edit."
+    )?;
+    writeln!(out, "syntax = \"proto3\";")?;
+    writeln!(out)?;
+    writeln!(out, "package {};", PACKAGE)?;
+
+    for family in FAMILIES {
+        let prefix = screaming_snake_case(family.name);
+
+        writeln!(out)?;
+        writeln!(out, "// Family {}", family.id)?;
+        writeln!(out, "enum {} {{", family.name)?;
+        writeln!(out, "  {}_UNKNOWN = 0;", prefix)?;
+        for variant in family.variants {
+            write!(
+                out,
+                "  {}_{} = {};",
+                prefix,
+                screaming_snake_case(variant.name),
+                variant.number
+            )?;
+            if let Some(payload) = variant.payload {
+                let field = detail_field(payload).map_or("encoded", |field| field.name);
+                write!(out, " // detail: {}", field)?;
+            }
+            writeln!(out)?;
+        }
+        writeln!(out, "}}")?;
+    }
+
+    writeln!(out)?;
+    writeln!(out, "message StringList {{")?;
+    writeln!(out, "  repeated string items = 1;")?;
+    writeln!(out, "}}")?;
+    writeln!(out)?;
+    writeln!(out, "message BytesList {{")?;
+    writeln!(out, "  repeated bytes items = 1;")?;
+    writeln!(out, "}}")?;
+    writeln!(out)?;
+    writeln!(out, "// A code of a family declared by an application")?;
+    writeln!(out, "message OtherCode {{")?;
+    writeln!(out, "  uint32 family = 1;")?;
+    writeln!(out, "  int32 number = 2;")?;
+    writeln!(out, "}}")?;
+    writeln!(out)?;
+    writeln!(
+        out,
+        "// A code of any family with the payload carried by its variant"
+    )?;
+    writeln!(out, "message CodeEnvelope {{")?;
+    writeln!(out, "  oneof code {{")?;
+    for family in FAMILIES {
+        writeln!(
+            out,
+            "    {} {} = {};",
+            family.name,
+            SnakeCaseName(family.name),
+            family.id
+        )?;
+    }
+    writeln!(out, "    OtherCode other = {};", OTHER_CODE_TAG)?;
+    writeln!(out, "  }}")?;
+    writeln!(out, "  oneof detail {{")?;
+    for field in DETAIL_FIELDS {
+        writeln!(
+            out,
+            "    {} {} = {};",
+            field.proto_type, field.name, field.tag
+        )?;
+    }
+    writeln!(out, "    bytes encoded = {};", ENCODED_DETAIL_TAG)?;
+    writeln!(out, "  }}")?;
+    writeln!(out, "}}")
+}
+
+fn detail_field(payload: &str) -> Option<&'static DetailField> {
+    DETAIL_FIELDS.iter().find(|field| field.payload == payload)
+}
+
+#[cfg(feature = "prost")]
+pub use self::envelope::*;
+
+#[cfg(feature = "prost")]
+mod envelope {
+    use super::detail_field;
+    use crate::code::numbered;
+    use crate::codec::{self, Binary, RawCode, WirePayload};
+    use crate::{Code, KeyLength};
+    use alloc::{string::String, vec::Vec};
+    use core::convert::TryFrom;
+    use core::fmt;
+
+    /// A code of any family with the payload carried by its variant
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub struct CodeEnvelope {
+        /// The family and variant number of the code
+        #[prost(
+            oneof = "code_envelope::Code",
+            tags = "1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 100"
+        )]
+        pub code: Option<code_envelope::Code>,
+        /// The payload carried by the variant
+        #[prost(oneof = "code_envelope::Detail", tags = "101, 102, 103, 104, 105, 106")]
+        pub detail: Option<code_envelope::Detail>,
+    }
+
+    /// A list of strings carried as a `detail`
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub struct StringList {
+        /// The strings
+        #[prost(string, repeated, tag = "1")]
+        pub items: Vec<String>,
+    }
+
+    /// A list of byte strings carried as a `detail`
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub struct BytesList {
+        /// The byte strings
+        #[prost(bytes = "vec", repeated, tag = "1")]
+        pub items: Vec<Vec<u8>>,
+    }
+
+    /// A code of a family declared by an application
+    #[derive(Clone, Copy, PartialEq, Eq, prost::Message)]
+    pub struct OtherCode {
+        /// The family number
+        #[prost(uint32, tag = "1")]
+        pub family: u32,
+        /// The variant number within the family
+        #[prost(int32, tag = "2")]
+        pub number: i32,
+    }
+
+    /// The oneof fields of `CodeEnvelope`
+    pub mod code_envelope {
+        use super::BytesList;
+        use super::OtherCode;
+        use super::StringList;
+        use alloc::{string::String, vec::Vec};
+        use core::convert::TryFrom;
+
+        macro_rules! envelope_code {
+            ($( $family:ident = $tag:tt, )*) => {
+                /// The family and variant number of a code, proto enums are carried as `i32`
+                #[derive(Clone, Copy, PartialEq, Eq, prost::Oneof)]
+                pub enum Code {
+                    $(
+                        #[doc = concat!("A `", stringify!($family), "` code")]
+                        #[prost(int32, tag = $tag)]
+                        $family(i32),
+                    )*
+                    /// A code of a family declared by an application
+                    #[prost(message, tag = "100")]
+                    Other(OtherCode),
+                }
+
+                impl Code {
+                    pub(super) fn new(family: u16, number: u16) -> Self {
+                        $(
+                            if family == <crate::$family as crate::Code>::FAMILY_ID {
+                                return Code::$family(number as i32);
+                            }
+                        )*
+
+                        Code::Other(OtherCode {
+                            family: family as u32,
+                            number: number as i32,
+                        })
+                    }
+
+                    /// The family number of the code, `u16::MAX` for a number out of range
+                    pub fn family(&self) -> u16 {
+                        match self {
+                            $( Code::$family(_) => <crate::$family as crate::Code>::FAMILY_ID, )*
+                            Code::Other(other) => u16::try_from(other.family).unwrap_or(u16::MAX),
+                        }
+                    }
+
+                    /// The variant number of the code within its family
+                    pub fn number(&self) -> i32 {
+                        match self {
+                            $( Code::$family(number) )|* => *number,
+                            Code::Other(other) => other.number,
+                        }
+                    }
+                }
+            };
+        }
+
+        envelope_code! {
+            Outcome = "1",
+            AccessStatus = "2",
+            CustomBool = "3",
+            DbOps = "4",
+            ExecCommand = "5",
+            FileOps = "6",
+            Compression = "7",
+            Subscription = "8",
+            ActivityStatus = "9",
+            ActivityToggle = "10",
+            Cli = "11",
+            DateTimeOp = "12",
+            SecOps = "13",
+            KeyLength = "14",
+            HardwareResources = "15",
+            Networking = "16",
+            SecHardware = "17",
+        }
+
+        /// The payload carried by a variant
+        #[derive(Clone, PartialEq, prost::Oneof)]
+        pub enum Detail {
+            /// A `String` payload
+            #[prost(string, tag = "101")]
+            Text(String),
+            /// A `Vec<String>` payload
+            #[prost(message, tag = "102")]
+            Strings(StringList),
+            /// A `Vec<u8>` payload
+            #[prost(bytes = "vec", tag = "103")]
+            Contents(Vec<u8>),
+            /// A `Vec<Vec<u8>>` payload
+            #[prost(message, tag = "104")]
+            ByteList(BytesList),
+            /// A `KeyLength` payload
+            #[prost(int32, tag = "105")]
+            KeySize(i32),
+            /// The binary encoding of a payload of another type, see `codec::WirePayload`
+            #[prost(bytes = "vec", tag = "106")]
+            Encoded(Vec<u8>),
+        }
+    }
+
+    use self::code_envelope::Detail;
+
+    impl CodeEnvelope {
+        /// Wraps a code together with the payload of its variant, failing with `ProtoError::InvalidCode`
+        /// when the code does not decode from its own binary encoding
+        pub fn from_code<T: Binary>(code: &T) -> Result<Self, ProtoError> {
+            let encoded = code.encode();
+            let (raw, _) = RawCode::decode(&encoded).map_err(|_| ProtoError::InvalidCode)?;
+            let detail = match payload_of::<T>(code.number()) {
+                Some(payload) => Some(read_detail(payload, &raw).ok_or(ProtoError::InvalidCode)?),
+                None => None,
+            };
+
+            Ok(CodeEnvelope {
+                code: Some(code_envelope::Code::new(T::FAMILY_ID, code.number())),
+                detail,
+            })
+        }
+
+        /// Converts the envelope back into a code of the family of `T`
+        pub fn to_code<T: Binary>(&self) -> Result<T, ProtoError> {
+            let code = self.code.ok_or(ProtoError::MissingCode)?;
+            if code.family() != T::FAMILY_ID {
+                return Err(ProtoError::WrongFamily {
+                    expected: T::FAMILY_ID,
+                    found: code.family(),
+                });
+            }
+
+            let unknown = ProtoError::UnknownVariant {
+                family: code.family(),
+                number: code.number(),
+            };
+            let number = u16::try_from(code.number()).map_err(|_| unknown)?;
+            let variant = numbered::<T>(number).ok_or(unknown)?;
+
+            let mut buffer = Vec::new();
+            match (variant.payload, &self.detail) {
+                (None, None) => codec::write_header(&mut buffer, T::FAMILY_ID, number, false),
+                (None, Some(_)) => return Err(ProtoError::UnexpectedDetail),
+                (Some(_), None) => return Err(ProtoError::MissingDetail),
+                (Some(payload), Some(detail)) => {
+                    write_detail(&mut buffer, T::FAMILY_ID, number, payload, detail)?
+                }
+            }
+
+            T::decode(&buffer)
+                .map(|(code, _)| code)
+                .map_err(|_| ProtoError::InvalidDetail)
+        }
+    }
+
+    fn payload_of<T: Code>(number: u16) -> Option<&'static str> {
+        numbered::<T>(number).and_then(|variant| variant.payload)
+    }
+
+    fn read_detail(payload: &str, raw: &RawCode<'_>) -> Option<Detail> {
+        Some(match payload {
+            "String" => Detail::Text(String::read(raw).ok()?),
+            "Vec<String>" => Detail::Strings(StringList {
+                items: Vec::read(raw).ok()?,
+            }),
+            "Vec<u8>" => Detail::Contents(Vec::read(raw).ok()?),
+            "Vec<Vec<u8>>" => Detail::ByteList(BytesList {
+                items: Vec::read(raw).ok()?,
+            }),
+            "KeyLength" => Detail::KeySize(KeyLength::read(raw).ok()?.number() as i32),
+            _ => Detail::Encoded(raw.payload()?.into()),
+        })
+    }
+
+    fn write_detail(
+        buffer: &mut Vec<u8>,
+        family: u16,
+        number: u16,
+        payload: &str,
+        detail: &Detail,
+    ) -> Result<(), ProtoError> {
+        match (payload, detail) {
+            ("String", Detail::Text(text)) => {
+                codec::write_with_payload(buffer, family, number, text)
+            }
+            ("Vec<String>", Detail::Strings(list)) => {
+                codec::write_with_payload(buffer, family, number, &list.items)
+            }
+            ("Vec<u8>", Detail::Contents(contents)) => {
+                codec::write_with_payload(buffer, family, number, contents)
+            }
+            ("Vec<Vec<u8>>", Detail::ByteList(list)) => {
+                codec::write_with_payload(buffer, family, number, &list.items)
+            }
+            ("KeyLength", Detail::KeySize(key_length)) => {
+                let key_length = u16::try_from(*key_length)
+                    .ok()
+                    .and_then(KeyLength::from_number)
+                    .ok_or(ProtoError::InvalidDetail)?;
+                codec::write_with_payload(buffer, family, number, &key_length)
+            }
+            (payload, Detail::Encoded(encoded)) if detail_field(payload).is_none() => {
+                codec::write_with_payload(buffer, family, number, encoded)
+            }
+            _ => return Err(ProtoError::InvalidDetail),
+        }
+
+        Ok(())
+    }
+
+    /// Errors returned when converting a `CodeEnvelope` into a code
+    #[derive(Debug, PartialEq, Eq, Clone, Copy)]
+    pub enum ProtoError {
+        /// The envelope does not carry a code
+        MissingCode,
+        /// The code belongs to another family than the one being converted into
+        WrongFamily {
+            /// The family number being converted into
+            expected: u16,
+            /// The family number found in the envelope
+            found: u16,
+        },
+        /// The variant number is not known to the family, including the `UNKNOWN` zero value
+        UnknownVariant {
+            /// The family number
+            family: u16,
+            /// The variant number
+            number: i32,
+        },
+        /// The variant carries a payload but the envelope has no detail
+        MissingDetail,
+        /// The variant does not carry a payload but the envelope has a detail
+        UnexpectedDetail,
+        /// The detail does not match the payload carried by the variant
+        InvalidDetail,
+        /// The code does not decode from its own binary encoding
+        InvalidCode,
+    }
+
+    impl fmt::Display for ProtoError {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            match self {
+                ProtoError::MissingCode => write!(f, "The envelope does not carry a code"),
+                ProtoError::WrongFamily { expected, found } => write!(
+                    f,
+                    "Expected a code of family `{}` but found family `{}`",
+                    expected, found
+                ),
+                ProtoError::UnknownVariant { family, number } => {
+                    write!(f, "Variant `{}` is unknown to family `{}`", number, family)
+                }
+                ProtoError::MissingDetail => {
+                    write!(
+                        f,
+                        "The variant carries a payload but the envelope has no detail"
+                    )
+                }
+                ProtoError::UnexpectedDetail => write!(f, "The variant does not carry a payload"),
+                ProtoError::InvalidDetail => write!(f, "The detail is invalid for the variant"),
+                ProtoError::InvalidCode => {
+                    write!(f, "The code does not decode from its own binary encoding")
+                }
+            }
+        }
+    }
+
+    #[cfg(feature = "std")]
+    impl std::error::Error for ProtoError {}
+}
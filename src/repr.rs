@@ -0,0 +1,442 @@
+//! Wrapper types selecting the serde representation of a code.
+//!
+//! The code enums use serde's default externally tagged representation, eg. `"DbFound"` and
+//! `{"EncounteredErrors":"disk full"}`. Wrapping a code in one of these types selects another wire form:
+//!
+//! | Wrapper       | `DbOps::DbFound`    | `DbOps::EncounteredErrors("disk full")`                 |
+//! | ------------- | ------------------- | ------------------------------------------------------- |
+//! | `VariantName` | `"DbFound"`         | error, the payload cannot be represented                |
+//! | `SnakeCase`   | `"db_found"`        | error, the payload cannot be represented                |
+//! | `NumericCode` | `4012`              | error, the payload cannot be represented                |
+//! | `Tagged`      | `{"code":"DbFound"}`| `{"code":"EncounteredErrors","detail":"disk full"}`     |
+//!
+//! `Tagged` reads `code` and `detail` in any order, a `detail` coming first is buffered as a `compat::Payload`
+//! until `code` is read, which requires the `alloc` feature.
+//!
+//! ### Examples
+//! ```
+//! use custom_codes::repr::{NumericCode, SnakeCase, Tagged, VariantName};
+//! use custom_codes::{DbOps, KeyLength, SecOps};
+//!
+//! assert_eq!(serde_json::to_string(&VariantName(DbOps::DbFound)).unwrap(), r#""DbFound""#);
+//! assert_eq!(serde_json::to_string(&SnakeCase(DbOps::DbFound)).unwrap(), r#""db_found""#);
+//! assert_eq!(serde_json::to_string(&NumericCode(DbOps::DbFound)).unwrap(), "4012");
+//!
+//! let json = serde_json::to_string(&Tagged(SecOps::KeyTooShort(KeyLength::Bytes32))).unwrap();
+//! assert_eq!(json, r#"{"code":"KeyTooShort","detail":"Bytes32"}"#);
+//!
+//! let decoded: Tagged<SecOps> = serde_json::from_str(&json).unwrap();
+//! assert_eq!(decoded.0, SecOps::KeyTooShort(KeyLength::Bytes32));
+//!
+//! let decoded: Tagged<DbOps> = serde_json::from_str(r#"{"code":"DbFound"}"#).unwrap();
+//! assert_eq!(decoded.0, DbOps::DbFound);
+//!
+//! # #[cfg(feature = "alloc")]
+//! # {
+//! let decoded: Tagged<SecOps> = serde_json::from_str(r#"{"detail":"Bytes32","code":"KeyTooShort"}"#).unwrap();
+//! assert_eq!(decoded.0, SecOps::KeyTooShort(KeyLength::Bytes32));
+//!
+//! let decoded: Tagged<DbOps> = serde_json::from_str(r#"{"detail":["users"],"code":"DbList"}"#).unwrap();
+//! assert_eq!(decoded.0, DbOps::DbList(vec!["users".into()]));
+//! # }
+//!
+//! let decoded: SnakeCase<DbOps> = serde_json::from_str(r#""db_found""#).unwrap();
+//! assert_eq!(decoded.0, DbOps::DbFound);
+//!
+//! # #[cfg(feature = "alloc")]
+//! assert!(serde_json::to_string(&VariantName(DbOps::EncounteredErrors("disk full".into()))).is_err());
+//! ```
+
+use crate::code::{variant_of, SnakeCaseName};
+#[cfg(feature = "alloc")]
+use crate::compat::Payload;
+use crate::Code;
+use core::fmt;
+use core::marker::PhantomData;
+#[cfg(feature = "alloc")]
+use serde::de::IntoDeserializer;
+use serde::de::{self, DeserializeSeed, EnumAccess, MapAccess, VariantAccess, Visitor};
+use serde::ser::{self, Impossible, SerializeStruct};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// Serializes a code as the name of its variant, eg. `"DocumentNotFound"`
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct VariantName<T>(pub T);
+
+/// Serializes a code as the snake case name of its variant, eg. `"document_not_found"`
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct SnakeCase<T>(pub T);
+
+/// Serializes a code as its stable numeric code, eg. `4025`. See `Code::code()`
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct NumericCode<T>(pub T);
+
+/// Serializes a code adjacently tagged as `{ "code": ..., "detail": ... }`
+/// where `detail` is only present for variants carrying a payload
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Tagged<T>(pub T);
+
+fn payload_error<T: Code, E: ser::Error>(code: &T) -> E {
+    E::custom(format_args!(
+        "`{}::{}` carries a payload, use `Tagged` to serialize it",
+        T::FAMILY,
+        code.name()
+    ))
+}
+
+fn has_payload<T: Code>(code: &T) -> bool {
+    variant_of(code).is_some_and(|variant| variant.payload.is_some())
+}
+
+impl<T: Code> Serialize for VariantName<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if has_payload(&self.0) {
+            return Err(payload_error(&self.0));
+        }
+
+        serializer.serialize_str(self.0.name())
+    }
+}
+
+impl<'de, T: Code> Deserialize<'de> for VariantName<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer
+            .deserialize_str(NameVisitor(T::from_name))
+            .map(VariantName)
+    }
+}
+
+impl<T: Code> Serialize for SnakeCase<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if has_payload(&self.0) {
+            return Err(payload_error(&self.0));
+        }
+
+        serializer.collect_str(&SnakeCaseName(self.0.name()))
+    }
+}
+
+impl<'de, T: Code> Deserialize<'de> for SnakeCase<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer
+            .deserialize_str(NameVisitor(from_snake_case::<T>))
+            .map(SnakeCase)
+    }
+}
+
+impl<T: Code> Serialize for NumericCode<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if has_payload(&self.0) {
+            return Err(payload_error(&self.0));
+        }
+
+        serializer.serialize_u32(self.0.code())
+    }
+}
+
+impl<'de, T: Code> Deserialize<'de> for NumericCode<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let code = u32::deserialize(deserializer)?;
+
+        match T::from_code(code) {
+            Some(found) => Ok(NumericCode(found)),
+            None => Err(de::Error::custom(format_args!(
+                "unknown `{}` code `{}`",
+                T::FAMILY,
+                code
+            ))),
+        }
+    }
+}
+
+fn from_snake_case<T: Code>(input: &str) -> Option<T> {
+    T::VARIANTS
+        .iter()
+        .find(|variant| SnakeCaseName(variant.name).matches(input))
+        .and_then(|variant| T::from_name(variant.name))
+}
+
+struct NameVisitor<T>(fn(&str) -> Option<T>);
+
+impl<'de, T: Code> Visitor<'de> for NameVisitor<T> {
+    type Value = T;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "the name of a `{}` variant", T::FAMILY)
+    }
+
+    fn visit_str<E: de::Error>(self, value: &str) -> Result<T, E> {
+        (self.0)(value)
+            .ok_or_else(|| E::custom(format_args!("unknown `{}` variant `{}`", T::FAMILY, value)))
+    }
+}
+
+impl<T: Serialize> Serialize for Tagged<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.0.serialize(TaggedSerializer(serializer))
+    }
+}
+
+impl<'de, T: Deserialize<'de>> Deserialize<'de> for Tagged<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer
+            .deserialize_struct("Tagged", FIELDS, TaggedVisitor(PhantomData))
+            .map(Tagged)
+    }
+}
+
+const FIELDS: &[&str] = &["code", "detail"];
+
+#[derive(Deserialize)]
+#[serde(field_identifier, rename_all = "lowercase")]
+enum Field {
+    Code,
+    Detail,
+}
+
+/// Intercepts the variant of an externally tagged enum and writes it adjacently tagged instead
+struct TaggedSerializer<S>(S);
+
+macro_rules! unsupported {
+    ($($method:ident($($arg:ty),*) -> $ok:ty;)*) => {
+        $(
+            fn $method(self, $(_: $arg),*) -> Result<$ok, S::Error> {
+                Err(ser::Error::custom("`Tagged` can only serialize enums"))
+            }
+        )*
+    };
+}
+
+impl<S: Serializer> Serializer for TaggedSerializer<S> {
+    type Ok = S::Ok;
+    type Error = S::Error;
+    type SerializeSeq = Impossible<S::Ok, S::Error>;
+    type SerializeTuple = Impossible<S::Ok, S::Error>;
+    type SerializeTupleStruct = Impossible<S::Ok, S::Error>;
+    type SerializeTupleVariant = Impossible<S::Ok, S::Error>;
+    type SerializeMap = Impossible<S::Ok, S::Error>;
+    type SerializeStruct = Impossible<S::Ok, S::Error>;
+    type SerializeStructVariant = Impossible<S::Ok, S::Error>;
+
+    fn serialize_unit_variant(
+        self,
+        name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+    ) -> Result<S::Ok, S::Error> {
+        let mut tagged = self.0.serialize_struct(name, 1)?;
+        tagged.serialize_field("code", variant)?;
+        tagged.end()
+    }
+
+    fn serialize_newtype_variant<T: ?Sized + Serialize>(
+        self,
+        name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        value: &T,
+    ) -> Result<S::Ok, S::Error> {
+        let mut tagged = self.0.serialize_struct(name, 2)?;
+        tagged.serialize_field("code", variant)?;
+        tagged.serialize_field("detail", value)?;
+        tagged.end()
+    }
+
+    fn serialize_newtype_struct<T: ?Sized + Serialize>(
+        self,
+        _name: &'static str,
+        _value: &T,
+    ) -> Result<S::Ok, S::Error> {
+        Err(ser::Error::custom("`Tagged` can only serialize enums"))
+    }
+
+    fn serialize_some<T: ?Sized + Serialize>(self, _value: &T) -> Result<S::Ok, S::Error> {
+        Err(ser::Error::custom("`Tagged` can only serialize enums"))
+    }
+
+    fn collect_str<T: ?Sized + fmt::Display>(self, _value: &T) -> Result<S::Ok, S::Error> {
+        Err(ser::Error::custom("`Tagged` can only serialize enums"))
+    }
+
+    unsupported! {
+        serialize_bool(bool) -> S::Ok;
+        serialize_i8(i8) -> S::Ok;
+        serialize_i16(i16) -> S::Ok;
+        serialize_i32(i32) -> S::Ok;
+        serialize_i64(i64) -> S::Ok;
+        serialize_u8(u8) -> S::Ok;
+        serialize_u16(u16) -> S::Ok;
+        serialize_u32(u32) -> S::Ok;
+        serialize_u64(u64) -> S::Ok;
+        serialize_f32(f32) -> S::Ok;
+        serialize_f64(f64) -> S::Ok;
+        serialize_char(char) -> S::Ok;
+        serialize_str(&str) -> S::Ok;
+        serialize_bytes(&[u8]) -> S::Ok;
+        serialize_none() -> S::Ok;
+        serialize_unit() -> S::Ok;
+        serialize_unit_struct(&'static str) -> S::Ok;
+        serialize_seq(Option<usize>) -> Self::SerializeSeq;
+        serialize_tuple(usize) -> Self::SerializeTuple;
+        serialize_tuple_struct(&'static str, usize) -> Self::SerializeTupleStruct;
+        serialize_tuple_variant(&'static str, u32, &'static str, usize) -> Self::SerializeTupleVariant;
+        serialize_map(Option<usize>) -> Self::SerializeMap;
+        serialize_struct(&'static str, usize) -> Self::SerializeStruct;
+        serialize_struct_variant(&'static str, u32, &'static str, usize) -> Self::SerializeStructVariant;
+    }
+}
+
+struct TaggedVisitor<T>(PhantomData<T>);
+
+impl<'de, T: Deserialize<'de>> Visitor<'de> for TaggedVisitor<T> {
+    type Value = T;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "a map with a `code` and an optional `detail`")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<T, A::Error> {
+        let tagged = match map.next_key::<Field>()? {
+            Some(Field::Code) => TaggedDeserializer::new(&mut map),
+            // The payload is kept until the variant it belongs to is known
+            #[cfg(feature = "alloc")]
+            Some(Field::Detail) => {
+                let detail = map.next_value::<Payload>()?;
+                match map.next_key::<Field>()? {
+                    Some(Field::Code) => (),
+                    Some(Field::Detail) => return Err(de::Error::duplicate_field("detail")),
+                    None => return Err(de::Error::missing_field("code")),
+                }
+
+                TaggedDeserializer {
+                    map: &mut map,
+                    detail: Some(detail),
+                }
+            }
+            #[cfg(not(feature = "alloc"))]
+            Some(Field::Detail) => {
+                return Err(de::Error::custom(
+                    "`code` must come before `detail` without the `alloc` feature",
+                ))
+            }
+            None => return Err(de::Error::missing_field("code")),
+        };
+        let buffered = tagged.is_buffered();
+
+        let value = T::deserialize(tagged)?;
+
+        match map.next_key::<Field>()? {
+            None => Ok(value),
+            Some(Field::Code) => Err(de::Error::duplicate_field("code")),
+            Some(Field::Detail) if buffered => Err(de::Error::duplicate_field("detail")),
+            Some(Field::Detail) => Err(de::Error::custom(
+                "unexpected `detail` for a variant without a payload",
+            )),
+        }
+    }
+}
+
+/// Presents the `code` and `detail` of a map to a derived enum `Deserialize` as an externally tagged enum,
+/// `detail` is read from the map or buffered when it came first
+struct TaggedDeserializer<'a, A> {
+    map: &'a mut A,
+    #[cfg(feature = "alloc")]
+    detail: Option<Payload>,
+}
+
+impl<'a, A> TaggedDeserializer<'a, A> {
+    fn new(map: &'a mut A) -> Self {
+        TaggedDeserializer {
+            map,
+            #[cfg(feature = "alloc")]
+            detail: None,
+        }
+    }
+
+    fn is_buffered(&self) -> bool {
+        #[cfg(feature = "alloc")]
+        return self.detail.is_some();
+        #[cfg(not(feature = "alloc"))]
+        return false;
+    }
+}
+
+impl<'de, 'a, A: MapAccess<'de>> Deserializer<'de> for TaggedDeserializer<'a, A> {
+    type Error = A::Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, _visitor: V) -> Result<V::Value, A::Error> {
+        Err(de::Error::custom("`Tagged` can only deserialize enums"))
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, A::Error> {
+        visitor.visit_enum(self)
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf option unit unit_struct newtype_struct seq tuple
+        tuple_struct map struct identifier ignored_any
+    }
+}
+
+impl<'de, 'a, A: MapAccess<'de>> EnumAccess<'de> for TaggedDeserializer<'a, A> {
+    type Error = A::Error;
+    type Variant = Self;
+
+    fn variant_seed<V: DeserializeSeed<'de>>(self, seed: V) -> Result<(V::Value, Self), A::Error> {
+        let variant = self.map.next_value_seed(seed)?;
+
+        Ok((variant, self))
+    }
+}
+
+impl<'de, 'a, A: MapAccess<'de>> VariantAccess<'de> for TaggedDeserializer<'a, A> {
+    type Error = A::Error;
+
+    fn unit_variant(self) -> Result<(), A::Error> {
+        if self.is_buffered() {
+            return Err(de::Error::custom(
+                "unexpected `detail` for a variant without a payload",
+            ));
+        }
+
+        Ok(())
+    }
+
+    fn newtype_variant_seed<T: DeserializeSeed<'de>>(self, seed: T) -> Result<T::Value, A::Error> {
+        #[cfg(feature = "alloc")]
+        if let Some(detail) = self.detail {
+            return seed.deserialize(detail.into_deserializer());
+        }
+
+        match self.map.next_key::<Field>()? {
+            Some(Field::Detail) => self.map.next_value_seed(seed),
+            _ => Err(de::Error::missing_field("detail")),
+        }
+    }
+
+    fn tuple_variant<V: Visitor<'de>>(
+        self,
+        _len: usize,
+        _visitor: V,
+    ) -> Result<V::Value, A::Error> {
+        Err(de::Error::custom(
+            "`Tagged` only supports unit and newtype variants",
+        ))
+    }
+
+    fn struct_variant<V: Visitor<'de>>(
+        self,
+        _fields: &'static [&'static str],
+        _visitor: V,
+    ) -> Result<V::Value, A::Error> {
+        Err(de::Error::custom(
+            "`Tagged` only supports unit and newtype variants",
+        ))
+    }
+}
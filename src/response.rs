@@ -0,0 +1,153 @@
+//! A uniform response envelope carrying a code together with the data of a successful operation.
+//!
+//! Every `Response` has the same shape whatever the service returning it: the `code`, a human readable `message`
+//! which defaults to the doc comment of the variant, the optional `data`, an optional `trace_id` correlating
+//! the response with its logs, a `timestamp` in milliseconds since the Unix epoch and a list of `warnings`.
+//! ### Examples
+//! ```
+//! use custom_codes::response::Response;
+//! use custom_codes::{DbOps, Outcome};
+//!
+//! let found = Response::ok(vec!["users", "orders"]).with_trace_id("4bf92f35");
+//! assert_eq!(found.code, Outcome::Success);
+//! assert_eq!(found.data, Some(vec!["users", "orders"]));
+//!
+//! let missing = Response::<(), _>::from_code(DbOps::DocumentNotFound)
+//!     .with_warning("the index is being rebuilt");
+//! assert_eq!(missing.message, "Document does not exist");
+//! assert!(!missing.is_success());
+//! ```
+//!
+//! With the `serde` feature the envelope is serialized as an object, leaving out the members which are not set
+//! ```
+//! # #[cfg(feature = "serde")]
+//! # {
+//! use custom_codes::response::Response;
+//! use custom_codes::DbOps;
+//!
+//! let response = Response::<(), _>::from_code(DbOps::DocumentNotFound).with_timestamp(1_700_000_000_000);
+//! assert_eq!(
+//!     serde_json::to_string(&response).unwrap(),
+//!     r#"{"code":"DocumentNotFound","message":"Document does not exist","timestamp":1700000000000}"#
+//! );
+//! # }
+//! ```
+
+use crate::code::variant_of;
+use crate::{Code, Outcome};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// The envelope of a response, `C` is the code reporting the outcome and `T` the data of the response
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Response<T, C = Outcome> {
+    /// The code reporting the outcome of the operation
+    pub code: C,
+    /// A human readable description of the outcome
+    pub message: String,
+    /// The data produced by the operation
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub data: Option<T>,
+    /// An id correlating the response with the logs and traces of the request
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub trace_id: Option<String>,
+    /// When the response was created, in milliseconds since the Unix epoch
+    pub timestamp: u64,
+    /// Problems which did not prevent the operation from completing
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Vec::is_empty")
+    )]
+    pub warnings: Vec<String>,
+}
+
+impl<T> Response<T, Outcome> {
+    /// A successful response carrying `data`, reported by `Outcome::Success`
+    pub fn ok(data: T) -> Self {
+        Response::from_code(Outcome::Success).with_data(data)
+    }
+}
+
+impl<T, C: Code> Response<T, C> {
+    /// A response reporting `code` without data, described by the doc comment of its variant
+    pub fn from_code(code: C) -> Self {
+        let message = variant_of(&code)
+            .map(|variant| variant.description())
+            .unwrap_or_default();
+
+        Response {
+            code,
+            message,
+            data: None,
+            trace_id: None,
+            timestamp: now(),
+            warnings: Vec::new(),
+        }
+    }
+
+    /// Whether the code reports a successful operation, see `Code::is_success`
+    pub fn is_success(&self) -> bool {
+        self.code.is_success()
+    }
+}
+
+impl<T, C> Response<T, C> {
+    /// Sets the data of the response
+    pub fn with_data(mut self, data: T) -> Self {
+        self.data = Some(data);
+        self
+    }
+
+    /// Replaces the message describing the code
+    pub fn with_message(mut self, message: impl Into<String>) -> Self {
+        self.message = message.into();
+        self
+    }
+
+    /// Sets the id correlating the response with the logs and traces of the request
+    pub fn with_trace_id(mut self, trace_id: impl Into<String>) -> Self {
+        self.trace_id = Some(trace_id.into());
+        self
+    }
+
+    /// Replaces the time the response was created, in milliseconds since the Unix epoch
+    pub fn with_timestamp(mut self, timestamp: u64) -> Self {
+        self.timestamp = timestamp;
+        self
+    }
+
+    /// Adds a warning to the response
+    pub fn with_warning(mut self, warning: impl Into<String>) -> Self {
+        self.warnings.push(warning.into());
+        self
+    }
+
+    /// Converts the data of the response, keeping the other members
+    pub fn map<U>(self, f: impl FnOnce(T) -> U) -> Response<U, C> {
+        Response {
+            code: self.code,
+            message: self.message,
+            data: self.data.map(f),
+            trace_id: self.trace_id,
+            timestamp: self.timestamp,
+            warnings: self.warnings,
+        }
+    }
+}
+
+/// Milliseconds since the Unix epoch, `0` if the clock is set before it
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_millis() as u64)
+        .unwrap_or_default()
+}
@@ -0,0 +1,251 @@
+//! Classifies codes and errors as transient so an operation reporting them can be retried.
+//!
+//! Every variant declares a `RetryHint` read with `Retryable::retry_hint()`, eg. `Networking::NetTimedOut`
+//! is retried with backoff while `SecOps::KeyInvalid` is never retried. `DownCastErrors` are classified
+//! from the kind of error they mirror.
+//!
+//! With the `std` feature, `retry()` runs an operation until it succeeds, fails with an error which is not
+//! retryable or runs out of attempts, waiting between attempts as told by the hint of the error and a `Backoff`.
+//! ### Examples
+//! ```
+//! use custom_codes::{DbOps, Networking, RetryHint, Retryable, SecOps};
+//!
+//! assert_eq!(Networking::NetTimedOut.retry_hint(), RetryHint::WithBackoff);
+//! assert_eq!(DbOps::ConnRefused.retry_hint(), RetryHint::AfterReconnect);
+//! assert!(DbOps::QueryConflictError.is_retryable());
+//! assert!(!SecOps::KeyInvalid.is_retryable());
+//! ```
+
+use crate::code::variant_of;
+use crate::Code;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Whether and how an operation can be retried after it reported a code
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum RetryHint {
+    /// Retrying cannot succeed without changing the request
+    Never,
+    /// The failure is transient and the operation can be retried at once
+    Immediately,
+    /// The failure is transient but the peer needs time to recover, wait longer before every attempt
+    WithBackoff,
+    /// The connection is lost, reconnect before retrying
+    AfterReconnect,
+}
+
+impl RetryHint {
+    /// Whether the operation can be retried at all
+    pub const fn is_retryable(self) -> bool {
+        !matches!(self, RetryHint::Never)
+    }
+}
+
+/// Codes and errors which tell whether the operation reporting them can be retried
+pub trait Retryable {
+    /// Whether and how the operation can be retried
+    fn retry_hint(&self) -> RetryHint;
+
+    /// Whether the operation can be retried
+    fn is_retryable(&self) -> bool {
+        self.retry_hint().is_retryable()
+    }
+}
+
+impl<C: Code> Retryable for C {
+    fn retry_hint(&self) -> RetryHint {
+        variant_of(self).map_or(RetryHint::Never, |variant| variant.retry)
+    }
+}
+
+/// ### Examples
+/// ```
+/// use custom_codes::{try_downcast_dyn, DownCastErrors, RetryHint, Retryable};
+/// use std::io::{Error, ErrorKind};
+///
+/// let error = Error::from(ErrorKind::WouldBlock);
+/// assert_eq!(try_downcast_dyn(&error).retry_hint(), RetryHint::Immediately);
+/// assert!(!DownCastErrors::PermissionDenied.is_retryable());
+/// ```
+#[cfg(feature = "std")]
+impl Retryable for crate::DownCastErrors<'_> {
+    fn retry_hint(&self) -> RetryHint {
+        use crate::DownCastErrors;
+
+        match self {
+            DownCastErrors::Coded(coded) => coded.any_code().retry_hint(),
+            DownCastErrors::WouldBlock | DownCastErrors::Interrupted => RetryHint::Immediately,
+            DownCastErrors::ConnectionRefused | DownCastErrors::TimedOut => RetryHint::WithBackoff,
+            DownCastErrors::ConnectionReset
+            | DownCastErrors::ConnectionAborted
+            | DownCastErrors::NotConnected
+            | DownCastErrors::BrokenPipe => RetryHint::AfterReconnect,
+            // A poisoned lock stays poisoned, an address in use is a configuration error and
+            // an unexpected end of file is as likely to be a truncated file as a closed connection
+            DownCastErrors::PoisonError
+            | DownCastErrors::AddrInUse
+            | DownCastErrors::UnexpectedEof => RetryHint::Never,
+            #[cfg(feature = "tokio")]
+            DownCastErrors::TokioElapsed => RetryHint::WithBackoff,
+            #[cfg(feature = "reqwest")]
+            DownCastErrors::Reqwest { kind, status, .. } => match status {
+                Some(408) | Some(429) | Some(502) | Some(503) | Some(504) => RetryHint::WithBackoff,
+                Some(_) => RetryHint::Never,
+                None => http_retry_hint(*kind),
+            },
+            #[cfg(feature = "hyper")]
+            DownCastErrors::Hyper(kind) => http_retry_hint(*kind),
+            _ => RetryHint::Never,
+        }
+    }
+}
+
+#[cfg(any(feature = "reqwest", feature = "hyper"))]
+fn http_retry_hint(kind: crate::HttpErrorKind) -> RetryHint {
+    use crate::HttpErrorKind;
+
+    match kind {
+        HttpErrorKind::Connect | HttpErrorKind::Timeout => RetryHint::WithBackoff,
+        HttpErrorKind::Closed | HttpErrorKind::IncompleteMessage => RetryHint::AfterReconnect,
+        _ => RetryHint::Never,
+    }
+}
+
+#[cfg(feature = "std")]
+pub use self::backoff::{retry, Attempt, Backoff};
+
+#[cfg(feature = "std")]
+mod backoff {
+    use super::{RetryHint, Retryable};
+    use std::collections::hash_map::RandomState;
+    use std::hash::{BuildHasher, Hasher};
+    use std::time::Duration;
+
+    /// How long `retry()` waits between attempts of an operation failing with `RetryHint::WithBackoff`
+    /// or `RetryHint::AfterReconnect`, the delay is multiplied after every attempt up to `max`
+    #[derive(Debug, PartialEq, Eq, Clone, Copy)]
+    pub struct Backoff {
+        /// The delay before the first retry
+        pub initial: Duration,
+        /// The longest delay between two attempts
+        pub max: Duration,
+        /// How much the delay grows after every retry
+        pub multiplier: u32,
+        /// The number of attempts, including the first one, before giving up
+        pub max_attempts: u32,
+        /// Wait a random delay between half of the computed delay and all of it, so clients failing
+        /// together do not retry together
+        pub jitter: bool,
+    }
+
+    impl Default for Backoff {
+        /// Five attempts waiting 100 milliseconds then doubling up to 10 seconds, with jitter
+        fn default() -> Self {
+            Backoff {
+                initial: Duration::from_millis(100),
+                max: Duration::from_secs(10),
+                multiplier: 2,
+                max_attempts: 5,
+                jitter: true,
+            }
+        }
+    }
+
+    impl Backoff {
+        /// The delay before retry number `retry`, starting at `0`, without jitter.
+        /// Useful to drive the retries of asynchronous operations
+        /// ### Examples
+        /// ```
+        /// use custom_codes::retry::Backoff;
+        /// use std::time::Duration;
+        ///
+        /// let backoff = Backoff { jitter: false, ..Backoff::default() };
+        /// assert_eq!(backoff.delay(0), Duration::from_millis(100));
+        /// assert_eq!(backoff.delay(3), Duration::from_millis(800));
+        /// assert_eq!(backoff.delay(20), Duration::from_secs(10));
+        /// ```
+        pub fn delay(&self, retry: u32) -> Duration {
+            let factor = self.multiplier.checked_pow(retry).unwrap_or(u32::MAX);
+
+            self.initial
+                .checked_mul(factor)
+                .map_or(self.max, |delay| delay.min(self.max))
+        }
+
+        /// The delay before retry number `retry` with jitter applied when enabled
+        pub fn jittered_delay(&self, retry: u32) -> Duration {
+            let delay = self.delay(retry);
+            if !self.jitter {
+                return delay;
+            }
+
+            let half = delay / 2;
+            let random = RandomState::new().build_hasher().finish();
+            let nanos = half.as_nanos() as u64;
+
+            half + Duration::from_nanos(random.checked_rem(nanos + 1).unwrap_or(0))
+        }
+    }
+
+    /// An attempt of the operation run by `retry()`
+    #[derive(Debug, PartialEq, Eq, Clone, Copy)]
+    pub struct Attempt {
+        /// The number of the attempt, starting at `1`
+        pub number: u32,
+        /// The previous attempt failed with `RetryHint::AfterReconnect`, reconnect before trying again
+        pub reconnect: bool,
+    }
+
+    /// Runs `operation` until it succeeds, fails with an error which is not retryable or `backoff` runs out
+    /// of attempts, then returns its last result. An error hinting `Immediately` is retried at once,
+    /// one hinting `WithBackoff` or `AfterReconnect` is retried after the delay of `backoff`
+    /// ### Examples
+    /// ```
+    /// use custom_codes::retry::{retry, Backoff};
+    /// use custom_codes::Networking;
+    /// use std::time::Duration;
+    ///
+    /// let backoff = Backoff { initial: Duration::from_millis(1), ..Backoff::default() };
+    /// let result = retry(&backoff, |attempt| {
+    ///     if attempt.number < 3 {
+    ///         Err(Networking::NetTimedOut)
+    ///     } else {
+    ///         Ok(attempt.number)
+    ///     }
+    /// });
+    /// assert_eq!(result, Ok(3));
+    /// ```
+    pub fn retry<T, E: Retryable>(
+        backoff: &Backoff,
+        mut operation: impl FnMut(&Attempt) -> Result<T, E>,
+    ) -> Result<T, E> {
+        let mut attempt = Attempt {
+            number: 1,
+            reconnect: false,
+        };
+        let mut retries = 0;
+
+        loop {
+            let error = match operation(&attempt) {
+                Ok(value) => return Ok(value),
+                Err(error) => error,
+            };
+
+            let hint = error.retry_hint();
+            if !hint.is_retryable() || attempt.number >= backoff.max_attempts {
+                return Err(error);
+            }
+
+            if hint != RetryHint::Immediately {
+                std::thread::sleep(backoff.jittered_delay(retries));
+                retries += 1;
+            }
+            attempt = Attempt {
+                number: attempt.number + 1,
+                reconnect: hint == RetryHint::AfterReconnect,
+            };
+        }
+    }
+}
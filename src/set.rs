@@ -0,0 +1,453 @@
+//! A set of the variants of a code family stored as a bitset, without allocating.
+//!
+//! `CodeSet<E>` keeps one bit per variant number so membership, set algebra and iteration are cheap and
+//! constant sets can be declared with `CodeSet::from_names()`, eg. the statuses accepted by a filter or
+//! the `HardwareResources` flags raised by a device. A variant carrying a payload is a member whatever
+//! its payload, `iter()` yields only the variants without a payload while `variants()` describes them all.
+//! With the `serde` feature a set serializes as the list of the names of its variants.
+//! ### Examples
+//! ```
+//! use custom_codes::{AccessStatus, CodeSet};
+//!
+//! const ACCEPTED: CodeSet<AccessStatus> = CodeSet::from_names(&["Granted", "InProgress"]);
+//!
+//! assert!(ACCEPTED.contains(&AccessStatus::Granted));
+//! assert!(!ACCEPTED.contains(&AccessStatus::Denied));
+//!
+//! let seen: CodeSet<AccessStatus> = vec![AccessStatus::Denied, AccessStatus::Granted].into_iter().collect();
+//! assert_eq!((seen & ACCEPTED).iter().collect::<Vec<_>>(), [AccessStatus::Granted]);
+//! assert_eq!((seen - ACCEPTED).len(), 1);
+//! ```
+
+use crate::code::equals;
+use crate::{Code, CodeVariant};
+use core::fmt;
+use core::hash::{Hash, Hasher};
+use core::iter::FromIterator;
+use core::marker::PhantomData;
+use core::ops::{BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor, Not, Sub};
+
+/// A set of the variants of the family `E`, see the module documentation.
+/// Variant numbers must be below `CodeSet::CAPACITY`, building a set of a family with a larger number
+/// fails to compile
+/// ```compile_fail
+/// use custom_codes::{define_codes, CodeSet};
+///
+/// define_codes! {
+///     /// Shipping of a parcel
+///     #[derive(Debug)]
+///     enum Shipping = 130 {
+///         Shipped = 1,
+///         Lost = 404,
+///     }
+/// }
+///
+/// let shipped = CodeSet::<Shipping>::new();
+/// ```
+pub struct CodeSet<E> {
+    bits: u128,
+    family: PhantomData<fn() -> E>,
+}
+
+impl<E: Code> CodeSet<E> {
+    /// The variant numbers a set can hold are below this number
+    pub const CAPACITY: u16 = 128;
+
+    /// Evaluated when a set of the family is built, so a variant number which does not fit fails the build
+    const FITS: () = {
+        let mut index = 0;
+        while index < E::VARIANTS.len() {
+            assert!(
+                E::VARIANTS[index].number < Self::CAPACITY,
+                "a variant number of the family does not fit in a `CodeSet`"
+            );
+            index += 1;
+        }
+    };
+
+    const fn from_bits(bits: u128) -> Self {
+        let () = Self::FITS;
+        CodeSet {
+            bits,
+            family: PhantomData,
+        }
+    }
+
+    const fn bit(number: u16) -> u128 {
+        1 << number
+    }
+
+    /// The set without any variant
+    pub const fn new() -> Self {
+        Self::from_bits(0)
+    }
+
+    /// The set of every variant of the family
+    pub const fn all() -> Self {
+        let mut bits = 0;
+        let mut index = 0;
+        while index < E::VARIANTS.len() {
+            bits |= Self::bit(E::VARIANTS[index].number);
+            index += 1;
+        }
+
+        Self::from_bits(bits)
+    }
+
+    /// The set of the variants named `names`, deprecated names are accepted.
+    /// Panics, at compile time in a constant, when a name is not a variant of the family
+    pub const fn from_names(names: &[&str]) -> Self {
+        let mut bits = 0;
+        let mut index = 0;
+        while index < names.len() {
+            match Self::number_of(names[index]) {
+                Some(number) => bits |= Self::bit(number),
+                None => panic!("a name is not a variant of the family"),
+            }
+            index += 1;
+        }
+
+        Self::from_bits(bits)
+    }
+
+    /// The set of the variants numbered `numbers`.
+    /// Panics, at compile time in a constant, when a number is not a variant of the family
+    pub const fn from_numbers(numbers: &[u16]) -> Self {
+        let mut bits = 0;
+        let mut index = 0;
+        while index < numbers.len() {
+            assert!(
+                Self::variant(numbers[index]).is_some(),
+                "a number is not a variant of the family"
+            );
+            bits |= Self::bit(numbers[index]);
+            index += 1;
+        }
+
+        Self::from_bits(bits)
+    }
+
+    const fn variant(number: u16) -> Option<&'static CodeVariant> {
+        let mut index = 0;
+        while index < E::VARIANTS.len() {
+            if E::VARIANTS[index].number == number {
+                return Some(&E::VARIANTS[index]);
+            }
+            index += 1;
+        }
+
+        None
+    }
+
+    const fn number_of(name: &str) -> Option<u16> {
+        let mut index = 0;
+        while index < E::VARIANTS.len() {
+            if equals(E::VARIANTS[index].name, name) {
+                return Some(E::VARIANTS[index].number);
+            }
+            index += 1;
+        }
+
+        let mut index = 0;
+        while index < E::ALIASES.len() {
+            if equals(E::ALIASES[index].name, name) {
+                return Self::number_of(E::ALIASES[index].canonical);
+            }
+            index += 1;
+        }
+
+        None
+    }
+
+    /// Whether the variant of `code` is in the set
+    pub fn contains(&self, code: &E) -> bool {
+        self.contains_number(code.number())
+    }
+
+    /// Whether the variant numbered `number` is in the set
+    pub const fn contains_number(&self, number: u16) -> bool {
+        number < Self::CAPACITY && self.bits & (1 << number) != 0
+    }
+
+    /// Adds the variant of `code`, returns whether it was not in the set yet
+    pub fn insert(&mut self, code: &E) -> bool {
+        let bit = Self::bit(code.number());
+        let added = self.bits & bit == 0;
+        self.bits |= bit;
+
+        added
+    }
+
+    /// Removes the variant of `code`, returns whether it was in the set
+    pub fn remove(&mut self, code: &E) -> bool {
+        let bit = Self::bit(code.number());
+        let removed = self.bits & bit != 0;
+        self.bits &= !bit;
+
+        removed
+    }
+
+    /// The number of variants in the set
+    pub const fn len(&self) -> usize {
+        self.bits.count_ones() as usize
+    }
+
+    /// Whether the set has no variant
+    pub const fn is_empty(&self) -> bool {
+        self.bits == 0
+    }
+
+    /// The variants in either set
+    pub const fn union(self, other: Self) -> Self {
+        Self::from_bits(self.bits | other.bits)
+    }
+
+    /// The variants in both sets
+    pub const fn intersection(self, other: Self) -> Self {
+        Self::from_bits(self.bits & other.bits)
+    }
+
+    /// The variants in this set but not in `other`
+    pub const fn difference(self, other: Self) -> Self {
+        Self::from_bits(self.bits & !other.bits)
+    }
+
+    /// The variants in exactly one of the sets
+    pub const fn symmetric_difference(self, other: Self) -> Self {
+        Self::from_bits(self.bits ^ other.bits)
+    }
+
+    /// The variants of the family not in the set
+    pub const fn complement(self) -> Self {
+        Self::all().difference(self)
+    }
+
+    /// Whether every variant of the set is in `other`
+    pub const fn is_subset(&self, other: &Self) -> bool {
+        self.bits & !other.bits == 0
+    }
+
+    /// Whether every variant of `other` is in the set
+    pub const fn is_superset(&self, other: &Self) -> bool {
+        other.is_subset(self)
+    }
+
+    /// Whether the sets have no variant in common
+    pub const fn is_disjoint(&self, other: &Self) -> bool {
+        self.bits & other.bits == 0
+    }
+
+    /// The description of every variant in the set, ordered by number
+    pub fn variants(&self) -> impl Iterator<Item = &'static CodeVariant> + '_ {
+        self.numbers().filter_map(Self::variant)
+    }
+
+    /// The variants in the set which carry no payload, ordered by number
+    pub fn iter(&self) -> impl Iterator<Item = E> + '_ {
+        self.numbers().filter_map(E::from_number)
+    }
+
+    fn numbers(&self) -> impl Iterator<Item = u16> + '_ {
+        let mut bits = self.bits;
+
+        core::iter::from_fn(move || {
+            if bits == 0 {
+                return None;
+            }
+            let number = bits.trailing_zeros() as u16;
+            bits &= bits - 1;
+
+            Some(number)
+        })
+    }
+}
+
+impl<E> Clone for CodeSet<E> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<E> Copy for CodeSet<E> {}
+
+impl<E> PartialEq for CodeSet<E> {
+    fn eq(&self, other: &Self) -> bool {
+        self.bits == other.bits
+    }
+}
+
+impl<E> Eq for CodeSet<E> {}
+
+impl<E> Hash for CodeSet<E> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.bits.hash(state);
+    }
+}
+
+impl<E: Code> Default for CodeSet<E> {
+    fn default() -> Self {
+        CodeSet::new()
+    }
+}
+
+impl<E: Code> fmt::Debug for CodeSet<E> {
+    /// Writes the names of the variants, eg. `{Granted, InProgress}`
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        struct Name(&'static str);
+
+        impl fmt::Debug for Name {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str(self.0)
+            }
+        }
+
+        f.debug_set()
+            .entries(self.variants().map(|variant| Name(variant.name)))
+            .finish()
+    }
+}
+
+impl<E: Code> BitOr for CodeSet<E> {
+    type Output = Self;
+
+    fn bitor(self, other: Self) -> Self {
+        self.union(other)
+    }
+}
+
+impl<E: Code> BitOrAssign for CodeSet<E> {
+    fn bitor_assign(&mut self, other: Self) {
+        *self = self.union(other);
+    }
+}
+
+impl<E: Code> BitAnd for CodeSet<E> {
+    type Output = Self;
+
+    fn bitand(self, other: Self) -> Self {
+        self.intersection(other)
+    }
+}
+
+impl<E: Code> BitAndAssign for CodeSet<E> {
+    fn bitand_assign(&mut self, other: Self) {
+        *self = self.intersection(other);
+    }
+}
+
+impl<E: Code> BitXor for CodeSet<E> {
+    type Output = Self;
+
+    fn bitxor(self, other: Self) -> Self {
+        self.symmetric_difference(other)
+    }
+}
+
+impl<E: Code> Sub for CodeSet<E> {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        self.difference(other)
+    }
+}
+
+impl<E: Code> Not for CodeSet<E> {
+    type Output = Self;
+
+    fn not(self) -> Self {
+        self.complement()
+    }
+}
+
+impl<E: Code> Extend<E> for CodeSet<E> {
+    fn extend<I: IntoIterator<Item = E>>(&mut self, codes: I) {
+        for code in codes {
+            self.insert(&code);
+        }
+    }
+}
+
+impl<'a, E: Code> Extend<&'a E> for CodeSet<E> {
+    fn extend<I: IntoIterator<Item = &'a E>>(&mut self, codes: I) {
+        for code in codes {
+            self.insert(code);
+        }
+    }
+}
+
+impl<E: Code> FromIterator<E> for CodeSet<E> {
+    fn from_iter<I: IntoIterator<Item = E>>(codes: I) -> Self {
+        let mut set = CodeSet::new();
+        set.extend(codes);
+        set
+    }
+}
+
+impl<'a, E: Code> FromIterator<&'a E> for CodeSet<E> {
+    fn from_iter<I: IntoIterator<Item = &'a E>>(codes: I) -> Self {
+        let mut set = CodeSet::new();
+        set.extend(codes);
+        set
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<E: Code> serde::Serialize for CodeSet<E> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.variants().map(|variant| variant.name))
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, E: Code> serde::Deserialize<'de> for CodeSet<E> {
+    /// Reads a list of names, deprecated names are accepted
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        use serde::de::{self, SeqAccess, Visitor};
+
+        /// The number of the variant named by a string
+        struct Number<E>(u16, PhantomData<fn() -> E>);
+
+        impl<'de, E: Code> serde::Deserialize<'de> for Number<E> {
+            fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                struct Name<E>(PhantomData<fn() -> E>);
+
+                impl<E: Code> Visitor<'_> for Name<E> {
+                    type Value = Number<E>;
+
+                    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                        write!(f, "a variant of `{}`", E::FAMILY)
+                    }
+
+                    fn visit_str<Error: de::Error>(self, name: &str) -> Result<Self::Value, Error> {
+                        CodeSet::<E>::number_of(name)
+                            .map(|number| Number(number, PhantomData))
+                            .ok_or_else(|| Error::invalid_value(de::Unexpected::Str(name), &self))
+                    }
+                }
+
+                deserializer.deserialize_str(Name(PhantomData))
+            }
+        }
+
+        struct Names<E>(PhantomData<fn() -> E>);
+
+        impl<'de, E: Code> Visitor<'de> for Names<E> {
+            type Value = CodeSet<E>;
+
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                write!(f, "a list of variants of `{}`", E::FAMILY)
+            }
+
+            fn visit_seq<A: SeqAccess<'de>>(self, mut names: A) -> Result<Self::Value, A::Error> {
+                let mut set = CodeSet::new();
+                while let Some(Number(number, _)) = names.next_element::<Number<E>>()? {
+                    set.bits |= CodeSet::<E>::bit(number);
+                }
+
+                Ok(set)
+            }
+        }
+
+        deserializer.deserialize_seq(Names(PhantomData))
+    }
+}
@@ -0,0 +1,189 @@
+//! Severity levels a code is logged at.
+//!
+//! Every variant declares a `Severity`, read with `Code::severity()`. A successful variant is `Debug` and
+//! a failure is `Error` unless its family says otherwise, eg. `SecOps::PossibleIdTheft` is `Alert`.
+//! A severity maps to an RFC 5424 syslog severity, to a `log::Level` with the `log` feature
+//! and to a `tracing::Level` with the `tracing` feature.
+//!
+//! An application logging some codes at another level builds `Overrides`, and with the `std` feature
+//! can install them for the whole process so `effective()` picks them up.
+//! ### Examples
+//! ```
+//! use custom_codes::{Code, DbOps, Severity};
+//!
+//! assert_eq!(DbOps::DocumentNotFound.severity(), Severity::Warning);
+//!
+//! # #[cfg(feature = "alloc")]
+//! # {
+//! use custom_codes::severity::Overrides;
+//!
+//! let overrides = Overrides::new().with(&DbOps::DocumentNotFound, Severity::Info);
+//! assert_eq!(overrides.severity(&DbOps::DocumentNotFound), Severity::Info);
+//! assert_eq!(overrides.severity(&DbOps::DbIntegrityCorrupted), Severity::Critical);
+//! # }
+//! ```
+
+#[cfg(feature = "alloc")]
+use crate::Code;
+use core::fmt;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// How severe a code is, ordered from the least to the most severe
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Severity {
+    /// Fine grained progress of an operation
+    Trace,
+    /// Routine outcome of an operation, only useful when debugging
+    Debug,
+    /// Outcome worth recording during normal operation
+    Info,
+    /// Normal but significant condition
+    Notice,
+    /// Unexpected condition which did not prevent the operation from being handled
+    Warning,
+    /// The operation failed
+    Error,
+    /// A critical condition such as corrupted data
+    Critical,
+    /// Action must be taken immediately, eg. a possible identity theft
+    Alert,
+}
+
+impl Severity {
+    /// The numeric severity of RFC 5424 syslog messages, `Trace` is reported as `Debug`
+    /// ### Examples
+    /// ```
+    /// # use custom_codes::Severity;
+    /// assert_eq!(Severity::Alert.syslog(), 1);
+    /// assert_eq!(Severity::Debug.syslog(), 7);
+    /// ```
+    pub const fn syslog(self) -> u8 {
+        match self {
+            Severity::Alert => 1,
+            Severity::Critical => 2,
+            Severity::Error => 3,
+            Severity::Warning => 4,
+            Severity::Notice => 5,
+            Severity::Info => 6,
+            Severity::Debug | Severity::Trace => 7,
+        }
+    }
+
+    /// The name of the severity, eg. `Warning`
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Severity::Trace => "Trace",
+            Severity::Debug => "Debug",
+            Severity::Info => "Info",
+            Severity::Notice => "Notice",
+            Severity::Warning => "Warning",
+            Severity::Error => "Error",
+            Severity::Critical => "Critical",
+            Severity::Alert => "Alert",
+        }
+    }
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// `Notice` is logged as `Info` while `Critical` and `Alert` are logged as `Error`
+#[cfg(feature = "log")]
+impl From<Severity> for log::Level {
+    fn from(severity: Severity) -> Self {
+        match severity {
+            Severity::Trace => log::Level::Trace,
+            Severity::Debug => log::Level::Debug,
+            Severity::Info | Severity::Notice => log::Level::Info,
+            Severity::Warning => log::Level::Warn,
+            Severity::Error | Severity::Critical | Severity::Alert => log::Level::Error,
+        }
+    }
+}
+
+/// `Notice` is traced as `INFO` while `Critical` and `Alert` are traced as `ERROR`
+#[cfg(feature = "tracing")]
+impl From<Severity> for tracing::Level {
+    fn from(severity: Severity) -> Self {
+        match severity {
+            Severity::Trace => tracing::Level::TRACE,
+            Severity::Debug => tracing::Level::DEBUG,
+            Severity::Info | Severity::Notice => tracing::Level::INFO,
+            Severity::Warning => tracing::Level::WARN,
+            Severity::Error | Severity::Critical | Severity::Alert => tracing::Level::ERROR,
+        }
+    }
+}
+
+/// Severities an application gives to some codes instead of the ones declared by their families
+#[cfg(feature = "alloc")]
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct Overrides {
+    severities: alloc::collections::BTreeMap<u32, Severity>,
+}
+
+#[cfg(feature = "alloc")]
+impl Overrides {
+    /// No overrides, every code keeps the severity declared by its family
+    pub fn new() -> Self {
+        Overrides::default()
+    }
+
+    /// Logs every occurrence of the variant of `code` at `severity`, whatever its payload
+    pub fn with<T: Code>(mut self, code: &T, severity: Severity) -> Self {
+        self.severities.insert(code.code(), severity);
+        self
+    }
+
+    /// Logs the code returned by `Code::code()` at `severity`, for codes read from configuration
+    pub fn with_numeric(mut self, code: u32, severity: Severity) -> Self {
+        self.severities.insert(code, severity);
+        self
+    }
+
+    /// The severity of `code`, overridden or declared by its family
+    pub fn severity<T: Code>(&self, code: &T) -> Severity {
+        self.severities
+            .get(&code.code())
+            .copied()
+            .unwrap_or_else(|| code.severity())
+    }
+
+    /// Uses these overrides for the whole process, see `effective()`
+    #[cfg(feature = "std")]
+    pub fn install(self) {
+        *INSTALLED
+            .write()
+            .unwrap_or_else(|poisoned| poisoned.into_inner()) = Some(self);
+    }
+}
+
+#[cfg(feature = "std")]
+static INSTALLED: std::sync::RwLock<Option<Overrides>> = std::sync::RwLock::new(None);
+
+/// The severity of `code` after applying the overrides installed with `Overrides::install()`
+/// ### Examples
+/// ```
+/// use custom_codes::severity::{effective, Overrides};
+/// use custom_codes::{Networking, Severity};
+///
+/// Overrides::new().with(&Networking::NetTimedOut, Severity::Warning).install();
+/// assert_eq!(effective(&Networking::NetTimedOut), Severity::Warning);
+/// assert_eq!(effective(&Networking::BrokenPipe), Severity::Error);
+/// ```
+#[cfg(feature = "std")]
+pub fn effective<T: Code>(code: &T) -> Severity {
+    match &*INSTALLED
+        .read()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+    {
+        Some(overrides) => overrides.severity(code),
+        None => code.severity(),
+    }
+}
@@ -0,0 +1,251 @@
+//! Summarizes the codes reported by a batch of operations, eg. one `DbOps` per document of an import.
+//!
+//! `OutcomeSummary` counts every variant, keeps the first failures together with the index or key of the item
+//! which reported them, and derives the `Outcome` of the whole batch under a `Policy`. It is collected from
+//! an iterator of codes, keyed by their index, or of `(key, code)` pairs. With the `serde` feature it serializes
+//! into a report naming every variant.
+//! ### Examples
+//! ```
+//! use custom_codes::summary::{OutcomeSummary, Policy};
+//! use custom_codes::{DbOps, Outcome};
+//!
+//! let codes = vec![
+//!     DbOps::Inserted,
+//!     DbOps::DocumentAlreadyExists,
+//!     DbOps::Inserted,
+//!     DbOps::Inserted,
+//! ];
+//!
+//! let summary: OutcomeSummary<DbOps> = codes.iter().cloned().collect();
+//! assert_eq!(summary.count(&DbOps::Inserted), 3);
+//! assert_eq!(summary.failed()[0].key, 1);
+//! assert_eq!(summary.outcome(), Outcome::Failure);
+//!
+//! let mut summary = OutcomeSummary::new(Policy::Threshold(75));
+//! summary.extend(codes);
+//! assert_eq!(summary.outcome(), Outcome::Success);
+//! ```
+
+use crate::code::index_of;
+use crate::{Code, Outcome};
+use alloc::vec::Vec;
+use core::iter::FromIterator;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// How the `Outcome` of a batch is derived from the codes of its items
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Policy {
+    /// The batch fails when any item fails
+    AllOrNothing,
+    /// The batch fails only when every item fails
+    BestEffort,
+    /// The batch succeeds when at least this percentage of its items succeed
+    Threshold(u8),
+}
+
+impl Default for Policy {
+    /// `Policy::AllOrNothing`
+    fn default() -> Self {
+        Policy::AllOrNothing
+    }
+}
+
+/// A failure kept by an `OutcomeSummary`
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct Failure<C, K> {
+    /// The index or key of the item which failed
+    pub key: K,
+    /// The code the item reported
+    pub code: C,
+}
+
+/// Counts the codes of a batch, see the module documentation
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct OutcomeSummary<C, K = usize> {
+    policy: Policy,
+    max_failures: usize,
+    /// The count of every variant, indexed like `Code::VARIANTS`
+    counts: Vec<u64>,
+    total: u64,
+    successes: u64,
+    failed: Vec<Failure<C, K>>,
+}
+
+impl<C: Code, K> OutcomeSummary<C, K> {
+    /// The number of failures kept unless `with_max_failures()` says otherwise
+    pub const DEFAULT_MAX_FAILURES: usize = 10;
+
+    /// An empty summary deriving its outcome under `policy`
+    pub fn new(policy: Policy) -> Self {
+        OutcomeSummary {
+            policy,
+            max_failures: Self::DEFAULT_MAX_FAILURES,
+            counts: Vec::new(),
+            total: 0,
+            successes: 0,
+            failed: Vec::new(),
+        }
+    }
+
+    /// Keeps the first `max_failures` failures instead of `DEFAULT_MAX_FAILURES`
+    pub fn with_max_failures(mut self, max_failures: usize) -> Self {
+        self.max_failures = max_failures;
+        self.failed.truncate(max_failures);
+        self
+    }
+
+    /// Counts the code reported by the item identified by `key`
+    pub fn push_keyed(&mut self, key: K, code: C) {
+        if let Some(index) = index_of(&code) {
+            if self.counts.is_empty() {
+                self.counts.resize(C::VARIANTS.len(), 0);
+            }
+            self.counts[index] += 1;
+        }
+
+        self.total += 1;
+        if code.is_success() {
+            self.successes += 1;
+        } else if self.failed.len() < self.max_failures {
+            self.failed.push(Failure { key, code });
+        }
+    }
+
+    /// The policy deriving the outcome
+    pub fn policy(&self) -> Policy {
+        self.policy
+    }
+
+    /// How many times the variant of `code` was reported
+    pub fn count(&self, code: &C) -> u64 {
+        index_of(code)
+            .and_then(|index| self.counts.get(index))
+            .copied()
+            .unwrap_or(0)
+    }
+
+    /// The name and count of every variant reported at least once, ordered like `Code::VARIANTS`
+    pub fn counts(&self) -> impl Iterator<Item = (&'static str, u64)> + '_ {
+        C::VARIANTS
+            .iter()
+            .zip(&self.counts)
+            .filter(|(_, count)| **count > 0)
+            .map(|(variant, count)| (variant.name, *count))
+    }
+
+    /// The number of items
+    pub fn total(&self) -> u64 {
+        self.total
+    }
+
+    /// The number of items which succeeded
+    pub fn successes(&self) -> u64 {
+        self.successes
+    }
+
+    /// The number of items which failed, including those not kept by `failed()`
+    pub fn failures(&self) -> u64 {
+        self.total - self.successes
+    }
+
+    /// The first failures in the order they were counted
+    pub fn failed(&self) -> &[Failure<C, K>] {
+        &self.failed
+    }
+
+    /// The outcome of the whole batch under the policy, an empty batch succeeds
+    pub fn outcome(&self) -> Outcome {
+        let success = match self.policy {
+            Policy::AllOrNothing => self.successes == self.total,
+            Policy::BestEffort => self.total == 0 || self.successes > 0,
+            Policy::Threshold(percent) => self.successes * 100 >= u64::from(percent) * self.total,
+        };
+
+        if success {
+            Outcome::Success
+        } else {
+            Outcome::Failure
+        }
+    }
+}
+
+impl<C: Code> OutcomeSummary<C> {
+    /// Counts the code reported by the next item, keyed by its index
+    pub fn push(&mut self, code: C) {
+        let index = self.total as usize;
+        self.push_keyed(index, code);
+    }
+}
+
+impl<C: Code, K> Default for OutcomeSummary<C, K> {
+    fn default() -> Self {
+        OutcomeSummary::new(Policy::default())
+    }
+}
+
+impl<C: Code> Extend<C> for OutcomeSummary<C> {
+    fn extend<I: IntoIterator<Item = C>>(&mut self, codes: I) {
+        for code in codes {
+            self.push(code);
+        }
+    }
+}
+
+impl<C: Code, K> Extend<(K, C)> for OutcomeSummary<C, K> {
+    fn extend<I: IntoIterator<Item = (K, C)>>(&mut self, codes: I) {
+        for (key, code) in codes {
+            self.push_keyed(key, code);
+        }
+    }
+}
+
+impl<C: Code> FromIterator<C> for OutcomeSummary<C> {
+    fn from_iter<I: IntoIterator<Item = C>>(codes: I) -> Self {
+        let mut summary = OutcomeSummary::default();
+        summary.extend(codes);
+        summary
+    }
+}
+
+impl<C: Code, K> FromIterator<(K, C)> for OutcomeSummary<C, K> {
+    fn from_iter<I: IntoIterator<Item = (K, C)>>(codes: I) -> Self {
+        let mut summary = OutcomeSummary::default();
+        summary.extend(codes);
+        summary
+    }
+}
+
+/// Serializes a report holding the policy, the outcome, the totals, the count of every variant by name
+/// and the failures kept
+#[cfg(feature = "serde")]
+impl<C: Code + Serialize, K: Serialize> Serialize for OutcomeSummary<C, K> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::{SerializeMap, SerializeStruct};
+
+        struct Counts<'a, C, K>(&'a OutcomeSummary<C, K>);
+
+        impl<C: Code, K> Serialize for Counts<'_, C, K> {
+            fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                let mut map = serializer.serialize_map(None)?;
+                for (name, count) in self.0.counts() {
+                    map.serialize_entry(name, &count)?;
+                }
+                map.end()
+            }
+        }
+
+        let mut report = serializer.serialize_struct("OutcomeSummary", 7)?;
+        report.serialize_field("policy", &self.policy)?;
+        report.serialize_field("outcome", &self.outcome())?;
+        report.serialize_field("total", &self.total)?;
+        report.serialize_field("successes", &self.successes)?;
+        report.serialize_field("failures", &self.failures())?;
+        report.serialize_field("counts", &Counts(self))?;
+        report.serialize_field("failed", &self.failed)?;
+        report.end()
+    }
+}
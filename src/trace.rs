@@ -0,0 +1,183 @@
+//! Records codes as structured `tracing` fields so events and spans can be aggregated by code.
+//!
+//! `tracing::Value` is sealed, so a code is recorded through `TraceCode::as_value()`, which writes it as
+//! `Family::Variant`, or as the fields `code.family`, `code.name` and `code.num` holding `Code::FAMILY`,
+//! `Code::name()` and `Code::code()`.
+//!
+//! `TraceCode::emit()` or the `trace_code!` macro, which also takes extra fields, logs an event with those
+//! fields at the severity of the code, see `severity::effective()`. Logging a code does not count it,
+//! `metrics::record()` does. `RecordOutcome::record_outcome()` records the final `Outcome` of the current
+//! span, which must declare the fields it records as empty.
+//! ### Examples
+//! ```
+//! use custom_codes::trace::{RecordOutcome, TraceCode};
+//! use custom_codes::{trace_code, DbOps};
+//!
+//! #[tracing::instrument(fields(
+//!     outcome = tracing::field::Empty,
+//!     code.family = tracing::field::Empty,
+//!     code.name = tracing::field::Empty,
+//!     code.num = tracing::field::Empty,
+//! ))]
+//! fn find(id: u32) -> DbOps {
+//!     let code = if id == 0 { DbOps::DocumentNotFound } else { DbOps::DocumentFound };
+//!     trace_code!(code, id);
+//!
+//!     code.record_outcome()
+//! }
+//!
+//! tracing::info!(code = %DbOps::DocumentNotFound.as_value(), "lookup");
+//! assert_eq!(find(0), DbOps::DocumentNotFound);
+//! ```
+
+use crate::code::variant_of;
+use crate::{Code, Outcome};
+use core::fmt;
+use tracing::field::{display, DisplayValue};
+use tracing::Span;
+
+/// The field of a span recording its final `Outcome`
+pub const OUTCOME_FIELD: &str = "outcome";
+
+/// A code written as `Family::Variant`, eg. `DbOps::DocumentNotFound`
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct QualifiedName {
+    /// The name of the family
+    pub family: &'static str,
+    /// The name of the variant
+    pub name: &'static str,
+}
+
+impl fmt::Display for QualifiedName {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}::{}", self.family, self.name)
+    }
+}
+
+/// Records any code with `tracing`
+pub trait TraceCode: Code {
+    /// The code as a field value written as `Family::Variant`
+    fn as_value(&self) -> DisplayValue<QualifiedName> {
+        display(QualifiedName {
+            family: Self::FAMILY,
+            name: self.name(),
+        })
+    }
+
+    /// Logs an event at the severity of the code with the fields `code.family`, `code.name` and `code.num`,
+    /// described by the doc comment of the variant
+    fn emit(&self) {
+        crate::trace_code!(*self);
+    }
+}
+
+impl<T: Code> TraceCode for T {}
+
+/// Records the final outcome of the current span
+pub trait RecordOutcome: Sized {
+    /// Records `Outcome::Success` or `Outcome::Failure` in the `outcome` field of the current span
+    /// and returns `self` unchanged, so it can end an instrumented function
+    fn record_outcome(self) -> Self {
+        self.record_outcome_in(&Span::current())
+    }
+
+    /// Records the outcome in the fields of `span`
+    fn record_outcome_in(self, span: &Span) -> Self;
+}
+
+impl<T, E> RecordOutcome for Result<T, E> {
+    fn record_outcome_in(self, span: &Span) -> Self {
+        let outcome = if self.is_ok() {
+            Outcome::Success
+        } else {
+            Outcome::Failure
+        };
+        span.record(OUTCOME_FIELD, outcome.name());
+
+        self
+    }
+}
+
+/// The outcome of a code is derived from `Code::is_success`, its `code.family`, `code.name`
+/// and `code.num` fields are recorded too
+impl<C: Code> RecordOutcome for C {
+    fn record_outcome_in(self, span: &Span) -> Self {
+        let outcome = if self.is_success() {
+            Outcome::Success
+        } else {
+            Outcome::Failure
+        };
+        span.record(OUTCOME_FIELD, outcome.name());
+        span.record("code.family", C::FAMILY);
+        span.record("code.name", self.name());
+        span.record("code.num", self.code());
+
+        self
+    }
+}
+
+/// The severity a code is logged at
+#[doc(hidden)]
+pub fn severity<T: Code>(code: &T) -> crate::Severity {
+    #[cfg(feature = "std")]
+    return crate::severity::effective(code);
+    #[cfg(not(feature = "std"))]
+    return code.severity();
+}
+
+/// The name of the family of a code
+#[doc(hidden)]
+pub fn family<T: Code>(_: &T) -> &'static str {
+    T::FAMILY
+}
+
+/// The doc comment of the variant of a code
+#[doc(hidden)]
+pub fn message<T: Code>(code: &T) -> alloc::string::String {
+    variant_of(code)
+        .map(|variant| variant.description())
+        .unwrap_or_default()
+}
+
+/// Logs an event for a code at its severity with the fields `code.family`, `code.name`, `code.num`
+/// and `severity`, followed by any other field accepted by `tracing::event!`
+/// ### Examples
+/// ```
+/// use custom_codes::{trace_code, Networking};
+///
+/// let peer = "10.0.0.7";
+/// trace_code!(Networking::NetTimedOut, peer, retries = 3);
+/// ```
+#[macro_export]
+macro_rules! trace_code {
+    ($code:expr $(, $($fields:tt)+)?) => {{
+        use $crate::__tracing::Level;
+
+        let code = &$code;
+        let severity = $crate::trace::severity(code);
+        match Level::from(severity) {
+            Level::ERROR => $crate::__trace_code_event!(Level::ERROR, code, severity $(, $($fields)+)?),
+            Level::WARN => $crate::__trace_code_event!(Level::WARN, code, severity $(, $($fields)+)?),
+            Level::INFO => $crate::__trace_code_event!(Level::INFO, code, severity $(, $($fields)+)?),
+            Level::DEBUG => $crate::__trace_code_event!(Level::DEBUG, code, severity $(, $($fields)+)?),
+            _ => $crate::__trace_code_event!(Level::TRACE, code, severity $(, $($fields)+)?),
+        }
+    }};
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __trace_code_event {
+    ($level:expr, $code:ident, $severity:ident $(, $($fields:tt)+)?) => {
+        $crate::__tracing::event!(
+            $level,
+            code.family = $crate::trace::family($code),
+            code.name = $crate::Code::name($code),
+            code.num = $crate::Code::code($code),
+            severity = $severity.as_str(),
+            $($($fields)+,)?
+            "{}",
+            $crate::trace::message($code)
+        )
+    };
+}
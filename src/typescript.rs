@@ -0,0 +1,174 @@
+//! TypeScript definitions of the codes for front ends consuming them as JSON.
+//!
+//! `definitions()` generates a `.d.ts` file declaring every family as a union matching its serde representation:
+//! a unit variant is a string literal and a variant carrying a payload is an object with a single property
+//! named after the variant. Each family also gets a `<Family>Name` union of its variant names and
+//! a `<Family>Codes` map from the name of each variant to its numeric code, whose values are in the
+//! ES module generated by `constants()`. Names follow the `legacy-names` feature like serialization does.
+//!
+//! The `custom_codes_ts` binary writes both files to a directory.
+//! ### Examples
+//! ```
+//! use custom_codes::typescript::{constants, definitions};
+//!
+//! let definitions = definitions();
+//! assert!(definitions.contains("export type Subscription =\n"));
+//! assert!(definitions.contains("  | { DbList: string[] }\n"));
+//! assert!(definitions.contains("  readonly DocumentNotFound: 4025;\n"));
+//!
+//! assert!(constants().contains("  DocumentNotFound: 4025,\n"));
+//! ```
+
+use crate::{Code, CodeVariant, Outcome, FAMILIES};
+use alloc::string::String;
+use core::fmt::{self, Write};
+
+/// The file name `definitions()` is written to by the `custom_codes_ts` binary
+pub const DEFINITIONS_FILE: &str = "custom_codes.d.ts";
+/// The file name `constants()` is written to by the `custom_codes_ts` binary
+pub const CONSTANTS_FILE: &str = "custom_codes.js";
+
+/// Generates the `.d.ts` declarations of every family and `GenericOutcome`
+pub fn definitions() -> String {
+    let mut definitions = String::new();
+    write_definitions(&mut definitions).expect("writing to a `String` does not fail");
+
+    definitions
+}
+
+/// Generates the ES module holding the `<Family>Codes` maps declared by `definitions()`
+pub fn constants() -> String {
+    let mut constants = String::new();
+    write_constants(&mut constants).expect("writing to a `String` does not fail");
+
+    constants
+}
+
+fn write_definitions(out: &mut String) -> fmt::Result {
+    writeln!(
+        out,
+        "// Generated by `custom_codes::typescript::definitions()`, do not edit."
+    )?;
+
+    for family in FAMILIES {
+        writeln!(out)?;
+        write_doc(out, "", family.docs, None)?;
+        writeln!(out, "export type {} =", family.name)?;
+        for (index, variant) in family.variants.iter().enumerate() {
+            write_doc(out, "  ", variant.docs, family.accepted_name(variant.name))?;
+
+            let name = family.serialized_name(variant.name);
+            match variant.payload {
+                Some(payload) => write!(out, "  | {{ {}: {} }}", name, payload_type(payload))?,
+                None => write!(out, "  | \"{}\"", name)?,
+            }
+            writeln!(out, "{}", terminator(index, family.variants))?;
+        }
+
+        writeln!(out)?;
+        writeln!(out, "/** The names of the `{}` variants */", family.name)?;
+        write!(out, "export type {}Name =", family.name)?;
+        for variant in family.variants {
+            write!(out, "\n  | \"{}\"", family.serialized_name(variant.name))?;
+        }
+        writeln!(out, ";")?;
+
+        writeln!(out)?;
+        writeln!(
+            out,
+            "/** The numeric code of every `{}` variant */",
+            family.name
+        )?;
+        writeln!(out, "export declare const {}Codes: {{", family.name)?;
+        for variant in family.variants {
+            writeln!(
+                out,
+                "  readonly {}: {};",
+                family.serialized_name(variant.name),
+                family.code(variant)
+            )?;
+        }
+        writeln!(out, "}};")?;
+    }
+
+    writeln!(out)?;
+    writeln!(out, concat!("/** ", generic_outcome_docs!(), " */"))?;
+    writeln!(out, "export type GenericOutcome<Reason> =")?;
+    for (index, variant) in Outcome::VARIANTS.iter().enumerate() {
+        write_doc(out, "  ", variant.docs, None)?;
+        writeln!(
+            out,
+            "  | {{ {}: Reason }}{}",
+            variant.name,
+            terminator(index, Outcome::VARIANTS)
+        )?;
+    }
+
+    Ok(())
+}
+
+fn write_constants(out: &mut String) -> fmt::Result {
+    writeln!(
+        out,
+        "// Generated by `custom_codes::typescript::constants()`, do not edit."
+    )?;
+
+    for family in FAMILIES {
+        writeln!(out)?;
+        writeln!(out, "export const {}Codes = Object.freeze({{", family.name)?;
+        for variant in family.variants {
+            writeln!(
+                out,
+                "  {}: {},",
+                family.serialized_name(variant.name),
+                family.code(variant)
+            )?;
+        }
+        writeln!(out, "}});")?;
+    }
+
+    Ok(())
+}
+
+fn payload_type(payload: &str) -> &str {
+    match payload {
+        "String" => "string",
+        "Vec<String>" => "string[]",
+        "Vec<u8>" => "number[]",
+        "Vec<Vec<u8>>" => "number[][]",
+        family => family,
+    }
+}
+
+fn terminator(index: usize, variants: &[CodeVariant]) -> &'static str {
+    if index + 1 == variants.len() {
+        ";"
+    } else {
+        ""
+    }
+}
+
+fn write_doc(
+    out: &mut String,
+    indent: &str,
+    docs: &[&str],
+    also_accepted: Option<&str>,
+) -> fmt::Result {
+    let mut lines = docs
+        .iter()
+        .map(|line| line.trim())
+        .take_while(|line| !line.starts_with('#') && !line.starts_with("```"))
+        .filter(|line| !line.is_empty());
+
+    write!(out, "{}/**", indent)?;
+    if let Some(first) = lines.next() {
+        write!(out, " {}", first.replace("*/", "*\\/"))?;
+    }
+    for line in lines {
+        write!(out, "\n{} * {}", indent, line.replace("*/", "*\\/"))?;
+    }
+    if let Some(name) = also_accepted {
+        write!(out, "\n{} * Also accepted as `\"{}\"`", indent, name)?;
+    }
+    writeln!(out, " */")
+}
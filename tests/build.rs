@@ -0,0 +1,155 @@
+#![cfg(feature = "build")]
+
+use custom_codes::build::{render, BuildError, Generator, ValidationError, ValidationErrorReason};
+use custom_codes::{Code, GrpcCode, RetryHint, Retryable, Severity};
+
+mod generated {
+    include!("fixtures/codes.rs");
+}
+
+use generated::{Billing, Messaging};
+
+/// Run with `UPDATE_CODES=1` to regenerate the fixture after changing the generator
+#[test]
+fn generated_fixture_is_up_to_date() {
+    let dir = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures");
+    let path = format!("{}/codes.rs", dir);
+    if std::env::var_os("UPDATE_CODES").is_some() {
+        Generator::new()
+            .generate_to(format!("{}/codes.toml", dir), &path)
+            .unwrap();
+    }
+
+    let toml = std::fs::read_to_string(format!("{}/codes.toml", dir)).unwrap();
+    assert!(
+        std::fs::read_to_string(&path).unwrap() == render(&toml).unwrap(),
+        "tests/fixtures/codes.rs is out of date, regenerate it with `UPDATE_CODES=1 cargo test --features build --test build`"
+    );
+}
+
+#[test]
+fn generated_families_behave_like_the_built_in_ones() {
+    assert_eq!(Billing::CardDeclined.code(), 120_002);
+    assert!(!Billing::CardDeclined.is_success());
+    assert_eq!(Billing::CardDeclined.severity(), Severity::Warning);
+    assert_eq!(Billing::CardDeclined.http_status(), 402);
+    assert_eq!(
+        Billing::CardDeclined.grpc_code(),
+        GrpcCode::FailedPrecondition
+    );
+    assert_eq!(
+        Billing::ProviderTimedOut.retry_hint(),
+        RetryHint::WithBackoff
+    );
+    assert_eq!(
+        Billing::ProviderTimedOut.to_string(),
+        "The payment provider did not answer before the deadline"
+    );
+    assert_eq!("Charged".parse(), Ok(Billing::Charged));
+    assert_eq!(Messaging::RecipientNotFound.http_status(), 404);
+    assert_eq!(Messaging::Delivered.to_string(), "Delivered");
+}
+
+fn invalid(toml: &str) -> ValidationError {
+    match render(toml) {
+        Err(BuildError::Invalid(error)) => error,
+        other => panic!("expected a validation error, got {:?}", other),
+    }
+}
+
+#[test]
+fn rejects_duplicates() {
+    let family = "[[families]]\nname = \"Billing\"\nid = 120\n";
+    let variant = |name: &str, number: u16| {
+        format!(
+            "[[families.variants]]\nname = \"{}\"\nnumber = {}\n",
+            name, number
+        )
+    };
+
+    assert_eq!(
+        invalid(&format!(
+            "{}{}{}",
+            family,
+            variant("Charged", 1),
+            variant("Refunded", 1)
+        )),
+        ValidationError {
+            family: Some("Billing".into()),
+            variant: Some("Refunded".into()),
+            reason: ValidationErrorReason::DuplicateNumber(1),
+        }
+    );
+    assert_eq!(
+        invalid(&format!(
+            "{}{}{}",
+            family,
+            variant("Charged", 1),
+            variant("Charged", 2)
+        ))
+        .reason,
+        ValidationErrorReason::DuplicateVariant("Charged".into())
+    );
+    assert_eq!(
+        invalid(&format!(
+            "{}{}{}{}",
+            family,
+            variant("Charged", 1),
+            family.replace("Billing", "Payments"),
+            variant("Captured", 1)
+        ))
+        .reason,
+        ValidationErrorReason::DuplicateFamilyId(120)
+    );
+}
+
+#[test]
+fn rejects_invalid_fields() {
+    let error = invalid("[[families]]\nname = \"Billing\"\nid = 12\nvariants = []\n");
+    assert_eq!(
+        error.reason,
+        ValidationErrorReason::OutOfRange {
+            field: "id",
+            value: 12
+        }
+    );
+    assert_eq!(error.to_string(), "Billing: `id` is out of range: 12");
+
+    let variant = |fields: &str| {
+        invalid(&format!(
+            "[[families]]\nname = \"Billing\"\nid = 120\n[[families.variants]]\nname = \"Charged\"\nnumber = 1\n{}\n",
+            fields
+        ))
+        .reason
+    };
+    assert_eq!(
+        variant("severty = \"Warning\""),
+        ValidationErrorReason::UnknownField("severty".into())
+    );
+    assert_eq!(
+        variant("severity = \"Loud\""),
+        ValidationErrorReason::UnknownValue {
+            field: "severity",
+            name: "Loud".into()
+        }
+    );
+    assert_eq!(
+        variant("failure = \"yes\""),
+        ValidationErrorReason::WrongType("failure")
+    );
+    assert_eq!(
+        invalid("[[families]]\nname = \"billing-codes\"\nid = 120\n").reason,
+        ValidationErrorReason::InvalidName("billing-codes".into())
+    );
+    assert!(matches!(render("families = ["), Err(BuildError::Toml(_))));
+}
+
+#[test]
+fn adds_derives() {
+    let source = Generator::new()
+        .with_derive("serde::Serialize")
+        .render("[[families]]\nname = \"Billing\"\nid = 120\nvariants = [{ name = \"Charged\", number = 1 }]\n")
+        .unwrap();
+
+    assert!(source.contains("#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash, serde::Serialize)]"));
+}
@@ -0,0 +1,173 @@
+#![cfg(feature = "alloc")]
+
+use custom_codes::catalog::{english_template, message_key, Catalog, ParseError, ParseErrorReason};
+use custom_codes::*;
+
+const FRENCH: &str = "\
+# Messages en français
+db-ops-document-not-found = Le document n'existe pas
+db-ops-db-list =
+    Bases de données :
+    { $payload }
+    .title = ignoré
+-brand = custom_codes
+sec-ops-key-too-short = La clé est trop courte, { $payload } attendus
+key-length-bytes32 = 32 octets
+";
+
+fn catalog() -> Catalog {
+    let mut catalog = Catalog::new().with_fallbacks("fr-CA", &["fr-FR"]);
+    catalog.add_ftl("fr", FRENCH).unwrap();
+    catalog.insert("fr-FR", "db-ops-document-not-found", "Document introuvable");
+    catalog
+}
+
+#[test]
+fn follows_the_fallback_chain() {
+    let catalog = catalog();
+
+    assert_eq!(
+        catalog.message("fr-CA", &DbOps::DocumentNotFound),
+        "Document introuvable"
+    );
+    assert_eq!(
+        catalog.message("fr-BE", &DbOps::DocumentNotFound),
+        "Le document n'existe pas"
+    );
+    assert_eq!(
+        catalog.message("de", &DbOps::DocumentNotFound),
+        "Document does not exist"
+    );
+    assert_eq!(
+        catalog.chain("sw-Latn-KE"),
+        ["sw-Latn-KE", "sw-Latn", "sw", "en"]
+    );
+}
+
+#[test]
+fn interpolates_payloads() {
+    let catalog = catalog();
+
+    assert_eq!(
+        catalog.message("fr", &DbOps::DbList(vec!["users".into(), "orders".into()])),
+        "Bases de données :\nusers, orders"
+    );
+    // A nested code is written as its message in the same locale
+    assert_eq!(
+        catalog.message("fr", &SecOps::KeyTooShort(KeyLength::Bytes32)),
+        "La clé est trop courte, 32 octets attendus"
+    );
+    assert_eq!(
+        catalog.message("en", &SecOps::KeyTooShort(KeyLength::Bytes32)),
+        "Key length too short: A Key length of 32 bytes"
+    );
+    assert_eq!(
+        catalog.message_with("fr", &DbOps::DocumentNotFound, &[("unused", "value")]),
+        "Le document n'existe pas"
+    );
+}
+
+#[test]
+fn writes_unknown_arguments_like_fluent() {
+    let mut catalog = Catalog::new();
+    catalog.insert("sw", "outcome-failure", "Imeshindwa { $sababu } {\"!\"}");
+
+    assert_eq!(
+        catalog.message("sw", &Outcome::Failure),
+        "Imeshindwa {$sababu} !"
+    );
+}
+
+#[test]
+fn rejects_invalid_catalogs() {
+    let mut catalog = Catalog::new();
+
+    assert_eq!(
+        catalog.add_ftl("fr", "outcome-success = Succès\nnot a message"),
+        Err(ParseError {
+            line: 2,
+            reason: ParseErrorReason::ExpectedMessage,
+        })
+    );
+    assert_eq!(
+        catalog
+            .add_ftl("fr", "outcome-failure = Échec { $raison")
+            .unwrap_err()
+            .reason,
+        ParseErrorReason::UnterminatedPlaceable
+    );
+    assert_eq!(
+        catalog
+            .add_ftl("fr", "outcome-failure = { $raison -> *[x] y }")
+            .unwrap_err()
+            .reason,
+        ParseErrorReason::InvalidPlaceable("$raison -> *[x] y".into())
+    );
+    assert_eq!(
+        catalog
+            .add_ftl("fr", "\n\noutcome-failure =\n")
+            .unwrap_err(),
+        ParseError {
+            line: 3,
+            reason: ParseErrorReason::MissingPattern,
+        }
+    );
+}
+
+#[test]
+fn the_english_template_covers_every_variant() {
+    let template = english_template();
+    let variants: usize = FAMILIES.iter().map(|family| family.variants.len()).sum();
+    assert_eq!(
+        template.lines().filter(|line| line.contains(" = ")).count(),
+        variants
+    );
+    assert!(template.contains("\nnetworking-net-timed-out = "));
+
+    let mut catalog = Catalog::new();
+    catalog.add_ftl("en-GB", &template).unwrap();
+    assert_eq!(
+        catalog.message("en-GB", &SecOps::KeyTooLong(KeyLength::Bytes8)),
+        Catalog::new().message("en", &SecOps::KeyTooLong(KeyLength::Bytes8))
+    );
+    assert_eq!(
+        message_key(&Networking::NetTimedOut),
+        "networking-net-timed-out"
+    );
+}
+
+#[cfg(feature = "toml")]
+#[test]
+fn reads_toml_catalogs() {
+    let mut catalog = Catalog::new();
+    catalog
+        .add_toml(
+            "sw",
+            r#"
+"db-ops-document-not-found" = "Hati haipo"
+"db-ops-encountered-errors" = "Hitilafu: { $payload }"
+"#,
+        )
+        .unwrap();
+
+    assert_eq!(
+        catalog.message("sw", &DbOps::EncounteredErrors("diski imejaa".into())),
+        "Hitilafu: diski imejaa"
+    );
+    assert_eq!(catalog.add_toml("sw", "key = 1").unwrap_err().line, 1);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn loads_catalog_files() {
+    use custom_codes::catalog::LoadError;
+
+    let path = std::env::temp_dir().join(format!("custom_codes_{}.ftl", std::process::id()));
+    std::fs::write(&path, "outcome-success = Imefaulu\n").unwrap();
+    let mut catalog = Catalog::new();
+    catalog.load("sw", &path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    assert_eq!(catalog.message("sw", &Outcome::Success), "Imefaulu");
+    assert!(matches!(catalog.load("sw", &path), Err(LoadError::Io(_))));
+}
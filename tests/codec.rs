@@ -0,0 +1,146 @@
+#![cfg(feature = "alloc")]
+
+use custom_codes::codec::{Binary, DecodeError, RawCode};
+use custom_codes::*;
+use std::fmt::Debug;
+
+/// A xorshift generator so the fuzzing is reproducible without extra dependencies
+struct Rng(u64);
+
+impl Rng {
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    fn below(&mut self, bound: usize) -> usize {
+        (self.next() % bound as u64) as usize
+    }
+
+    fn bytes(&mut self, max_len: usize) -> Vec<u8> {
+        (0..self.below(max_len + 1))
+            .map(|_| self.next() as u8)
+            .collect()
+    }
+
+    fn string(&mut self, max_len: usize) -> String {
+        let chars = ['a', 'z', '0', ' ', 'é', '😀', '\u{0}'];
+        (0..self.below(max_len + 1))
+            .map(|_| chars[self.below(chars.len())])
+            .collect()
+    }
+
+    fn key_length(&mut self) -> KeyLength {
+        let variant = KeyLength::VARIANTS[self.below(KeyLength::VARIANTS.len())];
+        KeyLength::from_number(variant.number).unwrap()
+    }
+}
+
+fn assert_round_trip<T: Binary + PartialEq + Debug>(code: &T, rng: &mut Rng) {
+    let mut buffer = rng.bytes(4);
+    let start = buffer.len();
+    code.encode_into(&mut buffer);
+    let end = buffer.len();
+    buffer.extend(rng.bytes(4));
+
+    let (decoded, read) = T::decode(&buffer[start..]).unwrap();
+    assert_eq!(&decoded, code);
+    assert_eq!(read, end - start);
+
+    for truncated in start..end {
+        assert!(T::decode(&buffer[start..truncated]).is_err());
+    }
+}
+
+fn fuzz_family<T: Binary + PartialEq + Debug>(
+    rng: &mut Rng,
+    with_payload: impl Fn(&str, &mut Rng) -> T,
+) {
+    for variant in T::VARIANTS {
+        for _ in 0..16 {
+            let code = match variant.payload {
+                None => T::from_number(variant.number).unwrap(),
+                Some(_) => with_payload(variant.name, rng),
+            };
+            assert_eq!(code.name(), variant.name);
+            assert_round_trip(&code, rng);
+        }
+    }
+
+    for _ in 0..2048 {
+        let mut garbage = rng.bytes(32);
+        if !garbage.is_empty() && rng.below(2) == 0 {
+            garbage[0] = T::FAMILY_ID as u8;
+        }
+
+        let _ = T::decode(&garbage);
+        if let Ok((raw, read)) = RawCode::decode(&garbage) {
+            assert!(read <= garbage.len());
+            let _ = raw.str();
+            let _ = raw.list().map(|list| list.count());
+        }
+    }
+}
+
+fn no_payload<T>(name: &str, _: &mut Rng) -> T {
+    panic!("`{}` does not carry a payload", name)
+}
+
+#[test]
+fn round_trips_every_family() {
+    let mut rng = Rng(0x2545_f491_4f6c_dd1d);
+
+    fuzz_family::<Outcome>(&mut rng, no_payload);
+    fuzz_family::<AccessStatus>(&mut rng, no_payload);
+    fuzz_family::<CustomBool>(&mut rng, no_payload);
+    fuzz_family::<DbOps>(&mut rng, |name, rng| match name {
+        "DbList" => DbOps::DbList((0..rng.below(4)).map(|_| rng.string(8)).collect()),
+        "DocumentList" => DbOps::DocumentList((0..rng.below(4)).map(|_| rng.string(8)).collect()),
+        "FieldContents" => DbOps::FieldContents(rng.bytes(300)),
+        "FieldList" => DbOps::FieldList((0..rng.below(4)).map(|_| rng.bytes(8)).collect()),
+        "EncounteredErrors" => DbOps::EncounteredErrors(rng.string(200)),
+        _ => no_payload(name, rng),
+    });
+    fuzz_family::<ExecCommand>(&mut rng, no_payload);
+    fuzz_family::<FileOps>(&mut rng, |_, rng| {
+        FileOps::EncounteredErrors(rng.string(16))
+    });
+    fuzz_family::<Compression>(&mut rng, no_payload);
+    fuzz_family::<Subscription>(&mut rng, no_payload);
+    fuzz_family::<ActivityStatus>(&mut rng, no_payload);
+    fuzz_family::<ActivityToggle>(&mut rng, no_payload);
+    fuzz_family::<Cli>(&mut rng, |_, rng| Cli::EncounteredErrors(rng.string(16)));
+    fuzz_family::<DateTimeOp>(&mut rng, no_payload);
+    fuzz_family::<SecOps>(&mut rng, |name, rng| match name {
+        "KeyTooShort" => SecOps::KeyTooShort(rng.key_length()),
+        _ => SecOps::KeyTooLong(rng.key_length()),
+    });
+    fuzz_family::<KeyLength>(&mut rng, no_payload);
+    fuzz_family::<HardwareResources>(&mut rng, no_payload);
+    fuzz_family::<Networking>(&mut rng, no_payload);
+    fuzz_family::<SecHardware>(&mut rng, no_payload);
+}
+
+#[test]
+fn unit_variants_take_two_bytes() {
+    assert_eq!(DbOps::DocumentNotFound.encode(), [4, 50]);
+    assert_eq!(HardwareResources::Unspecified.encode().len(), 2);
+    assert_eq!(
+        SecOps::KeyTooShort(KeyLength::Bytes32).encode(),
+        [13, 43, 1, 4]
+    );
+}
+
+#[test]
+fn rejects_codes_of_other_families() {
+    let encoded = Networking::NetTimedOut.encode();
+    assert_eq!(
+        DbOps::decode(&encoded),
+        Err(DecodeError::WrongFamily {
+            expected: DbOps::FAMILY_ID,
+            found: Networking::FAMILY_ID,
+        })
+    );
+}
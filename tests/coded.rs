@@ -0,0 +1,150 @@
+#![cfg(feature = "std")]
+
+use custom_codes::coded::{outermost_code, CodedError};
+use custom_codes::*;
+use std::error::Error;
+use std::io::{self, ErrorKind};
+
+#[test]
+fn wraps_a_std_error() {
+    let error = CodedError::new(
+        FileOps::ReadDenied,
+        io::Error::from(ErrorKind::PermissionDenied),
+    )
+    .with_context("reading the config");
+
+    assert_eq!(error.code::<FileOps>(), Some(&FileOps::ReadDenied));
+    assert_eq!(error.code::<DbOps>(), None);
+    assert_eq!(error.family(), "FileOps");
+    assert_eq!(error.name(), "ReadDenied");
+    assert_eq!(error.context(), Some("reading the config"));
+    assert_eq!(error.to_string(), "reading the config");
+    assert!(error
+        .source()
+        .unwrap()
+        .downcast_ref::<io::Error>()
+        .is_some());
+
+    let bare = CodedError::new(FileOps::ReadDenied, "denied");
+    assert_eq!(bare.to_string(), "FileOps::ReadDenied");
+    assert_eq!(bare.any_code().code(), FileOps::ReadDenied.code());
+}
+
+#[test]
+fn try_downcast_dyn_returns_the_attached_code_first() {
+    let error = CodedError::new(
+        Networking::NetTimedOut,
+        io::Error::from(ErrorKind::TimedOut),
+    );
+
+    match try_downcast_dyn(&error) {
+        DownCastErrors::Coded(coded) => {
+            assert_eq!(coded.code::<Networking>(), Some(&Networking::NetTimedOut))
+        }
+        _ => panic!("expected the attached code"),
+    }
+    assert_eq!(
+        try_downcast_dyn(&error).retry_hint(),
+        RetryHint::WithBackoff
+    );
+
+    let error = io::Error::from(ErrorKind::TimedOut);
+    assert!(outermost_code(&error).is_none());
+    assert!(matches!(try_downcast_dyn(&error), DownCastErrors::TimedOut));
+}
+
+#[cfg(feature = "anyhow")]
+mod anyhow_errors {
+    use super::*;
+    use anyhow::Context;
+    use custom_codes::coded::{attached_codes, innermost_code, WithCode};
+
+    fn open() -> anyhow::Result<()> {
+        Err(io::Error::from(ErrorKind::NotFound)).with_code(FileOps::OpenError)
+    }
+
+    fn load() -> anyhow::Result<()> {
+        open()
+            .context("opening the database file")
+            .with_code_context(DbOps::DbNotFound, "loading the users")
+    }
+
+    #[test]
+    fn finds_the_outermost_and_innermost_codes() {
+        let error = load().unwrap_err();
+
+        let codes: Vec<_> = attached_codes(error.as_ref())
+            .map(|coded| coded.name())
+            .collect();
+        assert_eq!(codes, ["DbNotFound", "OpenError"]);
+        assert_eq!(
+            outermost_code(error.as_ref()).unwrap().code::<DbOps>(),
+            Some(&DbOps::DbNotFound)
+        );
+        assert_eq!(
+            innermost_code(error.as_ref()).unwrap().code::<FileOps>(),
+            Some(&FileOps::OpenError)
+        );
+        assert_eq!(
+            error
+                .root_cause()
+                .downcast_ref::<io::Error>()
+                .unwrap()
+                .kind(),
+            ErrorKind::NotFound
+        );
+    }
+
+    #[test]
+    fn keeps_the_context_chain() {
+        let chain: Vec<_> = load()
+            .unwrap_err()
+            .chain()
+            .map(|error| error.to_string())
+            .collect();
+
+        assert_eq!(
+            chain,
+            [
+                "loading the users",
+                "opening the database file",
+                "FileOps::OpenError",
+                "entity not found",
+            ]
+        );
+    }
+
+    #[test]
+    fn try_downcast_returns_the_attached_code_first() {
+        let error = open().unwrap_err();
+        match try_downcast(&error) {
+            DownCastErrors::Coded(coded) => assert_eq!(coded.name(), "OpenError"),
+            _ => panic!("expected the attached code"),
+        }
+
+        let error = anyhow::Error::new(io::Error::from(ErrorKind::NotFound));
+        assert!(matches!(try_downcast(&error), DownCastErrors::NotFound));
+    }
+
+    #[test]
+    fn attaches_codes_to_anyhow_errors() {
+        let error = anyhow::anyhow!("disk full").with_code(DbOps::QueryConflictError);
+
+        assert_eq!(try_downcast(&error).retry_hint(), RetryHint::WithBackoff);
+        assert_eq!(error.chain().nth(1).unwrap().to_string(), "disk full");
+    }
+
+    #[cfg(feature = "problem-details")]
+    #[test]
+    fn describes_the_attached_code_as_a_problem() {
+        use custom_codes::problem::{type_uri, ProblemDetails, TYPE_BASE};
+
+        let error = load().unwrap_err();
+        let problem = ProblemDetails::from(&try_downcast(&error));
+
+        assert_eq!(problem.type_uri, type_uri::<DbOps>(TYPE_BASE, "DbNotFound"));
+        assert_eq!(problem.status, Some(404));
+        assert_eq!(problem.detail.as_deref(), Some("loading the users"));
+        assert_eq!(problem.to_code::<DbOps>(), Ok(DbOps::DbNotFound));
+    }
+}
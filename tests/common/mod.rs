@@ -0,0 +1,34 @@
+//! Helpers shared by the integration tests
+#![allow(dead_code, unused_macros)]
+
+use custom_codes::Code;
+
+/// The variants of `T` which carry no payload
+pub fn unit_variants<T: Code>() -> impl Iterator<Item = T> {
+    T::VARIANTS
+        .iter()
+        .filter_map(|variant| T::from_number(variant.number))
+}
+
+/// Calls `$check::<Family>($args)` for every family of the crate
+macro_rules! for_each_family {
+    ($check:ident $(, $arg:expr)*) => {
+        $check::<custom_codes::Outcome>($($arg),*);
+        $check::<custom_codes::AccessStatus>($($arg),*);
+        $check::<custom_codes::CustomBool>($($arg),*);
+        $check::<custom_codes::DbOps>($($arg),*);
+        $check::<custom_codes::ExecCommand>($($arg),*);
+        $check::<custom_codes::FileOps>($($arg),*);
+        $check::<custom_codes::Compression>($($arg),*);
+        $check::<custom_codes::Subscription>($($arg),*);
+        $check::<custom_codes::ActivityStatus>($($arg),*);
+        $check::<custom_codes::ActivityToggle>($($arg),*);
+        $check::<custom_codes::Cli>($($arg),*);
+        $check::<custom_codes::DateTimeOp>($($arg),*);
+        $check::<custom_codes::SecOps>($($arg),*);
+        $check::<custom_codes::KeyLength>($($arg),*);
+        $check::<custom_codes::HardwareResources>($($arg),*);
+        $check::<custom_codes::Networking>($($arg),*);
+        $check::<custom_codes::SecHardware>($($arg),*);
+    };
+}
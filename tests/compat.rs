@@ -0,0 +1,150 @@
+#![cfg(all(feature = "serde", feature = "alloc"))]
+
+use custom_codes::compat::{Compat, Payload};
+use custom_codes::*;
+use serde::de::IntoDeserializer;
+use serde::Deserialize;
+
+fn read(json: &str) -> Compat<DbOps> {
+    serde_json::from_str(json).unwrap()
+}
+
+/// The payload of an unknown `DbOps` variant named `DbReplicated`
+fn payload(json: &str) -> Payload {
+    match read(&format!(r#"{{"DbReplicated":{}}}"#, json)) {
+        Compat::Unknown {
+            payload: Some(payload),
+            ..
+        } => payload,
+        other => panic!(
+            "expected an unknown variant with a payload, found {:?}",
+            other
+        ),
+    }
+}
+
+#[test]
+fn reads_known_variants() {
+    assert_eq!(read(r#""DbFound""#), Compat::Known(DbOps::DbFound));
+    assert_eq!(read("4012"), Compat::Known(DbOps::DbFound));
+    assert_eq!(
+        read(r#"{"EncounteredErrors":"disk full"}"#),
+        Compat::Known(DbOps::EncounteredErrors("disk full".into()))
+    );
+    assert_eq!(
+        serde_json::from_str::<Compat<AccessStatus>>(r#""AccpetedRAC""#).unwrap(),
+        Compat::Known(AccessStatus::AcceptedRAC)
+    );
+}
+
+#[test]
+fn keeps_unknown_names() {
+    let unknown = read(r#""DbReplicated""#);
+    assert!(unknown.is_unknown());
+    assert_eq!(unknown.known(), None);
+    assert_eq!(
+        unknown,
+        Compat::Unknown {
+            name: Some("DbReplicated".into()),
+            code: None,
+            payload: None,
+        }
+    );
+    assert_eq!(
+        serde_json::to_string(&unknown).unwrap(),
+        r#""DbReplicated""#
+    );
+
+    // Names are case sensitive
+    assert!(read(r#""dbfound""#).is_unknown());
+}
+
+#[test]
+fn falls_back_to_unknown_for_codes_of_other_families() {
+    let code = Networking::NetTimedOut.code();
+    let unknown = read(&code.to_string());
+    assert_eq!(
+        unknown,
+        Compat::Unknown {
+            name: None,
+            code: Some(code),
+            payload: None,
+        }
+    );
+    assert_eq!(serde_json::to_string(&unknown).unwrap(), code.to_string());
+
+    assert_eq!(
+        serde_json::from_str::<Compat<Networking>>(&code.to_string()).unwrap(),
+        Compat::Known(Networking::NetTimedOut)
+    );
+    assert!(read("4999").is_unknown());
+}
+
+#[test]
+fn rejects_malformed_known_variants() {
+    assert!(serde_json::from_str::<Compat<DbOps>>(r#""EncounteredErrors""#).is_err());
+    assert!(serde_json::from_str::<Compat<DbOps>>(r#"{"EncounteredErrors":7}"#).is_err());
+    assert!(serde_json::from_str::<Compat<DbOps>>(r#"{"DbFound":null,"DbList":[]}"#).is_err());
+    assert!(serde_json::from_str::<Compat<DbOps>>("{}").is_err());
+    assert!(serde_json::from_str::<Compat<DbOps>>("4294967296").is_err());
+}
+
+#[test]
+fn keeps_payloads_of_every_shape() {
+    assert_eq!(payload("null"), Payload::Unit);
+    assert_eq!(payload("true"), Payload::Bool(true));
+    assert_eq!(payload("-3"), Payload::I64(-3));
+    assert_eq!(payload("3"), Payload::U64(3));
+    assert_eq!(payload("0.5"), Payload::F64(0.5));
+    assert_eq!(payload(r#""users""#), Payload::String("users".into()));
+    assert_eq!(
+        payload(r#"[1,"a"]"#),
+        Payload::Seq(vec![Payload::U64(1), Payload::String("a".into())])
+    );
+    assert_eq!(
+        payload(r#"{"replicas":3}"#),
+        Payload::Map(vec![(Payload::String("replicas".into()), Payload::U64(3))])
+    );
+    // JSON has no bytes, they are written as a list of numbers
+    assert_eq!(
+        serde_json::to_string(&Payload::Bytes(vec![0, 255])).unwrap(),
+        "[0,255]"
+    );
+
+    for json in &[
+        "null",
+        "true",
+        "-3",
+        "3",
+        "0.5",
+        r#""users""#,
+        r#"[1,"a"]"#,
+        r#"{"replicas":3}"#,
+    ] {
+        let unknown = format!(r#"{{"DbReplicated":{}}}"#, json);
+        assert_eq!(serde_json::to_string(&read(&unknown)).unwrap(), unknown);
+    }
+}
+
+#[test]
+fn reads_payloads_back() {
+    fn into<T: for<'de> Deserialize<'de>>(payload: Payload) -> T {
+        T::deserialize(IntoDeserializer::<serde_json::Error>::into_deserializer(
+            payload,
+        ))
+        .unwrap()
+    }
+
+    assert_eq!(into::<String>(Payload::Bytes(b"users".to_vec())), "users");
+    assert_eq!(into::<Vec<u8>>(payload("[0,255]")), [0, 255]);
+    assert_eq!(into::<Option<String>>(payload("null")), None);
+    assert_eq!(into::<i8>(payload("-3")), -3);
+    assert_eq!(
+        into::<KeyLength>(payload(r#""Bytes32""#)),
+        KeyLength::Bytes32
+    );
+    assert_eq!(
+        into::<DbOps>(payload(r#"{"DbList":["users"]}"#)),
+        DbOps::DbList(vec!["users".into()])
+    );
+}
@@ -0,0 +1,181 @@
+use custom_codes::{
+    define_codes, Code, CodeSet, GrpcCode, ParseCodeError, RetryHint, Retryable, Severity,
+};
+
+define_codes! {
+    /// Outcome of a payment
+    #[derive(Debug, PartialEq, Eq, Clone)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+    pub enum Payments = 121 {
+        /// The payment has been captured
+        Captured = 1,
+        /// The card has been declined
+        /// by the issuer
+        CardDeclined = 2,
+        /// The payment provider did not answer in time
+        ProviderTimedOut = 3,
+        /// Misspelled, use `Payments::Refunded` instead
+        #[deprecated(note = "misspelled, use `Payments::Refunded` instead")]
+        Refundded => Refunded,
+        /// The payment has been refunded
+        Refunded = 4,
+        /// The provider rejected the payment
+        Rejected(String) = 5,
+        Pending = 6,
+    }
+    failures [CardDeclined, ProviderTimedOut, Rejected]
+    severities [CardDeclined => Warning]
+    retries [ProviderTimedOut => WithBackoff]
+    http [CardDeclined => 402, ProviderTimedOut => 504, Rejected => 422]
+    grpc [CardDeclined => FailedPrecondition]
+}
+
+define_codes! {
+    /// Delivery of a message
+    #[derive(Debug, PartialEq, Eq, Clone, Copy)]
+    enum Delivery = 122 {
+        /// The message has been delivered
+        Delivered = 1,
+        /// The recipient is unknown
+        RecipientNotFound = 2,
+    }
+    failures [RecipientNotFound]
+    custom_impls [Display]
+}
+
+impl std::fmt::Display for Delivery {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "delivery: {}", self.name())
+    }
+}
+
+#[test]
+fn behaves_like_a_built_in_family() {
+    assert_eq!(Payments::FAMILY, "Payments");
+    assert_eq!(Payments::CardDeclined.code(), 121_002);
+    assert_eq!(Payments::from_code(121_004), Some(Payments::Refunded));
+    assert!(!Payments::CardDeclined.is_success());
+    assert_eq!(Payments::CardDeclined.severity(), Severity::Warning);
+    assert_eq!(Payments::Captured.severity(), Severity::Debug);
+    assert_eq!(
+        Payments::ProviderTimedOut.retry_hint(),
+        RetryHint::WithBackoff
+    );
+    assert_eq!(
+        Payments::VARIANTS
+            .iter()
+            .map(|variant| variant.name)
+            .collect::<Vec<_>>(),
+        [
+            "Captured",
+            "CardDeclined",
+            "ProviderTimedOut",
+            "Refunded",
+            "Rejected",
+            "Pending"
+        ]
+    );
+    assert_eq!(Payments::VARIANTS[4].payload, Some("String"));
+    assert_eq!(
+        CodeSet::<Payments>::from_names(&["Captured", "Rejected"]).len(),
+        2
+    );
+}
+
+#[test]
+#[allow(deprecated)]
+fn keeps_deprecated_names() {
+    assert_eq!(Payments::Refundded.name(), "Refunded");
+    assert_eq!(Payments::Refundded.number(), 4);
+    assert_eq!("Refundded".parse(), Ok(Payments::Refunded));
+}
+
+#[test]
+fn maps_to_http_and_grpc() {
+    assert_eq!(Payments::Captured.http_status(), 200);
+    assert_eq!(Payments::CardDeclined.http_status(), 402);
+    assert_eq!(Payments::Rejected("fraud".into()).http_status(), 422);
+    assert_eq!(Payments::ProviderTimedOut.http_status(), 504);
+    // A failure without a declared status is an internal error whatever its name
+    assert_eq!(Delivery::RecipientNotFound.http_status(), 500);
+    assert_eq!(Delivery::Delivered.http_status(), 200);
+
+    assert_eq!(Payments::Captured.grpc_code(), GrpcCode::Ok);
+    assert_eq!(
+        Payments::CardDeclined.grpc_code(),
+        GrpcCode::FailedPrecondition
+    );
+    assert_eq!(
+        Payments::ProviderTimedOut.grpc_code(),
+        GrpcCode::DeadlineExceeded
+    );
+    assert_eq!(
+        Payments::Rejected("fraud".into()).grpc_code(),
+        GrpcCode::FailedPrecondition
+    );
+    assert_eq!(Delivery::RecipientNotFound.grpc_code(), GrpcCode::Internal);
+}
+
+#[test]
+fn displays_and_parses_variants() {
+    assert_eq!(
+        Payments::CardDeclined.to_string(),
+        "The card has been declined by the issuer"
+    );
+    assert_eq!(
+        Payments::Rejected("fraud".into()).to_string(),
+        "The provider rejected the payment"
+    );
+    assert_eq!(Payments::Pending.to_string(), "Pending");
+    assert_eq!(
+        Delivery::RecipientNotFound.to_string(),
+        "delivery: RecipientNotFound"
+    );
+
+    assert_eq!("ProviderTimedOut".parse(), Ok(Payments::ProviderTimedOut));
+    assert_eq!(
+        "Rejected".parse::<Payments>(),
+        Err(ParseCodeError { family: "Payments" })
+    );
+    assert_eq!(
+        "Unknown".parse::<Delivery>().unwrap_err().to_string(),
+        "not the name of a Delivery code without a payload"
+    );
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn is_an_error() {
+    let error: Box<dyn std::error::Error> = Box::new(Payments::CardDeclined);
+    assert_eq!(
+        error.to_string(),
+        "The card has been declined by the issuer"
+    );
+    assert!(error.downcast_ref::<Payments>().is_some());
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn encodes_like_a_built_in_family() {
+    use custom_codes::codec::Binary;
+
+    let bytes = Payments::Rejected("fraud".into()).encode();
+    assert_eq!(
+        Payments::decode(&bytes).unwrap().0,
+        Payments::Rejected("fraud".into())
+    );
+    assert!(custom_codes::DbOps::decode(&bytes).is_err());
+}
+
+#[cfg(feature = "serde_json")]
+#[test]
+fn serializes_with_the_derives_of_the_enum() {
+    assert_eq!(
+        serde_json::to_string(&Payments::Rejected("fraud".into())).unwrap(),
+        r#"{"Rejected":"fraud"}"#
+    );
+    assert_eq!(
+        serde_json::from_str::<Payments>(r#""Captured""#).unwrap(),
+        Payments::Captured
+    );
+}
@@ -0,0 +1,30 @@
+// Generated by `custom_codes::build`, do not edit
+
+::custom_codes::define_codes! {
+    #[doc = " Outcome of charging a customer"]
+    #[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+    pub enum Billing = 120 {
+        #[doc = " The customer has been charged"]
+        Charged = 1,
+        #[doc = " The card of the customer has been declined"]
+        CardDeclined = 2,
+        #[doc = " The payment provider did not answer"]
+        #[doc = " before the deadline"]
+        ProviderTimedOut = 3,
+    }
+    failures [CardDeclined, ProviderTimedOut]
+    severities [CardDeclined => Warning]
+    retries [ProviderTimedOut => WithBackoff]
+    http [CardDeclined => 402]
+    grpc [CardDeclined => FailedPrecondition]
+}
+
+::custom_codes::define_codes! {
+    #[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+    pub enum Messaging = 121 {
+        Delivered = 1,
+        RecipientNotFound = 2,
+    }
+    failures [RecipientNotFound]
+    http [RecipientNotFound => 404]
+}
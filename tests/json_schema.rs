@@ -0,0 +1,99 @@
+#![cfg(feature = "json-schema")]
+
+use custom_codes::json_schema::{json_schema, openapi_components};
+use custom_codes::*;
+use serde_json::Value;
+
+#[macro_use]
+mod common;
+
+fn alternatives<'a>(schemas: &'a Value, family: &str) -> &'a [Value] {
+    schemas[family]["oneOf"].as_array().unwrap()
+}
+
+/// Every serialized variant matches exactly one alternative of its family
+fn assert_described<T: Code + serde::Serialize>(schemas: &Value, code: &T) {
+    let serialized = serde_json::to_value(code).unwrap();
+    let matching = alternatives(schemas, T::FAMILY)
+        .iter()
+        .filter(|alternative| match &serialized {
+            Value::String(name) => alternative["const"] == *name,
+            Value::Object(tagged) => tagged
+                .keys()
+                .all(|name| alternative["required"][0] == *name.as_str()),
+            _ => false,
+        })
+        .count();
+    assert_eq!(matching, 1, "{} is not described once", serialized);
+}
+
+fn assert_family<T: Code + serde::Serialize>(schemas: &Value) {
+    assert!(!schemas[T::FAMILY]["description"]
+        .as_str()
+        .unwrap()
+        .is_empty());
+    for alternative in alternatives(schemas, T::FAMILY) {
+        assert!(!alternative["description"].as_str().unwrap().is_empty());
+    }
+
+    for code in common::unit_variants::<T>() {
+        assert_described(schemas, &code);
+    }
+}
+
+#[test]
+fn describes_every_unit_variant() {
+    let components = openapi_components();
+    let schemas = &components["schemas"];
+
+    for_each_family!(assert_family, schemas);
+}
+
+#[test]
+fn describes_payloads_and_generic_outcome() {
+    let components = openapi_components();
+    let schemas = &components["schemas"];
+
+    assert_described(schemas, &DbOps::DbList(vec!["users".into()]));
+    assert_described(schemas, &DbOps::FieldList(vec![vec![1]]));
+    assert_described(schemas, &SecOps::KeyTooLong(KeyLength::Bytes64));
+
+    let failure = serde_json::to_value(GenericOutcome::Failure("disk full")).unwrap();
+    assert!(alternatives(schemas, "GenericOutcome")
+        .iter()
+        .any(|alternative| failure
+            .get(alternative["required"][0].as_str().unwrap())
+            .is_some()));
+}
+
+#[test]
+fn deprecated_names_are_accepted_alternatives() {
+    let schema = json_schema();
+    let deprecated: Vec<&Value> = alternatives(&schema["$defs"], "AccessStatus")
+        .iter()
+        .filter(|alternative| alternative["deprecated"] == true)
+        .collect();
+    if cfg!(feature = "legacy-names") {
+        // The misspelled name is the serialized one and the correct spelling its alternative
+        assert!(deprecated.is_empty());
+    } else {
+        assert_eq!(deprecated.len(), 1);
+        assert_eq!(deprecated[0]["const"], "AccpetedRAC");
+
+        let status: AccessStatus = serde_json::from_value(deprecated[0]["const"].clone()).unwrap();
+        assert_eq!(status, AccessStatus::AcceptedRAC);
+    }
+}
+
+#[test]
+fn names_are_the_serialized_ones() {
+    let schema = json_schema();
+    let serialized = serde_json::to_value(AccessStatus::AcceptedRAC).unwrap();
+    let names: Vec<&Value> = alternatives(&schema["$defs"], "AccessStatus")
+        .iter()
+        .map(|alternative| &alternative["const"])
+        .collect();
+    assert!(names.contains(&&serialized));
+    assert!(names.contains(&&Value::from("AcceptedRAC")));
+    assert!(names.contains(&&Value::from("AccpetedRAC")));
+}
@@ -0,0 +1,186 @@
+#![cfg(feature = "alloc")]
+
+use custom_codes::lock::{Change, Lockfile, ParseError, ParseErrorReason};
+use custom_codes::*;
+
+/// Run with `UPDATE_LOCK=1` to lock the families after reviewing their changes
+#[test]
+fn shipped_lockfile_is_compatible_and_up_to_date() {
+    let path = concat!(env!("CARGO_MANIFEST_DIR"), "/custom_codes.lock");
+    if std::env::var_os("UPDATE_LOCK").is_some() {
+        std::fs::write(path, Lockfile::current().to_string()).unwrap();
+    }
+
+    let locked = Lockfile::parse(&std::fs::read_to_string(path).unwrap()).unwrap();
+    let diff = locked.diff(&Lockfile::current());
+    assert!(!diff.is_breaking(), "breaking changes:\n{}", diff);
+    assert!(
+        diff.is_empty(),
+        "custom_codes.lock is out of date, lock the additions with `UPDATE_LOCK=1 cargo test --test lock`:\n{}",
+        diff
+    );
+}
+
+#[test]
+fn reads_back_what_it_writes() {
+    let current = Lockfile::current();
+    let written = current.to_string();
+
+    assert!(written.contains("\n[DbOps = 4]\nRepoCreated = 1\n"));
+    assert!(written.contains("\nFieldList(Vec<Vec<u8>>) = 34\n"));
+    assert!(written.contains("\nAccpetedRAC => AcceptedRAC\n"));
+    assert_eq!(Lockfile::parse(&written).unwrap(), current);
+}
+
+#[test]
+fn reports_breaking_changes_first() {
+    let locked = Lockfile::of(&[Family::of::<DbOps>(), Family::of::<AccessStatus>()]);
+    let mut current = locked.clone();
+    let db_ops = &mut current.families[0];
+    // Inserting a variant without a number would shift the following ones
+    for variant in &mut db_ops.variants[2..] {
+        variant.number += 1;
+    }
+    db_ops.variants[3].payload = Some("String".into());
+    db_ops.variants.pop();
+    current.families[1].aliases.clear();
+    current
+        .families
+        .push(Lockfile::of(&[Family::of::<Outcome>()]).families.remove(0));
+
+    let diff = locked.diff(&current);
+    assert!(diff.is_breaking());
+    assert_eq!(
+        diff.changes[0],
+        Change::VariantRenumbered {
+            family: "DbOps".into(),
+            variant: "RepoAlreadyExists".into(),
+            locked: 3,
+            current: 4,
+        }
+    );
+    assert_eq!(
+        diff.changes
+            .iter()
+            .filter(|change| matches!(change, Change::VariantRemoved { .. }))
+            .count(),
+        1
+    );
+    assert!(diff.changes.contains(&Change::AliasRemoved {
+        family: "AccessStatus".into(),
+        alias: "AccpetedRAC".into(),
+    }));
+    assert_eq!(
+        diff.changes.last(),
+        Some(&Change::FamilyAdded {
+            family: "Outcome".into(),
+            id: 1,
+        })
+    );
+    assert!(diff
+        .to_string()
+        .contains("breaking: DbOps::RepoAlreadyExists was renumbered from 3 to 4\n"));
+    assert!(diff.to_string().ends_with("added: family Outcome = 1\n"));
+}
+
+#[test]
+fn reports_retyped_variants() {
+    let locked = Lockfile::parse("[SecOps = 13]\nKeyTooShort(KeyLength) = 21\n").unwrap();
+    let current = Lockfile::parse("[SecOps = 13]\nKeyTooShort = 21\n").unwrap();
+
+    assert_eq!(
+        locked.diff(&current).to_string(),
+        "breaking: the payload of SecOps::KeyTooShort changed from KeyLength to no payload\n"
+    );
+}
+
+#[test]
+fn accepts_variants_renamed_with_a_deprecated_name() {
+    let locked = Lockfile::parse("[SecOps = 13]\nKeyTooShort(KeyLength) = 21\n").unwrap();
+    let renamed =
+        Lockfile::parse("[SecOps = 13]\nKeyTooSmall(KeyLength) = 21\nKeyTooShort => KeyTooSmall\n")
+            .unwrap();
+    let diff = locked.diff(&renamed);
+    assert!(!diff.is_breaking(), "{}", diff);
+    assert_eq!(
+        diff.to_string(),
+        "added: SecOps::KeyTooSmall = 21\nadded: SecOps::KeyTooShort => KeyTooSmall\n"
+    );
+
+    // The deprecated name must still read the same code
+    let renumbered =
+        Lockfile::parse("[SecOps = 13]\nKeyTooSmall(KeyLength) = 22\nKeyTooShort => KeyTooSmall\n")
+            .unwrap();
+    assert!(locked
+        .diff(&renumbered)
+        .changes
+        .contains(&Change::VariantRemoved {
+            family: "SecOps".into(),
+            variant: "KeyTooShort".into(),
+        }));
+    let retyped =
+        Lockfile::parse("[SecOps = 13]\nKeyTooSmall = 21\nKeyTooShort => KeyTooSmall\n").unwrap();
+    assert!(locked.diff(&retyped).is_breaking());
+}
+
+#[test]
+fn reports_retargeted_deprecated_names() {
+    let locked =
+        Lockfile::parse("[AccessStatus = 1]\nAccepted = 1\nRejected = 2\nAccpeted => Accepted\n")
+            .unwrap();
+    let retargeted =
+        Lockfile::parse("[AccessStatus = 1]\nAccepted = 1\nRejected = 2\nAccpeted => Rejected\n")
+            .unwrap();
+
+    let diff = locked.diff(&retargeted);
+    assert!(diff.is_breaking());
+    assert_eq!(
+        diff.changes,
+        [Change::AliasRetargeted {
+            family: "AccessStatus".into(),
+            alias: "Accpeted".into(),
+            locked: "Accepted".into(),
+            current: "Rejected".into(),
+        }]
+    );
+    assert_eq!(
+        diff.to_string(),
+        "breaking: the deprecated name AccessStatus::Accpeted was retargeted from Accepted to Rejected\n"
+    );
+
+    // Following the rename of its variant keeps the deprecated name compatible
+    let renamed = Lockfile::parse(
+        "[AccessStatus = 1]\nGranted = 1\nRejected = 2\nAccpeted => Granted\nAccepted => Granted\n",
+    )
+    .unwrap();
+    assert!(!locked.diff(&renamed).is_breaking());
+}
+
+#[test]
+fn rejects_invalid_lockfiles() {
+    assert_eq!(
+        Lockfile::parse("# header\nSuccess = 1\n"),
+        Err(ParseError {
+            line: 2,
+            reason: ParseErrorReason::ExpectedFamily,
+        })
+    );
+    assert_eq!(
+        Lockfile::parse("[Outcome = 1]\nSuccess = one\n")
+            .unwrap_err()
+            .reason,
+        ParseErrorReason::InvalidNumber
+    );
+    assert_eq!(
+        Lockfile::parse("[Outcome = 1]\nSuccess\n")
+            .unwrap_err()
+            .reason,
+        ParseErrorReason::InvalidLine
+    );
+    assert_eq!(
+        Lockfile::parse("[Outcome = 1]\n[Outcome = 1]\n")
+            .unwrap_err()
+            .reason,
+        ParseErrorReason::DuplicateFamily("Outcome".into())
+    );
+}
@@ -0,0 +1,168 @@
+#![cfg(feature = "std")]
+
+use custom_codes::metrics::{count, CodeCount, InMemoryRecorder, Recorder};
+use custom_codes::*;
+use std::sync::Arc;
+
+#[test]
+fn counts_concurrently() {
+    let recorder = Arc::new(InMemoryRecorder::new());
+    let threads: Vec<_> = (0..8)
+        .map(|_| {
+            let recorder = Arc::clone(&recorder);
+            std::thread::spawn(move || {
+                for _ in 0..1000 {
+                    count(&*recorder, &DbOps::QueryConflictError);
+                    count(&*recorder, &DbOps::EncounteredErrors("disk full".into()));
+                }
+            })
+        })
+        .collect();
+    for thread in threads {
+        thread.join().unwrap();
+    }
+
+    let snapshot = recorder.snapshot();
+    assert_eq!(snapshot.count(&DbOps::QueryConflictError), 8000);
+    assert_eq!(
+        snapshot.count(&DbOps::EncounteredErrors(String::new())),
+        8000
+    );
+    assert_eq!(snapshot.counts.len(), 2);
+}
+
+#[test]
+fn covers_every_variant_of_every_family() {
+    let recorder = InMemoryRecorder::new();
+    for family in FAMILIES {
+        for variant in family.variants {
+            recorder.record(family, variant);
+        }
+    }
+
+    let snapshot = recorder.snapshot();
+    let variants: usize = FAMILIES.iter().map(|family| family.variants.len()).sum();
+    assert_eq!(snapshot.counts.len(), variants);
+    assert!(snapshot.counts.iter().all(|count| count.count == 1));
+    assert_eq!(snapshot.unknown, 0);
+
+    recorder.reset();
+    assert!(recorder.snapshot().counts.is_empty());
+}
+
+#[test]
+fn counts_families_it_was_not_created_with_as_unknown() {
+    let recorder = InMemoryRecorder::with_families(&[Family::of::<Networking>()]);
+    count(&recorder, &Networking::NetTimedOut);
+    count(&recorder, &AccessStatus::Denied);
+
+    let snapshot = recorder.snapshot();
+    assert_eq!(
+        snapshot.counts,
+        [CodeCount {
+            family: "Networking",
+            name: "NetTimedOut",
+            code: Networking::NetTimedOut.code(),
+            count: 1,
+        }]
+    );
+    assert_eq!(snapshot.unknown, 1);
+}
+
+#[test]
+fn renders_prometheus_text() {
+    let recorder = InMemoryRecorder::new();
+    count(&recorder, &AccessStatus::Denied);
+    count(&recorder, &AccessStatus::Denied);
+
+    assert_eq!(
+        recorder.snapshot().to_prometheus(),
+        "# HELP custom_codes_total Codes emitted by family and variant\n\
+         # TYPE custom_codes_total counter\n\
+         custom_codes_total{family=\"AccessStatus\",name=\"Denied\",code=\"2004\"} 2\n"
+    );
+}
+
+#[cfg(feature = "metrics")]
+mod metrics_crate {
+    use custom_codes::metrics::{count, MetricsRecorder};
+    use custom_codes::Networking;
+    use metrics::{
+        Counter, CounterFn, Gauge, Histogram, Key, KeyName, Metadata, Recorder, SharedString, Unit,
+    };
+    use std::sync::atomic::{AtomicU64, Ordering};
+    use std::sync::{Arc, Mutex};
+
+    #[derive(Default)]
+    struct Total(AtomicU64);
+
+    impl CounterFn for Total {
+        fn increment(&self, value: u64) {
+            self.0.fetch_add(value, Ordering::Relaxed);
+        }
+
+        fn absolute(&self, value: u64) {
+            self.0.store(value, Ordering::Relaxed);
+        }
+    }
+
+    /// Keeps the key and total of every registered counter
+    #[derive(Default)]
+    struct Counters(Mutex<Vec<(Key, Arc<Total>)>>);
+
+    impl Recorder for Counters {
+        fn describe_counter(&self, _: KeyName, _: Option<Unit>, _: SharedString) {}
+        fn describe_gauge(&self, _: KeyName, _: Option<Unit>, _: SharedString) {}
+        fn describe_histogram(&self, _: KeyName, _: Option<Unit>, _: SharedString) {}
+
+        fn register_counter(&self, key: &Key, _: &Metadata<'_>) -> Counter {
+            let mut counters = self.0.lock().unwrap();
+            let total = match counters.iter().find(|(known, _)| known == key) {
+                Some((_, total)) => Arc::clone(total),
+                None => {
+                    let total = Arc::new(Total::default());
+                    counters.push((key.clone(), Arc::clone(&total)));
+                    total
+                }
+            };
+
+            Counter::from_arc(total)
+        }
+
+        fn register_gauge(&self, _: &Key, _: &Metadata<'_>) -> Gauge {
+            Gauge::noop()
+        }
+
+        fn register_histogram(&self, _: &Key, _: &Metadata<'_>) -> Histogram {
+            Histogram::noop()
+        }
+    }
+
+    #[test]
+    fn forwards_to_the_metrics_crate() {
+        let counters = Counters::default();
+        metrics::with_local_recorder(&counters, || {
+            count(&MetricsRecorder, &Networking::NetTimedOut);
+            count(&MetricsRecorder, &Networking::NetTimedOut);
+        });
+
+        let counters = counters.0.lock().unwrap();
+        assert_eq!(counters.len(), 1);
+
+        let (key, total) = &counters[0];
+        assert_eq!(key.name(), "custom_codes_total");
+        let labels: Vec<_> = key
+            .labels()
+            .map(|label| (label.key(), label.value()))
+            .collect();
+        assert_eq!(
+            labels,
+            [
+                ("family", "Networking"),
+                ("name", "NetTimedOut"),
+                ("code", "16016"),
+            ]
+        );
+        assert_eq!(total.0.load(Ordering::Relaxed), 2);
+    }
+}
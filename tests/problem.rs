@@ -0,0 +1,156 @@
+#![cfg(feature = "problem-details")]
+
+use custom_codes::problem::{ProblemDetails, ProblemError, ABOUT_BLANK};
+use custom_codes::*;
+use serde_json::json;
+use std::fmt::Debug;
+
+#[macro_use]
+mod common;
+
+define_codes! {
+    /// Outcome of charging a customer
+    #[derive(Debug, PartialEq, Eq, Clone, serde::Serialize, serde::Deserialize)]
+    enum Billing = 120 {
+        /// The customer has been charged
+        Charged = 1,
+        /// The card of the customer has been declined
+        CardDeclined = 2,
+    }
+    failures [CardDeclined]
+    http [CardDeclined => 402]
+}
+
+fn round_trip<T>(code: &T) -> T
+where
+    T: Code + serde::Serialize + serde::de::DeserializeOwned,
+{
+    let problem = ProblemDetails::from_code(code);
+    let json = serde_json::to_string(&problem).unwrap();
+    let parsed: ProblemDetails = serde_json::from_str(&json).unwrap();
+    assert_eq!(parsed, problem);
+
+    parsed.to_code().unwrap()
+}
+
+fn assert_family<T>()
+where
+    T: Code + serde::Serialize + serde::de::DeserializeOwned + PartialEq + Debug,
+{
+    for code in common::unit_variants::<T>() {
+        let problem = ProblemDetails::from_code(&code);
+        assert!(!problem.title.as_deref().unwrap().is_empty());
+        assert_eq!(problem.extensions["code"], json!(code.code()));
+        assert_eq!(round_trip(&code), code);
+    }
+}
+
+#[test]
+fn unit_variants_round_trip_every_family() {
+    for_each_family!(assert_family);
+}
+
+#[test]
+fn payloads_round_trip() {
+    let codes = [
+        DbOps::DbList(vec!["users".into(), "orders".into()]),
+        DbOps::FieldContents(vec![0, 1, 255]),
+        DbOps::FieldList(vec![vec![1], vec![]]),
+        DbOps::EncounteredErrors("disk full".into()),
+    ];
+    for code in &codes {
+        assert_eq!(&round_trip(code), code);
+    }
+
+    let key_too_short = SecOps::KeyTooShort(KeyLength::Bytes32);
+    let problem = ProblemDetails::from_code(&key_too_short);
+    assert_eq!(problem.status, Some(400));
+    assert_eq!(problem.extensions["payload"], json!("Bytes32"));
+    assert_eq!(round_trip(&key_too_short), key_too_short);
+}
+
+#[test]
+fn serializes_the_standard_members() {
+    let problem = ProblemDetails::from_code(&Cli::EncounteredErrors("bad flag".into()))
+        .with_instance("/jobs/42")
+        .with_extension("trace_id", "abc");
+
+    assert_eq!(
+        serde_json::to_value(&problem).unwrap(),
+        json!({
+            "type": "https://docs.rs/custom_codes/2/custom_codes/enum.Cli.html#variant.EncounteredErrors",
+            "title": problem.title.clone().unwrap(),
+            "status": 500,
+            "detail": "bad flag",
+            "instance": "/jobs/42",
+            "code": 11_u32 * 1000 + Cli::EncounteredErrors(String::new()).number() as u32,
+            "trace_id": "abc",
+        })
+    );
+}
+
+#[test]
+fn rejects_foreign_problems() {
+    let foreign: ProblemDetails =
+        serde_json::from_str(r#"{ "title": "Out of credit", "status": 403 }"#).unwrap();
+    assert_eq!(foreign.type_uri, ABOUT_BLANK);
+    assert!(matches!(
+        foreign.to_code::<DbOps>(),
+        Err(ProblemError::UnknownType(_))
+    ));
+
+    let networking = ProblemDetails::from_code(&Networking::NetTimedOut);
+    assert_eq!(
+        networking.to_code::<DbOps>(),
+        Err(ProblemError::WrongFamily {
+            expected: "DbOps",
+            found: "Networking".into(),
+        })
+    );
+
+    let mut missing = ProblemDetails::from_code(&DbOps::EncounteredErrors("disk full".into()));
+    missing.detail = None;
+    assert_eq!(missing.to_code::<DbOps>(), Err(ProblemError::MissingDetail));
+}
+
+#[test]
+fn links_application_families_to_their_own_documentation() {
+    let problem = ProblemDetails::from_code(&Billing::CardDeclined);
+    assert_eq!(problem.type_uri, ABOUT_BLANK);
+    assert_eq!(problem.status, Some(402));
+    assert_eq!(problem.extensions["code"], json!(120_002));
+
+    let base = "https://example.com/docs/billing/";
+    let problem = ProblemDetails::from_code_under(&Billing::CardDeclined, base);
+    assert_eq!(
+        problem.type_uri,
+        "https://example.com/docs/billing/enum.Billing.html#variant.CardDeclined"
+    );
+    assert_eq!(problem.to_code_under(base), Ok(Billing::CardDeclined));
+    assert!(matches!(
+        problem.to_code::<Billing>(),
+        Err(ProblemError::UnknownType(_))
+    ));
+
+    let error = coded::CodedError::new(Billing::CardDeclined, "expired card");
+    let problem = ProblemDetails::from(&try_downcast_dyn(&error));
+    assert_eq!(problem.type_uri, ABOUT_BLANK);
+    assert_eq!(problem.status, Some(402));
+}
+
+#[test]
+fn describes_downcast_errors() {
+    let error: Box<dyn std::error::Error> = Box::new("x".parse::<u8>().unwrap_err());
+    let problem = ProblemDetails::from(&try_downcast_dyn(error.as_ref()));
+
+    assert_eq!(
+        problem.type_uri,
+        "https://docs.rs/custom_codes/2/custom_codes/enum.DownCastErrors.html#variant.ParseIntError"
+    );
+    assert_eq!(problem.status, Some(400));
+    assert_eq!(problem.detail.as_deref(), Some("InvalidDigit"));
+
+    let not_found = ProblemDetails::from(&DownCastErrors::NotFound);
+    assert_eq!(not_found.status, Some(404));
+    assert_eq!(not_found.detail, None);
+}
@@ -0,0 +1,195 @@
+#![cfg(feature = "alloc")]
+
+use custom_codes::proto::schema;
+
+/// Run with `UPDATE_PROTO=1` to regenerate the shipped schema after changing a code family
+#[test]
+fn shipped_schema_is_up_to_date() {
+    let path = concat!(env!("CARGO_MANIFEST_DIR"), "/proto/custom_codes.proto");
+    if std::env::var_os("UPDATE_PROTO").is_some() {
+        std::fs::write(path, schema()).unwrap();
+    }
+
+    let shipped = std::fs::read_to_string(path).unwrap();
+    assert!(
+        shipped == schema(),
+        "proto/custom_codes.proto is out of date, regenerate it with `UPDATE_PROTO=1 cargo test --test proto`"
+    );
+}
+
+#[cfg(feature = "prost")]
+mod envelope {
+    use custom_codes::codec::{DecodeError, RawCode, WirePayload};
+    use custom_codes::proto::code_envelope::{Code as EnvelopeCode, Detail};
+    use custom_codes::proto::{CodeEnvelope, OtherCode, ProtoError, StringList};
+    use custom_codes::*;
+    use prost::Message;
+
+    /// A payload type without a typed field in the envelope
+    #[derive(Debug, PartialEq, Eq, Clone, Copy)]
+    pub struct Attempts(u8);
+
+    impl WirePayload for Attempts {
+        fn encoded_len(&self) -> usize {
+            1
+        }
+
+        fn write(&self, buffer: &mut Vec<u8>) {
+            buffer.push(self.0);
+        }
+
+        fn read(raw: &RawCode<'_>) -> Result<Self, DecodeError> {
+            match raw.bytes()? {
+                [attempts] => Ok(Attempts(*attempts)),
+                _ => Err(DecodeError::InvalidPayload),
+            }
+        }
+    }
+
+    define_codes! {
+        /// Outcome of a payment
+        #[derive(Debug, PartialEq, Eq, Clone)]
+        pub enum Payments = 121 {
+            /// The payment has been captured
+            Captured = 1,
+            /// The provider rejected the payment
+            Rejected(String) = 2,
+            /// The payment is retried
+            Retrying(Attempts) = 3,
+        }
+        failures [Rejected, Retrying]
+    }
+
+    #[test]
+    fn unit_variants_round_trip_every_family() {
+        fn round_trip<T: codec::Binary + PartialEq + std::fmt::Debug>() {
+            for variant in T::VARIANTS
+                .iter()
+                .filter(|variant| variant.payload.is_none())
+            {
+                let code = T::from_number(variant.number).unwrap();
+                let envelope = CodeEnvelope::from_code(&code).unwrap();
+                let code_field = envelope.code.unwrap();
+                assert_eq!(code_field.family(), T::FAMILY_ID);
+                assert_eq!(code_field.number(), variant.number as i32);
+
+                let decoded = CodeEnvelope::decode(envelope.encode_to_vec().as_slice()).unwrap();
+                assert_eq!(decoded.to_code::<T>().unwrap(), code);
+            }
+        }
+
+        round_trip::<Outcome>();
+        round_trip::<AccessStatus>();
+        round_trip::<CustomBool>();
+        round_trip::<DbOps>();
+        round_trip::<ExecCommand>();
+        round_trip::<FileOps>();
+        round_trip::<Compression>();
+        round_trip::<Subscription>();
+        round_trip::<ActivityStatus>();
+        round_trip::<ActivityToggle>();
+        round_trip::<Cli>();
+        round_trip::<DateTimeOp>();
+        round_trip::<SecOps>();
+        round_trip::<KeyLength>();
+        round_trip::<HardwareResources>();
+        round_trip::<Networking>();
+        round_trip::<SecHardware>();
+    }
+
+    #[test]
+    fn payloads_round_trip() {
+        let codes = [
+            DbOps::DbList(vec!["users".into(), "orders".into()]),
+            DbOps::FieldContents(vec![0, 1, 255]),
+            DbOps::FieldList(vec![vec![], vec![7]]),
+            DbOps::EncounteredErrors("disk full".into()),
+        ];
+        for code in codes.iter() {
+            let envelope = CodeEnvelope::from_code(code).unwrap();
+            let decoded = CodeEnvelope::decode(envelope.encode_to_vec().as_slice()).unwrap();
+            assert_eq!(&decoded.to_code::<DbOps>().unwrap(), code);
+        }
+
+        let envelope = CodeEnvelope::from_code(&SecOps::KeyTooShort(KeyLength::Bytes32)).unwrap();
+        assert_eq!(envelope.detail, Some(Detail::KeySize(4)));
+        assert_eq!(
+            envelope.to_code::<SecOps>(),
+            Ok(SecOps::KeyTooShort(KeyLength::Bytes32))
+        );
+    }
+
+    #[test]
+    fn application_families_round_trip() {
+        let codes = [
+            Payments::Captured,
+            Payments::Rejected("fraud".into()),
+            Payments::Retrying(Attempts(3)),
+        ];
+        for code in codes.iter() {
+            let envelope = CodeEnvelope::from_code(code).unwrap();
+            let decoded = CodeEnvelope::decode(envelope.encode_to_vec().as_slice()).unwrap();
+            assert_eq!(&decoded.to_code::<Payments>().unwrap(), code);
+        }
+
+        let envelope = CodeEnvelope::from_code(&Payments::Retrying(Attempts(3))).unwrap();
+        assert_eq!(
+            envelope.code,
+            Some(EnvelopeCode::Other(OtherCode {
+                family: 121,
+                number: 3
+            }))
+        );
+        assert_eq!(envelope.detail, Some(Detail::Encoded(vec![3])));
+        assert_eq!(
+            envelope.to_code::<DbOps>(),
+            Err(ProtoError::WrongFamily {
+                expected: 4,
+                found: 121
+            })
+        );
+        assert_eq!(
+            CodeEnvelope::from_code(&Payments::Rejected("fraud".into()))
+                .unwrap()
+                .detail,
+            Some(Detail::Text("fraud".into()))
+        );
+    }
+
+    #[test]
+    fn rejects_mismatched_envelopes() {
+        let envelope = CodeEnvelope::from_code(&Networking::NetTimedOut).unwrap();
+        assert_eq!(
+            envelope.to_code::<DbOps>(),
+            Err(ProtoError::WrongFamily {
+                expected: 4,
+                found: 16
+            })
+        );
+
+        let unknown = CodeEnvelope {
+            code: Some(EnvelopeCode::DbOps(0)),
+            detail: None,
+        };
+        assert!(matches!(
+            unknown.to_code::<DbOps>(),
+            Err(ProtoError::UnknownVariant { .. })
+        ));
+
+        let wrong_detail = CodeEnvelope {
+            code: Some(EnvelopeCode::DbOps(
+                DbOps::EncounteredErrors(String::new()).number() as i32,
+            )),
+            detail: Some(Detail::Strings(StringList::default())),
+        };
+        assert_eq!(
+            wrong_detail.to_code::<DbOps>(),
+            Err(ProtoError::InvalidDetail)
+        );
+
+        assert_eq!(
+            CodeEnvelope::default().to_code::<DbOps>(),
+            Err(ProtoError::MissingCode)
+        );
+    }
+}
@@ -0,0 +1,55 @@
+#![cfg(all(feature = "std", feature = "serde"))]
+
+use custom_codes::response::Response;
+use custom_codes::*;
+use serde_json::json;
+
+#[test]
+fn round_trips_every_member() {
+    let response = Response::ok(vec![1, 2, 3])
+        .with_trace_id("4bf92f35")
+        .with_warning("served from cache")
+        .with_timestamp(1_700_000_000_000);
+
+    let json = serde_json::to_value(&response).unwrap();
+    assert_eq!(
+        json,
+        json!({
+            "code": "Success",
+            "message": "Result of Operation completed successfully",
+            "data": [1, 2, 3],
+            "trace_id": "4bf92f35",
+            "timestamp": 1_700_000_000_000_u64,
+            "warnings": ["served from cache"],
+        })
+    );
+    assert_eq!(
+        serde_json::from_value::<Response<Vec<u8>>>(json).unwrap(),
+        response
+    );
+}
+
+#[test]
+fn reads_envelopes_without_optional_members() {
+    let json = r#"{"code":{"EncounteredErrors":"disk full"},"message":"Failed","timestamp":0}"#;
+    let response: Response<String, DbOps> = serde_json::from_str(json).unwrap();
+
+    assert_eq!(response.code, DbOps::EncounteredErrors("disk full".into()));
+    assert_eq!(response.data, None);
+    assert_eq!(response.trace_id, None);
+    assert!(response.warnings.is_empty());
+    assert!(!response.is_success());
+}
+
+#[test]
+fn describes_the_code_and_sets_the_time() {
+    let response = Response::<(), _>::from_code(Networking::NetTimedOut);
+    let variant = Networking::VARIANTS
+        .iter()
+        .find(|variant| variant.name == "NetTimedOut")
+        .unwrap();
+
+    assert_eq!(response.message, variant.description());
+    assert!(response.timestamp > 0);
+    assert_eq!(response.map(|()| 1).data, None);
+}
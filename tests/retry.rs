@@ -0,0 +1,112 @@
+use custom_codes::*;
+
+#[test]
+fn only_failures_are_retryable() {
+    for family in FAMILIES {
+        for variant in family.variants {
+            assert!(
+                !(variant.success && variant.retry.is_retryable()),
+                "{}::{} is a success which can be retried",
+                family.name,
+                variant.name
+            );
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+mod backoff {
+    use custom_codes::retry::{retry, Attempt, Backoff};
+    use custom_codes::*;
+    use std::time::Duration;
+
+    fn backoff(max_attempts: u32) -> Backoff {
+        Backoff {
+            initial: Duration::from_millis(1),
+            max: Duration::from_millis(4),
+            max_attempts,
+            ..Backoff::default()
+        }
+    }
+
+    #[test]
+    fn gives_up_on_errors_which_are_not_retryable() {
+        let mut attempts = 0;
+        let result: Result<(), _> = retry(&backoff(5), |_| {
+            attempts += 1;
+            Err(SecOps::KeyInvalid)
+        });
+
+        assert_eq!(result, Err(SecOps::KeyInvalid));
+        assert_eq!(attempts, 1);
+    }
+
+    #[test]
+    fn stops_after_the_last_attempt() {
+        let mut attempts = Vec::new();
+        let result: Result<(), _> = retry(&backoff(3), |attempt| {
+            attempts.push(*attempt);
+            Err(Networking::ConnectionReset)
+        });
+
+        assert_eq!(result, Err(Networking::ConnectionReset));
+        assert_eq!(
+            attempts,
+            [
+                Attempt {
+                    number: 1,
+                    reconnect: false
+                },
+                Attempt {
+                    number: 2,
+                    reconnect: true
+                },
+                Attempt {
+                    number: 3,
+                    reconnect: true
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn retries_downcast_errors() {
+        let mut attempts = 0;
+        let result = retry(&backoff(5), |_| {
+            attempts += 1;
+            if attempts < 4 {
+                Err(DownCastErrors::Interrupted)
+            } else {
+                Ok("done")
+            }
+        });
+
+        assert!(matches!(result, Ok("done")));
+        assert_eq!(attempts, 4);
+    }
+
+    #[test]
+    fn classifies_downcast_errors() {
+        use std::io::{Error, ErrorKind};
+
+        let hint = |kind| try_downcast_dyn(&Error::from(kind)).retry_hint();
+        assert_eq!(hint(ErrorKind::TimedOut), RetryHint::WithBackoff);
+        assert_eq!(hint(ErrorKind::ConnectionReset), RetryHint::AfterReconnect);
+        // Retrying cannot fix a bind conflict nor a truncated file
+        assert_eq!(hint(ErrorKind::AddrInUse), RetryHint::Never);
+        assert_eq!(hint(ErrorKind::UnexpectedEof), RetryHint::Never);
+
+        // A poisoned lock stays poisoned
+        assert_eq!(DownCastErrors::PoisonError.retry_hint(), RetryHint::Never);
+    }
+
+    #[test]
+    fn jitter_stays_within_half_of_the_delay() {
+        let backoff = Backoff::default();
+        for retry in 0..8 {
+            let delay = backoff.delay(retry);
+            let jittered = backoff.jittered_delay(retry);
+            assert!(jittered >= delay / 2 && jittered <= delay);
+        }
+    }
+}
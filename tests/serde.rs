@@ -0,0 +1,49 @@
+#![cfg(feature = "serde")]
+
+use custom_codes::*;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::fmt::Debug;
+
+#[macro_use]
+mod common;
+
+/// Index based formats such as bincode number the variants as they are declared
+fn round_trip<T: Code + Serialize + DeserializeOwned + PartialEq + Debug>(code: T) {
+    let bytes = bincode::serialize(&code).unwrap();
+    assert_eq!(
+        bincode::deserialize::<T>(&bytes).unwrap(),
+        code,
+        "{}::{}",
+        T::FAMILY,
+        code.name()
+    );
+}
+
+fn round_trip_unit_variants<T: Code + Serialize + DeserializeOwned + PartialEq + Debug>() {
+    common::unit_variants::<T>().for_each(round_trip);
+}
+
+#[test]
+fn every_family_round_trips_through_bincode() {
+    for_each_family!(round_trip_unit_variants);
+
+    round_trip(SecOps::KeyTooShort(KeyLength::Bytes32));
+    #[cfg(feature = "alloc")]
+    {
+        round_trip(DbOps::EncounteredErrors("disk full".into()));
+        round_trip(DbOps::FieldList(vec![vec![0, 255]]));
+    }
+}
+
+#[test]
+#[allow(deprecated)]
+fn deprecated_names_are_their_replacements() {
+    assert_eq!(AccessStatus::AccpetedRAC, AccessStatus::AcceptedRAC);
+    assert_eq!(DbOps::Commited, DbOps::Committed);
+    assert_eq!(SecOps::KeyGenSucceded, SecOps::KeyGenSucceeded);
+    round_trip(HardwareResources::OticalDevChipMissing);
+
+    let status: AccessStatus = serde_json::from_str(r#""AccpetedRAC""#).unwrap();
+    assert_eq!(status, AccessStatus::AccpetedRAC);
+}
@@ -0,0 +1,87 @@
+use custom_codes::*;
+
+const ACCEPTED: CodeSet<AccessStatus> = CodeSet::from_names(&["Granted", "InProgress"]);
+const CPU: CodeSet<HardwareResources> =
+    CodeSet::from_names(&["CpuUnderHeavyLoad", "CpuMaxed", "CpuThrottling"]);
+
+#[test]
+fn builds_sets_in_constants() {
+    assert_eq!(ACCEPTED.len(), 2);
+    assert!(ACCEPTED.contains(&AccessStatus::InProgress));
+    assert_eq!(
+        ACCEPTED,
+        CodeSet::from_numbers(&[
+            AccessStatus::Granted.number(),
+            AccessStatus::InProgress.number()
+        ])
+    );
+    assert_eq!(CodeSet::<Outcome>::all().len(), Outcome::VARIANTS.len());
+    assert!(CodeSet::<Outcome>::new().is_empty());
+}
+
+#[test]
+#[allow(deprecated)]
+fn accepts_deprecated_names() {
+    let set = CodeSet::<AccessStatus>::from_names(&["AccpetedRAC"]);
+
+    assert!(set.contains(&AccessStatus::AcceptedRAC));
+    assert!(set.contains(&AccessStatus::AccpetedRAC));
+    assert_eq!(set.len(), 1);
+}
+
+#[test]
+fn combines_sets() {
+    let mut flags = CodeSet::new();
+    assert!(flags.insert(&HardwareResources::CpuMaxed));
+    assert!(!flags.insert(&HardwareResources::CpuMaxed));
+    flags.extend([HardwareResources::CpuIdle, HardwareResources::EthernetDevUp].iter());
+
+    assert_eq!(
+        (flags & CPU).iter().collect::<Vec<_>>(),
+        [HardwareResources::CpuMaxed]
+    );
+    assert_eq!((flags | CPU).len(), 5);
+    assert_eq!((flags - CPU).len(), 2);
+    assert_eq!((flags ^ CPU).len(), 4);
+    assert!((flags & CPU).is_subset(&CPU));
+    assert!(CPU.is_superset(&(flags & CPU)));
+    assert!((!CPU).is_disjoint(&CPU));
+    assert_eq!((!CPU).len(), HardwareResources::VARIANTS.len() - 3);
+
+    assert!(flags.remove(&HardwareResources::CpuMaxed));
+    assert!(!flags.remove(&HardwareResources::CpuMaxed));
+    assert!(flags.is_disjoint(&CPU));
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn holds_variants_with_a_payload() {
+    let set: CodeSet<DbOps> = vec![
+        DbOps::EncounteredErrors("disk full".into()),
+        DbOps::DocumentNotFound,
+    ]
+    .into_iter()
+    .collect();
+
+    assert!(set.contains(&DbOps::EncounteredErrors(String::new())));
+    assert_eq!(set.iter().collect::<Vec<_>>(), [DbOps::DocumentNotFound]);
+    assert_eq!(set.variants().count(), 2);
+    assert_eq!(
+        format!("{:?}", set),
+        "{DocumentNotFound, EncounteredErrors}"
+    );
+}
+
+#[cfg(feature = "serde_json")]
+#[test]
+fn serializes_as_a_list_of_names() {
+    assert_eq!(
+        serde_json::to_string(&ACCEPTED).unwrap(),
+        r#"["Granted","InProgress"]"#
+    );
+    assert_eq!(
+        serde_json::from_str::<CodeSet<AccessStatus>>(r#"["InProgress","Granted"]"#).unwrap(),
+        ACCEPTED
+    );
+    assert!(serde_json::from_str::<CodeSet<AccessStatus>>(r#"["Unknown"]"#).is_err());
+}
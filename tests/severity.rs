@@ -0,0 +1,78 @@
+use custom_codes::*;
+
+#[test]
+fn failures_are_more_severe_than_successes() {
+    for family in FAMILIES {
+        for variant in family.variants {
+            if variant.success {
+                assert!(
+                    variant.severity <= Severity::Info,
+                    "{}::{} is a success logged at {}",
+                    family.name,
+                    variant.name,
+                    variant.severity
+                );
+            } else {
+                assert!(
+                    variant.severity >= Severity::Notice,
+                    "{}::{} is a failure logged at {}",
+                    family.name,
+                    variant.name,
+                    variant.severity
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn syslog_severities_follow_rfc_5424() {
+    let severities = [
+        (Severity::Alert, 1),
+        (Severity::Critical, 2),
+        (Severity::Error, 3),
+        (Severity::Warning, 4),
+        (Severity::Notice, 5),
+        (Severity::Info, 6),
+        (Severity::Debug, 7),
+        (Severity::Trace, 7),
+    ];
+    for (severity, syslog) in severities {
+        assert_eq!(severity.syslog(), syslog);
+    }
+}
+
+#[cfg(feature = "log")]
+#[test]
+fn maps_to_log_levels() {
+    assert_eq!(log::Level::from(Severity::Notice), log::Level::Info);
+    assert_eq!(log::Level::from(Severity::Warning), log::Level::Warn);
+    assert_eq!(log::Level::from(Severity::Alert), log::Level::Error);
+}
+
+#[cfg(feature = "tracing")]
+#[test]
+fn maps_to_tracing_levels() {
+    assert_eq!(tracing::Level::from(Severity::Trace), tracing::Level::TRACE);
+    assert_eq!(
+        tracing::Level::from(Severity::Critical),
+        tracing::Level::ERROR
+    );
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn overrides_apply_to_every_payload() {
+    use custom_codes::severity::Overrides;
+
+    let overrides = Overrides::new()
+        .with(&DbOps::EncounteredErrors(String::new()), Severity::Critical)
+        .with_numeric(Outcome::Failure.code(), Severity::Warning);
+
+    assert_eq!(
+        overrides.severity(&DbOps::EncounteredErrors("disk full".into())),
+        Severity::Critical
+    );
+    assert_eq!(overrides.severity(&Outcome::Failure), Severity::Warning);
+    assert_eq!(overrides.severity(&Outcome::Success), Severity::Debug);
+}
@@ -0,0 +1,104 @@
+#![cfg(feature = "alloc")]
+
+use custom_codes::summary::{Failure, OutcomeSummary, Policy};
+use custom_codes::*;
+
+fn batch() -> Vec<DbOps> {
+    let mut codes = vec![DbOps::Inserted; 7];
+    codes[2] = DbOps::DocumentAlreadyExists;
+    codes[5] = DbOps::QueryConflictError;
+    codes[6] = DbOps::DocumentAlreadyExists;
+    codes
+}
+
+#[test]
+fn counts_every_variant() {
+    let summary: OutcomeSummary<DbOps> = batch().into_iter().collect();
+
+    assert_eq!(summary.total(), 7);
+    assert_eq!(summary.successes(), 4);
+    assert_eq!(summary.failures(), 3);
+    assert_eq!(summary.count(&DbOps::DocumentAlreadyExists), 2);
+    assert_eq!(summary.count(&DbOps::DocumentNotFound), 0);
+    assert_eq!(
+        summary.counts().collect::<Vec<_>>(),
+        [
+            ("DocumentAlreadyExists", 2),
+            ("Inserted", 4),
+            ("QueryConflictError", 1),
+        ]
+    );
+}
+
+#[test]
+fn keeps_the_first_failures_with_their_keys() {
+    let mut summary = OutcomeSummary::new(Policy::BestEffort).with_max_failures(2);
+    summary.extend(batch());
+    assert_eq!(
+        summary.failed(),
+        [
+            Failure {
+                key: 2,
+                code: DbOps::DocumentAlreadyExists,
+            },
+            Failure {
+                key: 5,
+                code: DbOps::QueryConflictError,
+            },
+        ]
+    );
+    assert_eq!(summary.failures(), 3);
+
+    let keyed: OutcomeSummary<DbOps, &str> = vec![
+        ("users/1", DbOps::Inserted),
+        ("users/2", DbOps::DocumentAlreadyExists),
+    ]
+    .into_iter()
+    .collect();
+    assert_eq!(keyed.failed()[0].key, "users/2");
+}
+
+#[test]
+fn derives_the_outcome_under_a_policy() {
+    let outcome = |policy| {
+        let mut summary = OutcomeSummary::new(policy);
+        summary.extend(batch());
+        summary.outcome()
+    };
+
+    assert_eq!(outcome(Policy::AllOrNothing), Outcome::Failure);
+    assert_eq!(outcome(Policy::BestEffort), Outcome::Success);
+    assert_eq!(outcome(Policy::Threshold(50)), Outcome::Success);
+    assert_eq!(outcome(Policy::Threshold(60)), Outcome::Failure);
+
+    let mut failed = OutcomeSummary::new(Policy::BestEffort);
+    failed.extend(vec![DbOps::DocumentNotFound; 3]);
+    assert_eq!(failed.outcome(), Outcome::Failure);
+
+    let empty = OutcomeSummary::<DbOps>::new(Policy::AllOrNothing);
+    assert_eq!(empty.outcome(), Outcome::Success);
+}
+
+#[cfg(feature = "serde_json")]
+#[test]
+fn serializes_a_report() {
+    let mut summary = OutcomeSummary::new(Policy::Threshold(50)).with_max_failures(1);
+    summary.extend(batch());
+
+    assert_eq!(
+        serde_json::to_value(&summary).unwrap(),
+        serde_json::json!({
+            "policy": { "Threshold": 50 },
+            "outcome": "Success",
+            "total": 7,
+            "successes": 4,
+            "failures": 3,
+            "counts": {
+                "Inserted": 4,
+                "DocumentAlreadyExists": 2,
+                "QueryConflictError": 1,
+            },
+            "failed": [{ "key": 2, "code": "DocumentAlreadyExists" }],
+        })
+    );
+}
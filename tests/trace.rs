@@ -0,0 +1,166 @@
+#![cfg(feature = "tracing")]
+
+use custom_codes::trace::{RecordOutcome, TraceCode};
+use custom_codes::*;
+use std::collections::BTreeMap;
+use std::fmt::Debug;
+use std::sync::{Arc, Mutex};
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id, Record};
+use tracing::{Event, Level, Metadata, Subscriber};
+use tracing_core::span::Current;
+
+type Fields = BTreeMap<String, String>;
+
+/// Keeps the fields of every event and span
+#[derive(Default, Clone)]
+struct Recorder {
+    events: Arc<Mutex<Vec<(Level, Fields)>>>,
+    spans: Arc<Mutex<Vec<Fields>>>,
+    metadata: Arc<Mutex<Vec<&'static Metadata<'static>>>>,
+    entered: Arc<Mutex<Vec<Id>>>,
+}
+
+struct Collect<'a>(&'a mut Fields);
+
+impl Visit for Collect<'_> {
+    fn record_debug(&mut self, field: &Field, value: &dyn Debug) {
+        self.0.insert(field.name().into(), format!("{:?}", value));
+    }
+
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.0.insert(field.name().into(), value.into());
+    }
+}
+
+impl Subscriber for Recorder {
+    fn enabled(&self, _: &Metadata<'_>) -> bool {
+        true
+    }
+
+    fn new_span(&self, span: &Attributes<'_>) -> Id {
+        let mut spans = self.spans.lock().unwrap();
+        let mut fields = Fields::new();
+        span.record(&mut Collect(&mut fields));
+        spans.push(fields);
+        self.metadata.lock().unwrap().push(span.metadata());
+
+        Id::from_u64(spans.len() as u64)
+    }
+
+    fn record(&self, span: &Id, values: &Record<'_>) {
+        let mut spans = self.spans.lock().unwrap();
+        values.record(&mut Collect(&mut spans[span.into_u64() as usize - 1]));
+    }
+
+    fn record_follows_from(&self, _: &Id, _: &Id) {}
+
+    fn event(&self, event: &Event<'_>) {
+        let mut fields = Fields::new();
+        event.record(&mut Collect(&mut fields));
+        self.events
+            .lock()
+            .unwrap()
+            .push((*event.metadata().level(), fields));
+    }
+
+    fn enter(&self, span: &Id) {
+        self.entered.lock().unwrap().push(span.clone());
+    }
+
+    fn exit(&self, _: &Id) {
+        self.entered.lock().unwrap().pop();
+    }
+
+    fn current_span(&self) -> Current {
+        match self.entered.lock().unwrap().last() {
+            Some(span) => {
+                let metadata = self.metadata.lock().unwrap()[span.into_u64() as usize - 1];
+                Current::new(span.clone(), metadata)
+            }
+            None => Current::none(),
+        }
+    }
+}
+
+fn recorded(f: impl FnOnce()) -> Recorder {
+    let recorder = Recorder::default();
+    tracing::subscriber::with_default(recorder.clone(), f);
+
+    recorder
+}
+
+#[test]
+fn emits_events_at_the_severity_of_the_code() {
+    let recorder = recorded(|| {
+        SecOps::PossibleIdTheft.emit();
+        FileOps::ReadDone.emit();
+        custom_codes::trace_code!(DbOps::DocumentNotFound, collection = "users");
+    });
+    let events = recorder.events.lock().unwrap();
+
+    assert_eq!(events[0].0, Level::ERROR);
+    assert_eq!(events[0].1["code.family"], "SecOps");
+    assert_eq!(events[0].1["code.name"], "PossibleIdTheft");
+    assert_eq!(
+        events[0].1["code.num"],
+        SecOps::PossibleIdTheft.code().to_string()
+    );
+    assert_eq!(events[0].1["severity"], "Alert");
+
+    assert_eq!(events[1].0, Level::DEBUG);
+
+    assert_eq!(events[2].0, Level::WARN);
+    assert_eq!(events[2].1["collection"], "users");
+    assert_eq!(events[2].1["message"], "Document does not exist");
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn logging_does_not_count_codes() {
+    use custom_codes::metrics::{record, set_recorder, InMemoryRecorder};
+
+    let counts: &'static InMemoryRecorder = Box::leak(Box::new(InMemoryRecorder::new()));
+    set_recorder(counts).unwrap();
+    recorded(|| {
+        Networking::NetTimedOut.emit();
+        custom_codes::trace_code!(Networking::NetTimedOut);
+    });
+    assert_eq!(counts.snapshot().count(&Networking::NetTimedOut), 0);
+
+    record(&Networking::NetTimedOut);
+    assert_eq!(counts.snapshot().count(&Networking::NetTimedOut), 1);
+}
+
+#[test]
+fn records_the_outcome_of_spans() {
+    let recorder = recorded(|| {
+        let span = tracing::info_span!(
+            "lookup",
+            outcome = tracing::field::Empty,
+            code.family = tracing::field::Empty,
+            code.name = tracing::field::Empty,
+            code.num = tracing::field::Empty,
+        );
+        let _entered = span.enter();
+        DbOps::DocumentNotFound.record_outcome();
+
+        let other = tracing::info_span!("parse", outcome = tracing::field::Empty);
+        "42".parse::<u8>().record_outcome_in(&other).unwrap();
+    });
+    let spans = recorder.spans.lock().unwrap();
+
+    assert_eq!(spans[0]["outcome"], "Failure");
+    assert_eq!(spans[0]["code.family"], "DbOps");
+    assert_eq!(spans[0]["code.name"], "DocumentNotFound");
+    assert_eq!(spans[0]["code.num"], "4025");
+    assert_eq!(spans[1]["outcome"], "Success");
+}
+
+#[test]
+fn records_codes_as_values() {
+    let recorder = recorded(|| tracing::info!(code = %Networking::NetTimedOut.as_value()));
+    let events = recorder.events.lock().unwrap();
+
+    assert_eq!(events[0].1["code"], "Networking::NetTimedOut");
+}
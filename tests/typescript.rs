@@ -0,0 +1,65 @@
+#![cfg(all(feature = "alloc", feature = "serde"))]
+
+use custom_codes::typescript::{constants, definitions};
+use custom_codes::*;
+
+#[macro_use]
+mod common;
+
+/// The members of the union declared as `export type <name> =`
+fn union_members(definitions: &str, name: &str) -> Vec<String> {
+    let start = definitions
+        .find(&format!("export type {} =\n", name))
+        .unwrap();
+    definitions[start..]
+        .lines()
+        .skip(1)
+        .filter(|line| line.starts_with("  | "))
+        .scan(false, |done, line| {
+            if *done {
+                return None;
+            }
+            *done = line.ends_with(';');
+            Some(line["  | ".len()..].trim_end_matches(';').to_string())
+        })
+        .collect()
+}
+
+fn assert_family<T: Code + serde::Serialize>(definitions: &str, constants: &str) {
+    let members = union_members(definitions, T::FAMILY);
+    assert_eq!(members.len(), T::VARIANTS.len());
+
+    for code in common::unit_variants::<T>() {
+        let serialized = serde_json::to_string(&code).unwrap();
+        assert!(
+            members.contains(&serialized),
+            "{} is not declared",
+            serialized
+        );
+
+        let name = serialized.trim_matches('"');
+        assert!(definitions.contains(&format!("  readonly {}: {};\n", name, code.code())));
+        assert!(constants.contains(&format!("  {}: {},\n", name, code.code())));
+    }
+}
+
+#[test]
+fn unions_match_serde() {
+    let definitions = definitions();
+    let constants = constants();
+
+    for_each_family!(assert_family, &definitions, &constants);
+}
+
+#[test]
+fn payloads_are_single_property_objects() {
+    let members = union_members(&definitions(), "SecOps");
+    assert!(members.contains(&"{ KeyTooShort: KeyLength }".to_string()));
+
+    let members = union_members(&definitions(), "DbOps");
+    assert!(members.contains(&"{ FieldList: number[][] }".to_string()));
+    assert_eq!(
+        serde_json::to_string(&DbOps::FieldList(vec![vec![1]])).unwrap(),
+        r#"{"FieldList":[[1]]}"#
+    );
+}